@@ -7,9 +7,15 @@ use axum::{
     response::{IntoResponse, Response},
     routing::get,
 };
+use axum_server::tls_rustls::RustlsConfig;
 use clap::Parser;
 use rust_embed::{Embed, EmbeddedFile};
-use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+use std::{
+    io::IsTerminal,
+    net::SocketAddr,
+    path::{Component, Path as FsPath, PathBuf},
+    sync::Arc,
+};
 use tokio::{
     fs::File,
     io::{AsyncReadExt, AsyncSeekExt},
@@ -28,11 +34,11 @@ struct WebAssets;
 
 #[derive(Parser, Debug)]
 #[command(name = "parquet-viewer-cli")]
-#[command(about = "Serve a local parquet file and open it in parquet-viewer")]
+#[command(about = "Serve local parquet files and open them in parquet-viewer")]
 #[command(version)]
 struct Args {
-    /// Path to the parquet file to serve
-    file: PathBuf,
+    /// Path to the parquet file, or a directory of parquet files, to serve (`-` reads stdin)
+    path: PathBuf,
 
     /// Port to serve the file on
     #[arg(short, long, default_value = "53703")]
@@ -45,18 +51,223 @@ struct Args {
     /// Bind address (default: 0.0.0.0)
     #[arg(short, long, default_value = "0.0.0.0")]
     bind: String,
+
+    /// Serve over HTTPS, e.g. to avoid mixed content warnings from an HTTPS viewer
+    #[arg(long)]
+    tls: bool,
+
+    /// PEM certificate for --tls (default: a generated self-signed certificate)
+    #[arg(long, requires = "tls", requires = "key")]
+    cert: Option<PathBuf>,
+
+    /// PEM private key for --cert
+    #[arg(long, requires = "tls", requires = "cert")]
+    key: Option<PathBuf>,
 }
 
+/// Name the file read from stdin is served under.
+const STDIN_FILE_NAME: &str = "stdin.parquet";
+
 #[derive(Clone)]
 struct AppState {
-    file_path: PathBuf,
-    file_name: String,
+    /// Directory that requested file names are resolved in.
+    root: PathBuf,
+    /// Set when serving a single file, which is then the only one exposed from `root`.
+    only_file: Option<String>,
+    /// `http` or `https`, used to build viewer URLs.
+    scheme: &'static str,
+}
+
+impl AppState {
+    /// Resolves a requested name to a parquet file under `root`, rejecting anything that
+    /// would escape it, including through symlinks.
+    async fn resolve(&self, requested: &str) -> Option<PathBuf> {
+        if self
+            .only_file
+            .as_deref()
+            .is_some_and(|only| only != requested)
+        {
+            return None;
+        }
+        let relative = relative_path(requested)?;
+        // A single file is served whatever its extension, as it was named explicitly
+        if self.only_file.is_none() && !has_parquet_extension(&relative) {
+            return None;
+        }
+        let path = tokio::fs::canonicalize(self.root.join(relative))
+            .await
+            .ok()?;
+        (path.starts_with(&self.root) && path.is_file()).then_some(path)
+    }
+
+    /// Relative paths of the files this server exposes, sorted.
+    fn list_files(&self) -> Vec<String> {
+        match &self.only_file {
+            Some(file_name) => vec![file_name.clone()],
+            None => {
+                let mut files = Vec::new();
+                collect_parquet_files(&self.root, "", &mut files);
+                // Drop symlinks that point outside the root, which `resolve` would refuse
+                files.retain(|file| {
+                    self.root
+                        .join(file)
+                        .canonicalize()
+                        .is_ok_and(|path| path.starts_with(&self.root))
+                });
+                files.sort();
+                files
+            }
+        }
+    }
+}
+
+/// Extensions served from a directory, the same ones the viewer accepts.
+const PARQUET_EXTENSIONS: &[&str] = &["parquet", "parq", "pq"];
+
+/// A `/`-separated relative path, or `None` if it is empty, absolute, or contains anything
+/// other than plain file and directory names.
+fn relative_path(requested: &str) -> Option<PathBuf> {
+    let path = FsPath::new(requested);
+    let plain = path.components().all(|c| matches!(c, Component::Normal(_)));
+    (!requested.is_empty() && plain).then(|| path.to_path_buf())
+}
+
+fn has_parquet_extension(path: &FsPath) -> bool {
+    path.extension().is_some_and(|ext| {
+        PARQUET_EXTENSIONS
+            .iter()
+            .any(|parquet| ext.eq_ignore_ascii_case(parquet))
+    })
+}
+
+fn collect_parquet_files(dir: &FsPath, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let relative = format!("{prefix}{name}");
+        let path = entry.path();
+        if path.is_dir() {
+            collect_parquet_files(&path, &format!("{relative}/"), files);
+        } else if has_parquet_extension(&path) {
+            files.push(relative);
+        }
+    }
+}
+
+/// Percent-encodes each segment of a relative path, keeping the `/` separators.
+fn encode_path(relative: &str) -> String {
+    relative
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Viewer URL that loads `relative` from the server at `host` (`name:port`).
+fn viewer_url(scheme: &str, host: &str, relative: &str) -> String {
+    format!(
+        "{}://{}/?url={}",
+        scheme,
+        host,
+        urlencoding::encode(&format!(
+            "{}://{}/file/{}",
+            scheme,
+            host,
+            encode_path(relative)
+        ))
+    )
+}
+
+/// Buffers stdin into `path`, since serving Range requests needs a seekable file.
+async fn read_stdin_to(path: &FsPath) -> Result<()> {
+    if std::io::stdin().is_terminal() {
+        anyhow::bail!("Expected parquet data piped to stdin");
+    }
+    let mut file = File::create(path)
+        .await
+        .context("Failed to create temp file")?;
+    let size = tokio::io::copy(&mut tokio::io::stdin(), &mut file)
+        .await
+        .context("Failed to read stdin")?;
+    if size == 0 {
+        anyhow::bail!("No data on stdin");
+    }
+    info!("Read {} bytes from stdin", size);
+    Ok(())
+}
+
+/// TLS config from the given PEM files, or a self-signed certificate for `hosts`.
+async fn tls_config(
+    cert: Option<&PathBuf>,
+    key: Option<&PathBuf>,
+    hosts: Vec<String>,
+) -> Result<RustlsConfig> {
+    if let (Some(cert), Some(key)) = (cert, key) {
+        return RustlsConfig::from_pem_file(cert, key)
+            .await
+            .context("Failed to load TLS certificate");
+    }
+    let certified = rcgen::generate_simple_self_signed(hosts)
+        .context("Failed to generate self-signed certificate")?;
+    RustlsConfig::from_pem(
+        certified.cert.pem().into_bytes(),
+        certified.signing_key.serialize_pem().into_bytes(),
+    )
+    .await
+    .context("Failed to load self-signed certificate")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 fn get_asset(path: &str) -> Option<EmbeddedFile> {
     <WebAssets as Embed>::get(path)
 }
 
+/// A plain HTML page linking every served file to the viewer.
+async fn serve_listing(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let Some(host) = headers.get(header::HOST).and_then(|h| h.to_str().ok()) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let listing_state = state.clone();
+    let files = match tokio::task::spawn_blocking(move || listing_state.list_files()).await {
+        Ok(files) => files,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let items: String = files
+        .iter()
+        .map(|file| {
+            format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                escape_html(&viewer_url(state.scheme, host, file)),
+                escape_html(file)
+            )
+        })
+        .collect();
+    let title = escape_html(&state.root.display().to_string());
+    let body = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title></head>\n<body><h1>{title}</h1>\n<p>{} parquet files</p>\n<ul>\n{items}</ul></body></html>\n",
+        files.len()
+    );
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    (StatusCode::OK, response_headers, body).into_response()
+}
+
 async fn serve_embedded(path: Option<Path<String>>) -> Response {
     let path = path.map(|p| p.0).unwrap_or_default();
     let path = if path.is_empty() { "index.html" } else { &path };
@@ -97,22 +308,52 @@ async fn main() -> Result<()> {
 
     let args = Args::parse();
 
-    // Validate the file exists and is a parquet file
-    let file_path = args.file.canonicalize().context("File not found")?;
-    if !file_path.is_file() {
-        anyhow::bail!("Path is not a file: {}", file_path.display());
-    }
+    let scheme = if args.tls { "https" } else { "http" };
 
-    let file_name = file_path
-        .file_name()
-        .context("Could not get file name")?
-        .to_string_lossy()
-        .to_string();
+    // `-` buffers stdin to a temp file, removed again when the server stops
+    let stdin_dir = if args.path.as_os_str() == "-" {
+        let dir = tempfile::tempdir().context("Failed to create temp dir")?;
+        read_stdin_to(&dir.path().join(STDIN_FILE_NAME)).await?;
+        Some(dir)
+    } else {
+        None
+    };
+    let input = match &stdin_dir {
+        Some(dir) => dir.path().join(STDIN_FILE_NAME),
+        None => args.path.clone(),
+    };
 
-    let state = Arc::new(AppState {
-        file_path,
-        file_name: file_name.clone(),
-    });
+    // Serve either a single file, or every parquet file under a directory
+    let path = input.canonicalize().context("Path not found")?;
+    let state = if path.is_dir() {
+        AppState {
+            root: path.clone(),
+            only_file: None,
+            scheme,
+        }
+    } else if path.is_file() {
+        let file_name = path
+            .file_name()
+            .context("Could not get file name")?
+            .to_string_lossy()
+            .to_string();
+        AppState {
+            root: path
+                .parent()
+                .context("File has no parent directory")?
+                .to_path_buf(),
+            only_file: Some(file_name),
+            scheme,
+        }
+    } else {
+        anyhow::bail!("Path is not a file or directory: {}", path.display());
+    };
+
+    let files = state.list_files();
+    let Some(first_file) = files.first().cloned() else {
+        anyhow::bail!("No parquet files found in {}", path.display());
+    };
+    let state = Arc::new(state);
 
     // Setup CORS (needed for parquet file requests)
     let cors = CorsLayer::new()
@@ -125,15 +366,20 @@ async fn main() -> Result<()> {
             header::ACCEPT_RANGES,
         ]);
 
-    // Serve the parquet file under /file/ prefix
+    // Serve the parquet files under /file/ prefix, by their path relative to the root
     let file_routes = Router::new()
-        .route("/{file_name}", get(serve_file).head(serve_file_head))
+        .route("/{*file_path}", get(serve_file).head(serve_file_head))
         .layer(cors)
         .with_state(state.clone());
 
+    let listing_routes = Router::new()
+        .route("/files", get(serve_listing))
+        .with_state(state.clone());
+
     // Serve embedded web assets with gzip compression
     let app = Router::new()
         .nest("/file", file_routes)
+        .merge(listing_routes)
         .route("/", get(|| serve_embedded(None)))
         .route("/{*path}", get(|path| serve_embedded(Some(path))))
         .layer(CompressionLayer::new());
@@ -146,36 +392,40 @@ async fn main() -> Result<()> {
     // Get hostname for convenience URL
     let hostname = gethostname::gethostname().to_string_lossy().to_string();
 
-    let encoded_file_name = urlencoding::encode(&file_name);
+    let tls = if args.tls {
+        let hosts = vec![
+            "localhost".to_string(),
+            "127.0.0.1".to_string(),
+            hostname.clone(),
+            args.bind.clone(),
+        ];
+        Some(tls_config(args.cert.as_ref(), args.key.as_ref(), hosts).await?)
+    } else {
+        None
+    };
+
+    // With several files, point at the listing page rather than picking one
+    let entry_url = |host: &str| {
+        if files.len() > 1 {
+            format!("{}://{}:{}/files", scheme, host, port)
+        } else {
+            viewer_url(scheme, &format!("{}:{}", host, port), &first_file)
+        }
+    };
 
     // Construct viewer URLs for different access methods
-    let file_url_localhost = format!("http://localhost:{}/file/{}", port, encoded_file_name);
-    let viewer_url_bind = format!(
-        "http://{}:{}/?url={}",
+    let viewer_url_bind = entry_url(&args.bind);
+    let viewer_url_localhost = entry_url("localhost");
+    let viewer_url_hostname = entry_url(&hostname);
+
+    info!(
+        "Serving {} parquet file(s) on {}://{}:{}",
+        files.len(),
+        scheme,
         args.bind,
-        port,
-        urlencoding::encode(&format!(
-            "http://{}:{}/file/{}",
-            args.bind, port, encoded_file_name
-        ))
-    );
-    let viewer_url_localhost = format!(
-        "http://localhost:{}/?url={}",
-        port,
-        urlencoding::encode(&file_url_localhost)
-    );
-    let viewer_url_hostname = format!(
-        "http://{}:{}/?url={}",
-        hostname,
-        port,
-        urlencoding::encode(&format!(
-            "http://{}:{}/file/{}",
-            hostname, port, encoded_file_name
-        ))
+        port
     );
 
-    info!("Serving {} on http://{}:{}", file_name, args.bind, port);
-
     if !args.no_open {
         if let Err(e) = open::that(&viewer_url_localhost) {
             tracing::warn!(
@@ -185,14 +435,47 @@ async fn main() -> Result<()> {
         }
     }
 
-    println!("\nServing: {}", state.file_path.display());
+    match stdin_dir {
+        Some(_) => println!("\nServing: stdin"),
+        None => println!("\nServing: {}", path.display()),
+    }
+    if files.len() > 1 {
+        println!("\nFiles ({}):", files.len());
+        for file in &files {
+            println!("  {}", file);
+        }
+    }
     println!("\nViewer URLs:");
     println!("  {}", viewer_url_bind);
     println!("  {}", viewer_url_localhost);
     println!("  {}", viewer_url_hostname);
+    if args.tls && args.cert.is_none() {
+        println!(
+            "\nUsing a self-signed certificate: open {}://localhost:{}/ once and accept it,",
+            scheme, port
+        );
+        println!("otherwise the browser will refuse to load the files.");
+    }
     println!("\nPress Ctrl+C to stop the server.");
 
-    axum::serve(listener, app).await?;
+    let server = async {
+        match tls {
+            Some(config) => {
+                axum_server::from_tcp_rustls(listener.into_std()?, config)?
+                    .serve(app.into_make_service())
+                    .await?
+            }
+            None => axum::serve(listener, app).await?,
+        }
+        anyhow::Ok(())
+    };
+
+    // Return on Ctrl+C rather than being killed, so the stdin temp dir is dropped
+    tokio::select! {
+        result = server => result?,
+        _ = tokio::signal::ctrl_c() => info!("Shutting down"),
+    }
+    drop(stdin_dir);
 
     Ok(())
 }
@@ -201,11 +484,11 @@ async fn serve_file_head(
     State(state): State<Arc<AppState>>,
     Path(requested_file): Path<String>,
 ) -> Response {
-    if urlencoding::decode(&requested_file).unwrap_or_default() != state.file_name {
+    let Some(file_path) = state.resolve(&requested_file).await else {
         return StatusCode::NOT_FOUND.into_response();
-    }
+    };
 
-    let metadata = match tokio::fs::metadata(&state.file_path).await {
+    let metadata = match tokio::fs::metadata(&file_path).await {
         Ok(m) => m,
         Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     };
@@ -226,11 +509,11 @@ async fn serve_file(
     Path(requested_file): Path<String>,
     headers: HeaderMap,
 ) -> Response {
-    if urlencoding::decode(&requested_file).unwrap_or_default() != state.file_name {
+    let Some(file_path) = state.resolve(&requested_file).await else {
         return StatusCode::NOT_FOUND.into_response();
-    }
+    };
 
-    let mut file = match File::open(&state.file_path).await {
+    let mut file = match File::open(&file_path).await {
         Ok(f) => f,
         Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     };
@@ -318,3 +601,40 @@ fn parse_range(range_header: &str, file_size: u64) -> Option<(u64, u64)> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path("year=2024/part-0.parquet"),
+            Some(PathBuf::from("year=2024/part-0.parquet"))
+        );
+        assert!(relative_path("data").is_some());
+        assert!(relative_path("../secret.parquet").is_none());
+        assert!(relative_path("a/../../b.parquet").is_none());
+        assert!(relative_path("/etc/b.parquet").is_none());
+        assert!(relative_path("./a.parquet").is_none());
+        assert!(relative_path("").is_none());
+    }
+
+    #[test]
+    fn test_has_parquet_extension() {
+        assert!(has_parquet_extension(FsPath::new("a/data.parquet")));
+        assert!(has_parquet_extension(FsPath::new("data.PARQUET")));
+        assert!(has_parquet_extension(FsPath::new("data.parq")));
+        assert!(has_parquet_extension(FsPath::new("data.pq")));
+        assert!(!has_parquet_extension(FsPath::new("notes.txt")));
+        assert!(!has_parquet_extension(FsPath::new("data")));
+    }
+
+    #[test]
+    fn test_viewer_url_encodes_segments() {
+        assert_eq!(
+            viewer_url("http", "localhost:53703", "a b/c.parquet"),
+            "http://localhost:53703/?url=http%3A%2F%2Flocalhost%3A53703%2Ffile%2Fa%2520b%2Fc.parquet"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+use crate::storage::{FetchProgress, IoStats};
+use crate::utils::{format_bytes, format_decimal};
+
+const POLL_INTERVAL_MS: u32 = 200;
+
+/// Copies the in-flight progress of `stats` into `progress` until something else sets it
+/// back to `None`, which is how the caller marks the load as done.
+pub(crate) async fn poll_fetch_progress(
+    stats: Arc<IoStats>,
+    mut progress: Signal<Option<FetchProgress>>,
+) {
+    while progress.peek().is_some() {
+        progress.set(Some(stats.fetch_progress()));
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+    }
+}
+
+/// Bytes received of the requests in flight, or a spinner between requests.
+#[component]
+pub fn FetchProgressBar(progress: FetchProgress) -> Element {
+    let file_size = (progress.file_size > 0)
+        .then(|| format!(" · {} file", format_bytes(progress.file_size)))
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "flex items-center gap-3 text-xs opacity-75",
+            match progress.fraction() {
+                Some(fraction) => rsx! {
+                    progress {
+                        class: "progress progress-success w-48",
+                        value: "{progress.received}",
+                        max: "{progress.expected}",
+                    }
+                    span {
+                        "Fetched {format_bytes(progress.received)} of {format_bytes(progress.expected)} ({format_decimal(fraction * 100.0, 0)}%){file_size}"
+                    }
+                },
+                None => rsx! {
+                    span { class: "loading loading-spinner loading-xs" }
+                    span { "Loading...{file_size}" }
+                },
+            }
+        }
+    }
+}
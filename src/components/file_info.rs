@@ -1,8 +1,7 @@
 use dioxus::prelude::*;
 
-use crate::parquet_ctx::MetadataSummary;
-use crate::utils::format_rows;
-use byte_unit::{Byte, UnitType};
+use crate::parquet_ctx::{MetadataSummary, ParquetFeature};
+use crate::utils::{format_bytes, format_decimal, format_rows};
 
 #[component]
 pub fn FileLevelInfo(metadata_summary: MetadataSummary) -> Element {
@@ -17,35 +16,24 @@ pub fn FileLevelInfo(metadata_summary: MetadataSummary) -> Element {
     let has_offset_index = metadata_summary.has_offset_index;
     let has_column_index = metadata_summary.has_column_index;
     let has_row_group_stats = metadata_summary.has_row_group_stats;
+    let page_index_preloaded = metadata_summary.page_index_preloaded;
 
-    let file_size = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.file_size).get_appropriate_unit(UnitType::Binary)
+    let file_size = format_bytes(metadata_summary.file_size);
+    let compressed_row_groups = format_bytes(metadata_summary.compressed_row_group_size);
+    let footer_size = format_bytes(metadata_summary.footer_size);
+    let metadata_memory_size = format_bytes(metadata_summary.metadata_memory_size);
+    let bloom_filter_size = format_bytes(metadata_summary.total_bloom_filter_size);
+    let uncompressed_size = format_bytes(metadata_summary.uncompressed_size);
+    let compression_pct = format!(
+        "{}%",
+        format_decimal(metadata_summary.compression_ratio * 100.0, 2)
     );
-    let compressed_row_groups = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.compressed_row_group_size)
-            .get_appropriate_unit(UnitType::Binary)
-    );
-    let footer_size = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.footer_size).get_appropriate_unit(UnitType::Binary)
-    );
-    let metadata_memory_size = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.metadata_memory_size)
-            .get_appropriate_unit(UnitType::Binary)
-    );
-    let bloom_filter_size = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.total_bloom_filter_size)
-            .get_appropriate_unit(UnitType::Binary)
-    );
-    let uncompressed_size = format!(
-        "{:.2}",
-        Byte::from_u64(metadata_summary.uncompressed_size).get_appropriate_unit(UnitType::Binary)
-    );
-    let compression_pct = format!("{:.2}%", metadata_summary.compression_ratio * 100.0);
+
+    let mut explained_feature = use_signal(|| None::<ParquetFeature>);
+    let mut toggle_feature = move |feature: ParquetFeature| {
+        let next = (explained_feature() != Some(feature)).then_some(feature);
+        explained_feature.set(next);
+    };
 
     let stats_class = if has_row_group_stats {
         "badge badge-success badge-outline"
@@ -122,37 +110,84 @@ pub fn FileLevelInfo(metadata_summary: MetadataSummary) -> Element {
             }
 
             div { class: "grid grid-cols-4 gap-2 text-xs",
-                div { class: "{stats_class}",
-                    if has_row_group_stats {
-                        "✓"
-                    } else {
-                        "✗"
+                div { class: "flex items-center gap-1",
+                    div { class: "{stats_class}",
+                        if has_row_group_stats {
+                            "✓"
+                        } else {
+                            "✗"
+                        }
+                        " Stats"
+                    }
+                    button {
+                        class: "btn btn-ghost btn-xs btn-circle opacity-60",
+                        title: "What is this?",
+                        onclick: move |_| toggle_feature(ParquetFeature::Statistics),
+                        "?"
                     }
-                    " Stats"
                 }
-                div { class: "{page_stats_class}",
-                    if has_column_index {
-                        "✓"
-                    } else {
-                        "✗"
+                div { class: "flex items-center gap-1",
+                    div {
+                        class: "{page_stats_class}",
+                        title: if !page_index_preloaded { "Page index preloading is off in settings" } else { "" },
+                        if !page_index_preloaded {
+                            "?"
+                        } else if has_column_index {
+                            "✓"
+                        } else {
+                            "✗"
+                        }
+                        " Page stats"
+                    }
+                    button {
+                        class: "btn btn-ghost btn-xs btn-circle opacity-60",
+                        title: "What is this?",
+                        onclick: move |_| toggle_feature(ParquetFeature::ColumnIndex),
+                        "?"
                     }
-                    " Page stats"
                 }
-                div { class: "{page_offsets_class}",
-                    if has_offset_index {
-                        "✓"
-                    } else {
-                        "✗"
+                div { class: "flex items-center gap-1",
+                    div {
+                        class: "{page_offsets_class}",
+                        title: if !page_index_preloaded { "Page index preloading is off in settings" } else { "" },
+                        if !page_index_preloaded {
+                            "?"
+                        } else if has_offset_index {
+                            "✓"
+                        } else {
+                            "✗"
+                        }
+                        " Page offsets"
+                    }
+                    button {
+                        class: "btn btn-ghost btn-xs btn-circle opacity-60",
+                        title: "What is this?",
+                        onclick: move |_| toggle_feature(ParquetFeature::OffsetIndex),
+                        "?"
                     }
-                    " Page offsets"
                 }
-                div { class: "{bloom_class}",
-                    if has_bloom_filter {
-                        "✓"
-                    } else {
-                        "✗"
+                div { class: "flex items-center gap-1",
+                    div { class: "{bloom_class}",
+                        if has_bloom_filter {
+                            "✓"
+                        } else {
+                            "✗"
+                        }
+                        " Bloom Filter"
+                    }
+                    button {
+                        class: "btn btn-ghost btn-xs btn-circle opacity-60",
+                        title: "What is this?",
+                        onclick: move |_| toggle_feature(ParquetFeature::BloomFilter),
+                        "?"
                     }
-                    " Bloom Filter"
+                }
+            }
+
+            if let Some(feature) = explained_feature() {
+                div { class: "mt-2 p-2 rounded-md bg-base-200 text-xs",
+                    span { class: "font-medium", "{feature.title()}: " }
+                    "{feature.explain(&metadata_summary)}"
                 }
             }
         }
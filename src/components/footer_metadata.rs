@@ -0,0 +1,303 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use parquet::file::metadata::{ColumnChunkMetaData, ParquetMetaData, RowGroupMetaData};
+use parquet::schema::types::Type;
+
+use crate::utils::copy_to_clipboard;
+
+/// A node of the decoded footer, mirroring the Thrift `FileMetaData` structure.
+#[derive(Clone)]
+struct FooterNode {
+    label: String,
+    value: Option<String>,
+    /// Builds the node's children, so a footer with many row groups and columns only pays
+    /// for the parts that are opened.
+    children: Rc<dyn Fn() -> Vec<FooterNode>>,
+}
+
+impl PartialEq for FooterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+            && self.value == other.value
+            && Rc::ptr_eq(&self.children, &other.children)
+    }
+}
+
+impl FooterNode {
+    fn leaf(label: &str, value: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            value: Some(value.to_string()),
+            children: Rc::new(Vec::new),
+        }
+    }
+
+    fn optional(label: &str, value: Option<impl ToString>) -> Self {
+        Self::leaf(
+            label,
+            value
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "null".to_string()),
+        )
+    }
+
+    fn group(label: impl ToString, children: Vec<FooterNode>) -> Self {
+        Self::lazy_group(label, move || children.clone())
+    }
+
+    fn lazy_group(label: impl ToString, children: impl Fn() -> Vec<FooterNode> + 'static) -> Self {
+        Self {
+            label: label.to_string(),
+            value: None,
+            children: Rc::new(children),
+        }
+    }
+
+    fn write_text(&self, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        match &self.value {
+            Some(value) => out.push_str(&format!("{indent}{}: {value}\n", self.label)),
+            None => out.push_str(&format!("{indent}{}\n", self.label)),
+        }
+        for child in (self.children)() {
+            child.write_text(depth + 1, out);
+        }
+    }
+}
+
+fn schema_element_node(tp: &Type) -> FooterNode {
+    let info = tp.get_basic_info();
+    let mut children = vec![FooterNode::leaf("name", info.name())];
+    if info.has_repetition() {
+        children.push(FooterNode::leaf(
+            "repetition_type",
+            format!("{:?}", info.repetition()),
+        ));
+    }
+    if let Type::PrimitiveType {
+        physical_type,
+        type_length,
+        scale,
+        precision,
+        ..
+    } = tp
+    {
+        children.push(FooterNode::leaf("type", format!("{physical_type:?}")));
+        children.push(FooterNode::leaf("type_length", type_length));
+        children.push(FooterNode::leaf("scale", scale));
+        children.push(FooterNode::leaf("precision", precision));
+    } else {
+        children.push(FooterNode::leaf("num_children", tp.get_fields().len()));
+    }
+    children.push(FooterNode::leaf(
+        "converted_type",
+        format!("{:?}", info.converted_type()),
+    ));
+    children.push(FooterNode::optional(
+        "logical_type",
+        info.logical_type().map(|t| format!("{t:?}")),
+    ));
+    children.push(FooterNode::optional(
+        "field_id",
+        info.has_id().then(|| info.id()),
+    ));
+
+    if tp.is_group() {
+        children.push(FooterNode::group(
+            "children",
+            tp.get_fields()
+                .iter()
+                .map(|f| schema_element_node(f))
+                .collect(),
+        ));
+    }
+    FooterNode::group(info.name(), children)
+}
+
+fn column_chunk_node(index: usize, col: &ColumnChunkMetaData) -> FooterNode {
+    let encodings = col
+        .encodings()
+        .into_iter()
+        .map(|e| format!("{e:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    FooterNode::group(
+        format!("[{index}] {}", col.column_path().string()),
+        vec![
+            FooterNode::optional("file_path", col.file_path()),
+            FooterNode::leaf("file_offset", col.file_offset()),
+            FooterNode::group(
+                "meta_data",
+                vec![
+                    FooterNode::leaf("type", format!("{:?}", col.column_type())),
+                    FooterNode::leaf("encodings", encodings),
+                    FooterNode::leaf("path_in_schema", col.column_path().string()),
+                    FooterNode::leaf("codec", format!("{:?}", col.compression())),
+                    FooterNode::leaf("num_values", col.num_values()),
+                    FooterNode::leaf("total_uncompressed_size", col.uncompressed_size()),
+                    FooterNode::leaf("total_compressed_size", col.compressed_size()),
+                    FooterNode::leaf("data_page_offset", col.data_page_offset()),
+                    FooterNode::optional("index_page_offset", col.index_page_offset()),
+                    FooterNode::optional("dictionary_page_offset", col.dictionary_page_offset()),
+                    FooterNode::optional(
+                        "statistics",
+                        col.statistics().map(|s| format!("{s:?}")),
+                    ),
+                    FooterNode::optional("bloom_filter_offset", col.bloom_filter_offset()),
+                    FooterNode::optional("bloom_filter_length", col.bloom_filter_length()),
+                ],
+            ),
+            FooterNode::optional("offset_index_offset", col.offset_index_offset()),
+            FooterNode::optional("offset_index_length", col.offset_index_length()),
+            FooterNode::optional("column_index_offset", col.column_index_offset()),
+            FooterNode::optional("column_index_length", col.column_index_length()),
+        ],
+    )
+}
+
+fn row_group_node(metadata: &Arc<ParquetMetaData>, index: usize) -> FooterNode {
+    let rg: &RowGroupMetaData = metadata.row_group(index);
+    let columns = {
+        let metadata = metadata.clone();
+        move || {
+            metadata
+                .row_group(index)
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(i, col)| column_chunk_node(i, col))
+                .collect()
+        }
+    };
+    FooterNode::group(
+        format!("[{index}]"),
+        vec![
+            FooterNode::lazy_group(format!("columns ({})", rg.num_columns()), columns),
+            FooterNode::leaf("total_byte_size", rg.total_byte_size()),
+            FooterNode::leaf("num_rows", rg.num_rows()),
+            FooterNode::optional(
+                "sorting_columns",
+                rg.sorting_columns().map(|s| format!("{s:?}")),
+            ),
+            FooterNode::optional("file_offset", rg.file_offset()),
+            FooterNode::leaf("total_compressed_size", rg.compressed_size()),
+            FooterNode::optional("ordinal", rg.ordinal()),
+        ],
+    )
+}
+
+fn file_metadata_tree(metadata: &Arc<ParquetMetaData>) -> FooterNode {
+    let file_metadata = metadata.file_metadata();
+    let key_value_metadata = file_metadata
+        .key_value_metadata()
+        .map(|kvs| {
+            kvs.iter()
+                .map(|kv| FooterNode::optional(&kv.key, kv.value.as_ref()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let row_groups = {
+        let metadata = metadata.clone();
+        move || {
+            (0..metadata.num_row_groups())
+                .map(|i| row_group_node(&metadata, i))
+                .collect()
+        }
+    };
+
+    FooterNode::group(
+        "FileMetaData",
+        vec![
+            FooterNode::leaf("version", file_metadata.version()),
+            FooterNode::group("schema", vec![schema_element_node(file_metadata.schema())]),
+            FooterNode::leaf("num_rows", file_metadata.num_rows()),
+            FooterNode::lazy_group(
+                format!("row_groups ({})", metadata.num_row_groups()),
+                row_groups,
+            ),
+            FooterNode::group(
+                format!("key_value_metadata ({})", key_value_metadata.len()),
+                key_value_metadata,
+            ),
+            FooterNode::optional("created_by", file_metadata.created_by()),
+            FooterNode::optional(
+                "column_orders",
+                file_metadata.column_orders().map(|o| format!("{o:?}")),
+            ),
+        ],
+    )
+}
+
+/// One node of the footer tree. A group renders its children only once its `<details>` has
+/// been opened, and keeps them afterwards so re-opening it is instant.
+#[component]
+fn FooterNodeView(node: FooterNode, open: bool) -> Element {
+    let mut opened = use_signal(|| open);
+
+    match &node.value {
+        Some(value) => rsx! {
+            div { class: "font-mono pl-4",
+                span { class: "opacity-60", "{node.label}: " }
+                span { class: "break-all", "{value}" }
+            }
+        },
+        None => rsx! {
+            details { class: "pl-4", open,
+                summary {
+                    class: "cursor-pointer font-mono font-medium",
+                    onclick: move |_| opened.set(true),
+                    "{node.label}"
+                }
+                if opened() {
+                    for (index , child) in (node.children)().into_iter().enumerate() {
+                        FooterNodeView { key: "{index}", node: child, open: false }
+                    }
+                }
+            }
+        },
+    }
+}
+
+/// Collapsible view of the decoded footer, for diagnosing writer bugs.
+#[component]
+pub fn FooterMetadata(metadata: Arc<ParquetMetaData>) -> Element {
+    let mut expanded = use_signal(|| false);
+
+    rsx! {
+        div { class: "mt-4 space-y-2",
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    onclick: move |_| expanded.set(!expanded()),
+                    if expanded() {
+                        "Hide raw footer"
+                    } else {
+                        "Show raw footer (Thrift FileMetaData)"
+                    }
+                }
+                if expanded() {
+                    button {
+                        class: "btn btn-xs btn-ghost",
+                        title: "Copy footer structure as text",
+                        onclick: {
+                            let metadata = metadata.clone();
+                            move |_| {
+                                let mut text = String::new();
+                                file_metadata_tree(&metadata).write_text(0, &mut text);
+                                copy_to_clipboard(&text);
+                            }
+                        },
+                        "Copy"
+                    }
+                }
+            }
+            if expanded() {
+                div { class: "max-h-[32rem] overflow-auto bg-base-200 rounded-md p-2 -ml-4",
+                    FooterNodeView { node: file_metadata_tree(&metadata), open: true }
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,25 @@
+mod fetch_progress;
 mod file_info;
+mod footer_metadata;
 mod page_info;
+mod query_history;
 mod query_input;
+mod schema_template;
 mod statistics;
 pub mod theme_provider;
 pub mod ui;
 
+pub use fetch_progress::FetchProgressBar;
+pub(crate) use fetch_progress::poll_fetch_progress;
 pub use file_info::FileLevelInfo;
+pub use footer_metadata::FooterMetadata;
 pub use page_info::PageInfo;
+pub use schema_template::SchemaTemplateExport;
 pub use statistics::StatisticsDisplay;
 
+pub use query_history::QueryHistory;
+pub(crate) use query_history::{clear_query_history, load_query_history, record_query};
 pub use query_input::QueryInput;
+pub(crate) use query_input::stored_sql_mode;
 pub mod toast;
 pub use theme_provider::{Theme, use_theme};
@@ -1,140 +1,219 @@
 use std::sync::Arc;
 
+use arrow_cast::display::array_value_to_string;
 use byte_unit::{Byte, UnitType};
 use dioxus::prelude::*;
+use parquet::basic::{Encoding, PageType};
 use parquet::file::page_index::column_index::{
     ByteArrayColumnIndex, ColumnIndexMetaData, PrimitiveColumnIndex,
 };
 
 use crate::{
+    components::ui::{LoadError, Skeleton},
+    page_header::PageCrc,
     parquet_ctx::ParquetResolved,
-    utils::{format_rows, get_column_chunk_page_info},
+    utils::{
+        bytes_display, bytes_to_hex, decode_data_page, format_rows, get_column_chunk_page_info,
+    },
 };
-fn index_display(index: ColumnIndexMetaData) -> Element {
+
+/// Min/max/null-count of a single data page, taken from the column index.
+#[derive(Clone)]
+struct PageStats {
+    min: String,
+    max: String,
+    /// Full min/max shown on hover, for values that are truncated.
+    min_title: String,
+    max_title: String,
+    null_count: String,
+}
+
+fn page_stats(index: &ColumnIndexMetaData) -> Vec<PageStats> {
     match index {
-        ColumnIndexMetaData::NONE => rsx! {
-            div { class: "opacity-60", "No page index available" }
-        },
+        ColumnIndexMetaData::NONE => vec![],
         ColumnIndexMetaData::BOOLEAN(native_index) => {
-            primitive_index_table(native_index, |v: &bool| v.to_string())
+            primitive_page_stats(native_index, |v: &bool| v.to_string())
         }
         ColumnIndexMetaData::INT32(native_index) => {
-            primitive_index_table(native_index, |v: &i32| v.to_string())
+            primitive_page_stats(native_index, |v: &i32| v.to_string())
         }
         ColumnIndexMetaData::INT64(native_index) => {
-            primitive_index_table(native_index, |v: &i64| v.to_string())
+            primitive_page_stats(native_index, |v: &i64| v.to_string())
         }
         ColumnIndexMetaData::INT96(native_index) => {
-            primitive_index_table(native_index, |v: &parquet::data_type::Int96| {
+            primitive_page_stats(native_index, |v: &parquet::data_type::Int96| {
                 format!("{v:?}")
             })
         }
         ColumnIndexMetaData::FLOAT(native_index) => {
-            primitive_index_table(native_index, |v: &f32| format!("{v:.6}"))
+            primitive_page_stats(native_index, |v: &f32| format!("{v:.6}"))
         }
         ColumnIndexMetaData::DOUBLE(native_index) => {
-            primitive_index_table(native_index, |v: &f64| format!("{v:.6}"))
+            primitive_page_stats(native_index, |v: &f64| format!("{v:.6}"))
         }
-        ColumnIndexMetaData::BYTE_ARRAY(native_index) => byte_array_index_table(native_index),
+        ColumnIndexMetaData::BYTE_ARRAY(native_index) => byte_array_page_stats(native_index),
         ColumnIndexMetaData::FIXED_LEN_BYTE_ARRAY(native_index) => {
-            byte_array_index_table(native_index)
+            byte_array_page_stats(native_index)
         }
     }
 }
 
-fn primitive_index_table<T, F>(index: PrimitiveColumnIndex<T>, format_value: F) -> Element
+fn primitive_page_stats<T, F>(index: &PrimitiveColumnIndex<T>, format_value: F) -> Vec<PageStats>
 where
-    T: Clone + 'static,
-    F: Fn(&T) -> String + Copy + 'static,
+    F: Fn(&T) -> String + Copy,
 {
-    let num_pages = index.num_pages() as usize;
-    let min_values: Vec<_> = index.min_values_iter().collect();
-    let max_values: Vec<_> = index.max_values_iter().collect();
+    index
+        .min_values_iter()
+        .zip(index.max_values_iter())
+        .enumerate()
+        .map(|(i, (min, max))| {
+            let min = min.map(format_value).unwrap_or_else(|| "-".to_string());
+            let max = max.map(format_value).unwrap_or_else(|| "-".to_string());
+            PageStats {
+                min_title: min.clone(),
+                max_title: max.clone(),
+                min,
+                max,
+                null_count: index
+                    .null_count(i)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            }
+        })
+        .collect()
+}
 
-    rsx! {
-        div { class: "space-y-2",
-            if num_pages > 0 {
-                div { class: "border border-gray-100 p-2",
-                    div { class: "grid grid-cols-[auto_1fr_1fr_auto] gap-4 opacity-75",
-                        div { "#" }
-                        div { "Min" }
-                        div { "Max" }
-                        div { "Nulls" }
-                    }
-                    div { class: "max-h-32 overflow-y-auto",
-                        for i in 0..num_pages {
-                            {
-                                let min_str = min_values[i].map(format_value).unwrap_or_else(|| "-".to_string());
-                                let max_str = max_values[i].map(format_value).unwrap_or_else(|| "-".to_string());
-                                let null_count_str = index
-                                    .null_count(i)
-                                    .map(|n| n.to_string())
-                                    .unwrap_or_else(|| "-".to_string());
-                                rsx! {
-                                    div { class: "py-1.5 last:border-b-0 hover:bg-base-200",
-                                        div { class: "grid grid-cols-[auto_1fr_1fr_auto] gap-4",
-                                            div { class: "font-mono", "{i + 1}" }
-                                            div { class: "font-mono text-base-content break-all", "{min_str}" }
-                                            div { class: "font-mono text-base-content break-all", "{max_str}" }
-                                            div { class: "font-mono opacity-75", "{null_count_str}" }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            } else {
-                div { class: "opacity-60", "No page data available" }
+fn byte_array_page_stats(index: &ByteArrayColumnIndex) -> Vec<PageStats> {
+    (0..index.num_pages() as usize)
+        .map(|i| {
+            let (min, min_title) = byte_array_stat(index.min_value(i));
+            let (max, max_title) = byte_array_stat(index.max_value(i));
+            PageStats {
+                min,
+                max,
+                min_title,
+                max_title,
+                null_count: index
+                    .null_count(i)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
             }
-        }
+        })
+        .collect()
+}
+
+/// Display text and tooltip of a byte array min/max; binary values are shown as hex.
+fn byte_array_stat(value: Option<&[u8]>) -> (String, String) {
+    match value {
+        Some(v) if std::str::from_utf8(v).is_ok() => (bytes_display(v), bytes_display(v)),
+        Some(v) => (bytes_display(v), bytes_to_hex(v)),
+        None => ("-".to_string(), "-".to_string()),
     }
 }
 
-fn byte_array_index_table(index: ByteArrayColumnIndex) -> Element {
-    let num_pages = index.num_pages() as usize;
+/// Values decoded when previewing a data page.
+const PAGE_PREVIEW_ROWS: usize = 100;
+
+/// Segment color for a page: dictionary pages stand out, and dictionary-encoded data pages are
+/// shaded apart from plainly encoded ones.
+fn page_segment_class(page_type: PageType, encoding: Encoding) -> &'static str {
+    match page_type {
+        PageType::DICTIONARY_PAGE => "bg-warning",
+        PageType::DATA_PAGE | PageType::DATA_PAGE_V2 => match encoding {
+            Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY => "bg-primary",
+            Encoding::PLAIN => "bg-secondary",
+            _ => "bg-accent",
+        },
+        _ => "bg-neutral",
+    }
+}
+
+/// Pages of a column chunk as segments of one bar, each as wide as its share of the page bytes.
+/// Hovering a segment shows the page details; clicking a data page previews it.
+fn page_byte_map(
+    pages: &[crate::utils::PageInfo],
+    byte_range: (u64, u64),
+    selected_page: Option<usize>,
+    on_preview: impl FnMut(usize) + Clone + 'static,
+) -> Element {
+    let total_bytes = pages.iter().map(|page| page.size_bytes).sum::<u64>().max(1);
+    let (start, length) = byte_range;
+    let end = start + length;
+    let mut data_page_idx = 0usize;
+    let segments = pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let data_page = matches!(page.page_type, PageType::DATA_PAGE | PageType::DATA_PAGE_V2)
+                .then(|| {
+                    data_page_idx += 1;
+                    data_page_idx - 1
+                });
+            let size = format!(
+                "{:.0}",
+                Byte::from_u64(page.size_bytes).get_appropriate_unit(UnitType::Binary)
+            );
+            let title = format!(
+                "#{i} {:?}\n{size}, {} values\n{:?}, CRC {}",
+                page.page_type,
+                format_rows(page.num_values as u64),
+                page.encoding,
+                page.crc.label()
+            );
+            let width = page.size_bytes as f64 * 100.0 / total_bytes as f64;
+            let mut class = format!(
+                "{} h-full border-r border-base-100",
+                page_segment_class(page.page_type, page.encoding)
+            );
+            if data_page.is_some() {
+                class.push_str(" cursor-pointer");
+            }
+            if data_page.is_some() && data_page == selected_page {
+                class.push_str(" ring-2 ring-inset ring-base-content");
+            } else {
+                class.push_str(" opacity-80 hover:opacity-100");
+            }
+            (i, width, class, title, data_page)
+        })
+        .collect::<Vec<_>>();
 
     rsx! {
-        div { class: "space-y-2",
-            if num_pages > 0 {
-                div { class: "border border-gray-100 p-2",
-                    div { class: "grid grid-cols-[auto_1fr_1fr_auto] gap-4 opacity-75",
-                        div { "#" }
-                        div { "Min" }
-                        div { "Max" }
-                        div { "Nulls" }
-                    }
-                    div { class: "max-h-32 overflow-y-auto",
-                        for i in 0..num_pages {
-                            {
-                                let min_str = index
-                                    .min_value(i)
-                                    .map(|v| String::from_utf8_lossy(v).to_string())
-                                    .unwrap_or_else(|| "-".to_string());
-                                let max_str = index
-                                    .max_value(i)
-                                    .map(|v| String::from_utf8_lossy(v).to_string())
-                                    .unwrap_or_else(|| "-".to_string());
-                                let null_count_str = index
-                                    .null_count(i)
-                                    .map(|n| n.to_string())
-                                    .unwrap_or_else(|| "-".to_string());
-                                rsx! {
-                                    div { class: "py-1.5 last:border-b-0 hover:bg-base-200",
-                                        div { class: "grid grid-cols-[auto_1fr_1fr_auto] gap-4",
-                                            div { class: "font-mono", "{i + 1}" }
-                                            div { class: "font-mono text-base-content break-all", "{min_str}" }
-                                            div { class: "font-mono text-base-content break-all", "{max_str}" }
-                                            div { class: "font-mono opacity-75", "{null_count_str}" }
-                                        }
-                                    }
+        div { class: "space-y-1",
+            div { class: "flex justify-between opacity-60",
+                span { "Byte map" }
+                span { "bytes {start}–{end}" }
+            }
+            div { class: "flex w-full h-4 rounded overflow-hidden border border-base-300 bg-base-200",
+                for (i , width , class , title , data_page) in segments {
+                    div {
+                        key: "{i}",
+                        class: "{class}",
+                        style: "width: {width}%; min-width: 2px",
+                        title: "{title}",
+                        onclick: {
+                            let mut on_preview = on_preview.clone();
+                            move |_| {
+                                if let Some(data_page) = data_page {
+                                    on_preview(data_page);
                                 }
                             }
-                        }
+                        },
+                    }
+                }
+            }
+            div { class: "flex flex-wrap gap-3 opacity-60",
+                for (label , color) in [
+                    ("Dictionary page", "bg-warning"),
+                    ("Dictionary-encoded data", "bg-primary"),
+                    ("Plain data", "bg-secondary"),
+                    ("Other data encoding", "bg-accent"),
+                ]
+                {
+                    span { class: "flex items-center gap-1",
+                        span { class: "inline-block w-2 h-2 rounded-sm {color}" }
+                        "{label}"
                     }
                 }
-            } else {
-                div { class: "opacity-60", "No page data available" }
             }
         }
     }
@@ -149,68 +228,325 @@ pub fn PageInfo(
     let metadata = parquet_reader.metadata().metadata.clone();
     let row_group_id_value = row_group_id();
     let column_id_value = column_id();
-    let page_index = metadata
-        .column_index()
-        .and_then(|v| v.get(row_group_id_value).map(|v| v.get(column_id_value)))
+    let byte_range = metadata
+        .row_group(row_group_id_value)
+        .column(column_id_value)
+        .byte_range();
+
+    // Cached on the file, so only the first page view of it fetches the index.
+    let page_index_metadata = use_resource({
+        let parquet_reader = parquet_reader.clone();
+        move || {
+            let parquet_reader = parquet_reader.clone();
+            async move {
+                parquet_reader
+                    .page_index_metadata()
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+        }
+    });
+
+    // The column index only has entries for data pages, in page order.
+    let page_index = (page_index_metadata.value())();
+    let data_page_stats = page_index
+        .clone()
+        .and_then(|metadata| metadata.ok())
+        .and_then(|metadata| {
+            metadata
+                .column_index()
+                .and_then(|v| v.get(row_group_id_value).map(|v| v.get(column_id_value)))
+                .flatten()
+                .map(page_stats)
+        })
+        .unwrap_or_default();
+    let has_page_stats = !data_page_stats.is_empty();
+    let page_index_error = page_index.as_ref().and_then(|r| r.as_ref().err()).cloned();
+    let page_index_loading = page_index.is_none();
+
+    // (row group, column, data page) being previewed, so a stale selection is ignored once
+    // another column chunk is shown.
+    let mut preview_page = use_signal(|| None::<(usize, usize, usize)>);
+    let page_preview = use_resource({
+        let parquet_reader = parquet_reader.clone();
+        let metadata = metadata.clone();
+        move || {
+            let reader = parquet_reader.reader().clone();
+            // Prefer the metadata with the offset index, which locates the page without
+            // reading the whole column chunk.
+            let metadata = match (page_index_metadata.value())() {
+                Some(Ok(metadata)) => metadata,
+                _ => metadata.clone(),
+            };
+            let selected = preview_page();
+            async move {
+                let Some((row_group_id, column_id, data_page_index)) = selected else {
+                    return None;
+                };
+                let preview = decode_data_page(
+                    reader,
+                    metadata,
+                    row_group_id,
+                    column_id,
+                    data_page_index,
+                    PAGE_PREVIEW_ROWS,
+                )
+                .await
+                .map_err(|e| e.to_string());
+                Some((selected, preview))
+            }
+        }
+    });
+    let current_preview = (page_preview.value())()
         .flatten()
-        .cloned();
+        .and_then(|(selected, preview)| {
+            let (row_group, column, page) = selected?;
+            (row_group == row_group_id_value && column == column_id_value)
+                .then_some((page, preview))
+        });
+    let selected_page = preview_page()
+        .filter(|&(row_group, column, _)| {
+            row_group == row_group_id_value && column == column_id_value
+        })
+        .map(|(_, _, page)| page);
 
-    let page_info = use_resource(move || {
+    let mut verify_crc = use_signal(|| false);
+    let mut page_info = use_resource(move || {
         let mut column_reader = parquet_reader.reader().clone();
         let metadata = metadata.clone();
+        let (row_group_id, column_id, verify_crc) = (row_group_id(), column_id(), verify_crc());
         async move {
-            get_column_chunk_page_info(&mut column_reader, &metadata, row_group_id(), column_id())
-                .await
-                .unwrap_or_default()
+            get_column_chunk_page_info(
+                &mut column_reader,
+                &metadata,
+                row_group_id,
+                column_id,
+                verify_crc,
+            )
+            .await
+            .map_err(|e| e.to_string())
         }
     });
+    let crc_summary = (page_info.value())()
+        .and_then(|pages| pages.ok())
+        .map(|pages| {
+            let with_crc = pages.iter().filter(|p| p.crc != PageCrc::Missing).count();
+            let mismatches = pages.iter().filter(|p| p.crc == PageCrc::Mismatch).count();
+            if with_crc == 0 {
+                "No page CRC checksums".to_string()
+            } else if mismatches > 0 {
+                format!("{mismatches} of {with_crc} page CRC checksums do not match")
+            } else {
+                format!("CRC checksums on {with_crc} of {} pages", pages.len())
+            }
+        });
 
     rsx! {
         div { class: "col-span-2 space-y-4",
             div { class: "space-y-2",
-                h4 { class: "font-semibold", "Page info" }
-                div { class: "border border-gray-100 p-2",
-                    div { class: "grid grid-cols-[1rem_7rem_4rem_4rem_1fr] gap-3 opacity-75 mb-2",
+                div { class: "flex items-center justify-between gap-2",
+                    h4 { class: "font-semibold", "Pages" }
+                    label {
+                        class: "flex items-center gap-1.5 text-xs cursor-pointer",
+                        title: "Recompute each page's CRC-32 and compare it with the checksum stored in its header",
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox checkbox-xs",
+                            checked: verify_crc(),
+                            onchange: move |ev| verify_crc.set(ev.checked()),
+                        }
+                        "Verify CRCs"
+                    }
+                }
+                if let Some(Ok(pages)) = (page_info.value())() {
+                    {
+                        page_byte_map(
+                            &pages,
+                            byte_range,
+                            selected_page,
+                            move |data_page| {
+                                let selection = (row_group_id_value, column_id_value, data_page);
+                                preview_page.set((preview_page() != Some(selection)).then_some(selection));
+                            },
+                        )
+                    }
+                }
+                div { class: "border border-gray-100 p-2 overflow-x-auto",
+                    div { class: "grid grid-cols-[1.5rem_7rem_4rem_4rem_7rem_1fr_1fr_3rem_4rem_4rem] gap-3 opacity-75 mb-2",
                         span { "#" }
                         span { "Type" }
                         span { "Size" }
                         span { "Rows" }
                         span { "Encoding" }
+                        span { "Min" }
+                        span { "Max" }
+                        span { "Nulls" }
+                        span { "CRC" }
+                        span {}
                     }
-                    div { class: "max-h-32 overflow-y-auto space-y-1",
+                    div { class: "max-h-48 overflow-y-auto space-y-1",
                         match (page_info.value())() {
-                            Some(pages) => rsx! {
-                                for (i , page) in pages.iter().enumerate() {
-                                    div { class: "grid grid-cols-[1rem_7rem_4rem_4rem_1fr] gap-3 hover:bg-base-200",
-                                        span { "{i}" }
-                                        span { "{page.page_type:?}" }
-                                        {
-                                            let size = format!(
-                                                "{:.0}",
-                                                Byte::from_u64(page.size_bytes).get_appropriate_unit(UnitType::Binary),
-                                            );
-                                            rsx! {
-                                                span { "{size}" }
+                            Some(Ok(pages)) => {
+                                let mut data_page_idx = 0usize;
+                                let rows = pages
+                                    .iter()
+                                    .map(|page| {
+                                        let is_data_page = matches!(
+                                            page.page_type,
+                                            PageType::DATA_PAGE | PageType::DATA_PAGE_V2
+                                        );
+                                        let (stats, data_page) = if is_data_page {
+                                            data_page_idx += 1;
+                                            (
+                                                data_page_stats.get(data_page_idx - 1).cloned(),
+                                                Some(data_page_idx - 1),
+                                            )
+                                        } else {
+                                            (None, None)
+                                        };
+                                        (page.clone(), stats, data_page)
+                                    })
+                                    .collect::<Vec<_>>();
+                                rsx! {
+                                    for (i , (page , stats , data_page)) in rows.into_iter().enumerate() {
+                                        div { class: "grid grid-cols-[1.5rem_7rem_4rem_4rem_7rem_1fr_1fr_3rem_4rem_4rem] gap-3 hover:bg-base-200",
+                                            span { "{i}" }
+                                            span { "{page.page_type:?}" }
+                                            {
+                                                let size = format!(
+                                                    "{:.0}",
+                                                    Byte::from_u64(page.size_bytes).get_appropriate_unit(UnitType::Binary),
+                                                );
+                                                rsx! {
+                                                    span { "{size}" }
+                                                }
+                                            }
+                                            span { "{format_rows(page.num_values as u64)}" }
+                                            span { "{page.encoding:?}" }
+                                            match stats {
+                                                Some(stats) => rsx! {
+                                                    span {
+                                                        class: "font-mono break-all",
+                                                        title: "{stats.min_title}",
+                                                        "{stats.min}"
+                                                    }
+                                                    span {
+                                                        class: "font-mono break-all",
+                                                        title: "{stats.max_title}",
+                                                        "{stats.max}"
+                                                    }
+                                                    span { class: "font-mono opacity-75", "{stats.null_count}" }
+                                                },
+                                                None => rsx! {
+                                                    span { class: "opacity-50", "-" }
+                                                    span { class: "opacity-50", "-" }
+                                                    span { class: "opacity-50", "-" }
+                                                },
+                                            }
+                                            span {
+                                                class: match page.crc {
+                                                    PageCrc::Mismatch => "text-error font-semibold",
+                                                    PageCrc::Missing => "opacity-50",
+                                                    _ => "",
+                                                },
+                                                "{page.crc.label()}"
+                                            }
+                                            if let Some(data_page) = data_page {
+                                                button {
+                                                    class: if selected_page == Some(data_page) { "btn btn-xs btn-primary" } else { "btn btn-xs btn-ghost" },
+                                                    title: "Decode and show the values stored in this page",
+                                                    onclick: move |_| {
+                                                        let selection = (row_group_id_value, column_id_value, data_page);
+                                                        preview_page.set((preview_page() != Some(selection)).then_some(selection));
+                                                    },
+                                                    "Preview"
+                                                }
+                                            } else {
+                                                span {}
                                             }
                                         }
-                                        span { "{format_rows(page.num_values as u64)}" }
-                                        span { "{page.encoding:?}" }
+                                    }
+                                }
+                            }
+                            Some(Err(e)) => rsx! {
+                                div { class: "py-2",
+                                    LoadError {
+                                        message: format!("Failed to read pages: {e}"),
+                                        on_retry: move |_| page_info.restart(),
                                     }
                                 }
                             },
                             None => rsx! {
-                                div { class: "flex justify-center items-center py-4",
-                                    div { class: "opacity-60", "Loading page info..." }
+                                for _ in 0..3 {
+                                    Skeleton { class: Some("h-4 w-full".to_string()) }
                                 }
                             },
                         }
                     }
                 }
-            }
-            div { class: "space-y-2",
-                h4 { class: "font-semibold", "Page stats" }
-                if let Some(index) = page_index {
-                    {index_display(index)}
+                if let Some(page) = selected_page {
+                    div { class: "border border-gray-100 p-2 space-y-2",
+                        match current_preview.filter(|(preview_page, _)| *preview_page == page) {
+                            Some((_, Ok(preview))) => {
+                                let column = preview.values.column(0).clone();
+                                let shown = column.len();
+                                let last_row = preview.first_row + preview.num_rows;
+                                rsx! {
+                                    div { class: "flex items-center justify-between gap-2",
+                                        h4 { class: "font-semibold", "Data page {page} values" }
+                                        span { class: "opacity-60",
+                                            if shown < preview.num_rows {
+                                                "First {shown} of {preview.num_rows} rows "
+                                            }
+                                            "(rows {preview.first_row}–{last_row} of the row group)"
+                                        }
+                                    }
+                                    div { class: "max-h-48 overflow-y-auto",
+                                        table { class: "table table-xs",
+                                            thead {
+                                                tr {
+                                                    th { "Row" }
+                                                    th { "{preview.values.schema().field(0).name()}" }
+                                                }
+                                            }
+                                            tbody {
+                                                for row in 0..shown {
+                                                    tr { class: "hover:bg-base-200",
+                                                        td { class: "opacity-60", "{preview.first_row + row}" }
+                                                        if column.is_null(row) {
+                                                            td { class: "opacity-50 italic", "NULL" }
+                                                        } else {
+                                                            td { class: "font-mono break-all",
+                                                                {array_value_to_string(column.as_ref(), row).unwrap_or_default()}
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            Some((_, Err(e))) => rsx! {
+                                LoadError { message: format!("Failed to decode data page {page}: {e}") }
+                            },
+                            None => rsx! {
+                                for _ in 0..3 {
+                                    Skeleton { class: Some("h-4 w-full".to_string()) }
+                                }
+                            },
+                        }
+                    }
+                }
+                if let Some(summary) = crc_summary {
+                    div { class: "opacity-60", "{summary}" }
+                }
+                if page_index_loading {
+                    div { class: "opacity-60", "Loading page index..." }
+                } else if let Some(e) = page_index_error {
+                    LoadError { message: format!("Failed to load page index: {e}") }
+                } else if !has_page_stats {
+                    div { class: "opacity-60", "No page index available" }
                 }
             }
         }
@@ -0,0 +1,141 @@
+use dioxus::prelude::*;
+use serde_json::{Value, json};
+
+use crate::utils::{get_stored_value, save_to_storage};
+
+const QUERY_HISTORY_KEY_PREFIX: &str = "query_history:";
+const MAX_QUERY_HISTORY: usize = 50;
+
+/// A submitted query and the SQL it ran as.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryHistoryEntry {
+    pub query: String,
+    pub sql: String,
+}
+
+/// History is kept per file, so switching datasets shows the queries written for it.
+fn history_key(table_name: &str) -> String {
+    format!("{QUERY_HISTORY_KEY_PREFIX}{table_name}")
+}
+
+fn parse_history(json: &str) -> Vec<QueryHistoryEntry> {
+    let Ok(Value::Array(items)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            Some(QueryHistoryEntry {
+                query: item.get("query")?.as_str()?.to_string(),
+                sql: item.get("sql")?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn history_to_json(entries: &[QueryHistoryEntry]) -> String {
+    Value::Array(
+        entries
+            .iter()
+            .map(|entry| json!({ "query": entry.query, "sql": entry.sql }))
+            .collect(),
+    )
+    .to_string()
+}
+
+/// Puts `entry` first, dropping an earlier run of the same query and the oldest entries past
+/// the cap.
+fn with_entry(
+    mut entries: Vec<QueryHistoryEntry>,
+    entry: QueryHistoryEntry,
+) -> Vec<QueryHistoryEntry> {
+    entries.retain(|existing| existing.query != entry.query);
+    entries.insert(0, entry);
+    entries.truncate(MAX_QUERY_HISTORY);
+    entries
+}
+
+/// Most recent first.
+pub(crate) fn load_query_history(table_name: &str) -> Vec<QueryHistoryEntry> {
+    get_stored_value(&history_key(table_name))
+        .map(|json| parse_history(&json))
+        .unwrap_or_default()
+}
+
+pub(crate) fn record_query(table_name: &str, query: &str, sql: &str) {
+    let entries = with_entry(
+        load_query_history(table_name),
+        QueryHistoryEntry {
+            query: query.to_string(),
+            sql: sql.to_string(),
+        },
+    );
+    save_to_storage(&history_key(table_name), &history_to_json(&entries));
+}
+
+pub(crate) fn clear_query_history(table_name: &str) {
+    save_to_storage(&history_key(table_name), "[]");
+}
+
+/// Past queries on the current file, most recent first. Picking one puts it back in the input.
+#[component]
+pub fn QueryHistory(
+    entries: Vec<QueryHistoryEntry>,
+    on_select: EventHandler<String>,
+    on_clear: EventHandler<()>,
+) -> Element {
+    if entries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        details { class: "text-xs mt-2",
+            summary { class: "cursor-pointer opacity-75 select-none", "History ({entries.len()})" }
+            div { class: "mt-1 space-y-0.5 max-h-60 overflow-auto",
+                for (index , entry) in entries.into_iter().enumerate() {
+                    button {
+                        key: "{index}",
+                        class: "block w-full text-left truncate font-mono px-2 py-1 rounded hover:bg-base-200 cursor-pointer",
+                        title: "{entry.sql}",
+                        onclick: move |_| on_select.call(entry.query.clone()),
+                        "{entry.query}"
+                    }
+                }
+            }
+            button {
+                class: "btn btn-xs btn-ghost mt-1",
+                onclick: move |_| on_clear.call(()),
+                "Clear history"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(query: &str) -> QueryHistoryEntry {
+        QueryHistoryEntry {
+            query: query.to_string(),
+            sql: format!("SELECT '{query}'"),
+        }
+    }
+
+    #[test]
+    fn test_query_history_round_trip_and_cap() {
+        let entries = vec![entry("a"), entry("b")];
+        assert_eq!(parse_history(&history_to_json(&entries)), entries);
+        assert!(parse_history("not json").is_empty());
+
+        let entries = with_entry(entries, entry("b"));
+        assert_eq!(entries, vec![entry("b"), entry("a")]);
+
+        let entries = (0..60).fold(Vec::new(), |entries, i| {
+            with_entry(entries, entry(&i.to_string()))
+        });
+        assert_eq!(entries.len(), MAX_QUERY_HISTORY);
+        assert_eq!(entries[0], entry("59"));
+        assert_eq!(entries.last(), Some(&entry("10")));
+    }
+}
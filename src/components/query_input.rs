@@ -1,41 +1,126 @@
+use std::sync::Arc;
+
 use dioxus::prelude::*;
 
 use crate::components::ui::{BUTTON_PRIMARY, INPUT_BASE};
+use crate::nl_to_sql::{is_sql_input, qualify_table_names, user_input_to_sql};
+use crate::utils::{get_stored_value, save_to_storage};
+use crate::{ParquetResolved, SESSION_CTX};
+
+const SQL_MODE_KEY: &str = "query_sql_mode";
+
+/// Whether the query input was last left in SQL mode.
+pub(crate) fn stored_sql_mode() -> bool {
+    get_stored_value(SQL_MODE_KEY).as_deref() == Some("true")
+}
+
+/// Parses and plans `input` without executing it, returning the error if it is invalid SQL.
+/// Natural language input is not validated; in SQL mode everything is.
+async fn validate_sql(input: &str, table: &ParquetResolved, sql_mode: bool) -> Option<String> {
+    let sql = if sql_mode {
+        if input.trim().is_empty() {
+            return None;
+        }
+        qualify_table_names(input, table)
+    } else if is_sql_input(input) {
+        match user_input_to_sql(input, table).await {
+            Ok(sql) => sql,
+            Err(e) => return Some(e.to_string()),
+        }
+    } else {
+        return None;
+    };
+    SESSION_CTX.sql(&sql).await.err().map(|e| e.to_string())
+}
 
 #[component]
 pub fn QueryInput(
-    value: String,
+    value: ReadSignal<String>,
+    parquet_table: Arc<ParquetResolved>,
+    /// Raw SQL is run as typed, without going through natural language translation.
+    sql_mode: Signal<bool>,
     on_value_change: EventHandler<String>,
     on_user_submit_query: EventHandler<String>,
 ) -> Element {
-    let on_submit_enter = {
-        let value = value.clone();
-        move || on_user_submit_query.call(value.clone())
+    let validation_error = use_resource(move || {
+        let input = value();
+        let sql_mode = sql_mode();
+        let parquet_table = parquet_table.clone();
+        async move { validate_sql(&input, &parquet_table, sql_mode).await }
+    });
+    let validation_error = validation_error.cloned().flatten();
+
+    let on_submit_enter = move || on_user_submit_query.call(value());
+    let on_submit_click = move || on_user_submit_query.call(value());
+
+    let toggle_sql_mode = move |ev: Event<FormData>| {
+        let mut sql_mode = sql_mode;
+        let enabled = ev.checked();
+        sql_mode.set(enabled);
+        save_to_storage(SQL_MODE_KEY, &enabled.to_string());
     };
-    let on_submit_click = {
-        let value = value.clone();
-        move || on_user_submit_query.call(value.clone())
+
+    let input_class = if validation_error.is_some() {
+        format!("flex-1 {INPUT_BASE} input-error")
+    } else {
+        format!("flex-1 {INPUT_BASE}")
     };
 
     rsx! {
         div { class: "flex w-full flex-col gap-2",
-            div { class: "flex w-full flex-col gap-2 sm:flex-row sm:items-center",
-                input {
-                    r#type: "text",
-                    class: "flex-1 {INPUT_BASE}",
-                    value: "{value}",
-                    oninput: move |ev| on_value_change.call(ev.value()),
-                    onkeydown: move |ev| {
-                        if ev.key() == Key::Enter {
-                            on_submit_enter();
-                        }
-                    },
+            div { class: if sql_mode() { "flex w-full flex-col gap-2 sm:flex-row sm:items-start" } else { "flex w-full flex-col gap-2 sm:flex-row sm:items-center" },
+                if sql_mode() {
+                    textarea {
+                        class: "{input_class} font-mono",
+                        rows: "4",
+                        spellcheck: "false",
+                        placeholder: "SELECT * FROM \"{parquet_table.table_name()}\" LIMIT 10",
+                        title: validation_error.clone().unwrap_or_default(),
+                        value: "{value()}",
+                        oninput: move |ev| on_value_change.call(ev.value()),
+                        onkeydown: move |ev| {
+                            let modifiers = ev.modifiers();
+                            let shortcut = modifiers.contains(Modifiers::CONTROL)
+                                || modifiers.contains(Modifiers::META);
+                            if shortcut && ev.key() == Key::Enter {
+                                ev.prevent_default();
+                                on_submit_enter();
+                            }
+                        },
+                    }
+                } else {
+                    input {
+                        r#type: "text",
+                        class: "{input_class}",
+                        title: validation_error.clone().unwrap_or_default(),
+                        value: "{value()}",
+                        oninput: move |ev| on_value_change.call(ev.value()),
+                        onkeydown: move |ev| {
+                            if ev.key() == Key::Enter {
+                                on_submit_enter();
+                            }
+                        },
+                    }
                 }
                 div { class: "flex items-center gap-1",
+                    label { class: "flex items-center gap-1 text-xs whitespace-nowrap cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-xs",
+                            checked: sql_mode(),
+                            onchange: toggle_sql_mode,
+                        }
+                        "SQL"
+                    }
                     button {
                         class: "{BUTTON_PRIMARY}",
+                        title: if sql_mode() { "Run (Ctrl+Enter)" } else { "" },
                         onclick: move |_| on_submit_click(),
-                        "Run Query"
+                        if sql_mode() {
+                            "Run"
+                        } else {
+                            "Run Query"
+                        }
                     }
                     div { class: "relative group",
                         svg {
@@ -52,11 +137,18 @@ pub fn QueryInput(
                             }
                         }
                         div { class: "pointer-events-none absolute bottom-full right-0 mb-2 w-64 rounded bg-gray-800 p-2 text-xs text-white opacity-0 shadow-lg transition-opacity duration-200 group-hover:opacity-100",
-                            "SQL (begin with 'SELECT') or natural language, your choice!"
+                            if sql_mode() {
+                                "SQL mode: the query runs exactly as typed. Press Ctrl+Enter to run."
+                            } else {
+                                "SQL (begin with 'SELECT') or natural language, your choice! Turn on SQL mode for other statements."
+                            }
                         }
                     }
                 }
             }
+            if let Some(error) = validation_error {
+                div { class: "text-xs text-error whitespace-pre-wrap break-words", "{error}" }
+            }
         }
     }
 }
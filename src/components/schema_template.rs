@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::{WriterProperties, WriterVersion};
+
+use crate::parquet_ctx::ParquetResolved;
+use crate::utils::{download_data, empty_parquet_with_schema};
+use crate::views::parquet_rewriter::CompressionChoice;
+
+/// Arrow writes its own copy of the schema; the original one would go stale.
+const ARROW_SCHEMA_META_KEY: &str = "ARROW:schema";
+
+/// Downloads a zero-row parquet file with the loaded file's schema, for use as a contract file.
+#[component]
+pub fn SchemaTemplateExport(parquet_reader: Arc<ParquetResolved>) -> Element {
+    let mut compression = use_signal(CompressionChoice::default);
+    let mut writer_v2 = use_signal(|| false);
+    let mut keep_key_value_metadata = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+
+    let export = move |_| {
+        let summary = parquet_reader.metadata();
+        let mut props = WriterProperties::builder()
+            .set_compression(compression().to_parquet())
+            .set_writer_version(if writer_v2() {
+                WriterVersion::PARQUET_2_0
+            } else {
+                WriterVersion::PARQUET_1_0
+            });
+        if keep_key_value_metadata() {
+            let kvs = summary
+                .metadata
+                .file_metadata()
+                .key_value_metadata()
+                .map(|kvs| {
+                    kvs.iter()
+                        .filter(|kv| kv.key != ARROW_SCHEMA_META_KEY)
+                        .cloned()
+                        .collect::<Vec<KeyValue>>()
+                })
+                .filter(|kvs| !kvs.is_empty());
+            props = props.set_key_value_metadata(kvs);
+        }
+
+        match empty_parquet_with_schema(summary.schema().clone(), props.build()) {
+            Ok(data) => {
+                error.set(None);
+                download_data(
+                    &format!("{}_empty.parquet", parquet_reader.table_name()),
+                    data,
+                );
+            }
+            Err(e) => error.set(Some(format!("{e:#}"))),
+        }
+    };
+
+    rsx! {
+        div { class: "mt-4 space-y-2",
+            div { class: "font-semibold", "Schema template" }
+            p { class: "opacity-75",
+                "Download an empty (zero-row) Parquet file with this schema to validate producers against."
+            }
+            div { class: "flex flex-wrap items-center gap-3",
+                select {
+                    class: "select select-bordered select-xs w-40",
+                    value: "{compression().value()}",
+                    onchange: move |ev| {
+                        if let Some(choice) = CompressionChoice::from_value(&ev.value()) {
+                            compression.set(choice);
+                        }
+                    },
+                    for option in CompressionChoice::all() {
+                        option { value: "{option.value()}", "{option.label()}" }
+                    }
+                }
+                label { class: "flex items-center gap-1.5",
+                    input {
+                        r#type: "checkbox",
+                        class: "checkbox checkbox-xs",
+                        checked: writer_v2(),
+                        onchange: move |ev| writer_v2.set(ev.checked()),
+                    }
+                    "Writer v2"
+                }
+                label { class: "flex items-center gap-1.5",
+                    input {
+                        r#type: "checkbox",
+                        class: "checkbox checkbox-xs",
+                        checked: keep_key_value_metadata(),
+                        onchange: move |ev| keep_key_value_metadata.set(ev.checked()),
+                    }
+                    "Keep key-value metadata"
+                }
+                button { class: "btn btn-xs btn-ghost", onclick: export, "Download empty file" }
+            }
+            if let Some(e) = error() {
+                div { class: "text-red-600 dark:text-red-400 whitespace-pre-wrap", "{e}" }
+            }
+        }
+    }
+}
@@ -1,49 +1,109 @@
-use crate::utils::format_rows;
+use std::sync::Arc;
+
+use crate::utils::{bytes_to_hex, format_rows, format_temporal_value};
+use arrow::array::{Array, AsArray};
+use arrow_cast::display::array_value_to_string;
+use arrow_schema::DataType;
 use dioxus::prelude::*;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::arrow::parquet_to_arrow_schema;
+use parquet::file::metadata::ParquetMetaData;
 use parquet::file::statistics::Statistics;
 
+/// Min and max of a column chunk rendered through the column's Arrow type, so timestamps,
+/// dates and decimals read as values instead of raw physical numbers. `None` for nested
+/// columns and types the statistics converter doesn't support.
+pub(crate) fn typed_min_max(
+    metadata: &ParquetMetaData,
+    row_group_id: usize,
+    column_id: usize,
+) -> Option<(Option<String>, Option<String>)> {
+    let schema_descr = metadata.file_metadata().schema_descr();
+    let [name] = schema_descr.column(column_id).path().parts() else {
+        return None;
+    };
+    let arrow_schema =
+        parquet_to_arrow_schema(schema_descr, metadata.file_metadata().key_value_metadata())
+            .ok()?;
+    let converter = StatisticsConverter::try_new(name, &arrow_schema, schema_descr).ok()?;
+    let row_group = std::iter::once(metadata.row_group(row_group_id));
+    let mins = converter.row_group_mins(row_group.clone()).ok()?;
+    let maxes = converter.row_group_maxes(row_group).ok()?;
+    Some((typed_value(mins.as_ref()), typed_value(maxes.as_ref())))
+}
+
+fn typed_value(array: &dyn Array) -> Option<String> {
+    if array.is_empty() || array.is_null(0) {
+        return None;
+    }
+    let value = match array.data_type() {
+        DataType::Binary => bytes_to_hex(array.as_binary::<i32>().value(0)),
+        DataType::LargeBinary => bytes_to_hex(array.as_binary::<i64>().value(0)),
+        DataType::BinaryView => bytes_to_hex(array.as_binary_view().value(0)),
+        DataType::FixedSizeBinary(_) => bytes_to_hex(array.as_fixed_size_binary().value(0)),
+        _ => format_temporal_value(array, 0).or_else(|| array_value_to_string(array, 0).ok())?,
+    };
+    Some(value)
+}
+
+fn physical_min_max(stats: &Statistics) -> (Option<String>, Option<String>) {
+    match stats {
+        Statistics::Int32(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Int64(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Int96(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Boolean(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Float(s) => (
+            s.min_opt().map(|v| format!("{v:.2}")),
+            s.max_opt().map(|v| format!("{v:.2}")),
+        ),
+        Statistics::Double(s) => (
+            s.min_opt().map(|v| format!("{v:.2}")),
+            s.max_opt().map(|v| format!("{v:.2}")),
+        ),
+        Statistics::ByteArray(s) => (
+            s.min_opt()
+                .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
+            s.max_opt()
+                .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
+        ),
+        Statistics::FixedLenByteArray(s) => (
+            s.min_opt()
+                .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
+            s.max_opt()
+                .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
+        ),
+    }
+}
+
+/// Statistics of one column chunk. Min and max are formatted by the column's Arrow type when
+/// it is known, falling back to the physical values otherwise.
 #[component]
-pub fn StatisticsDisplay(statistics: Option<Statistics>) -> Element {
+pub fn StatisticsDisplay(
+    metadata: Arc<ParquetMetaData>,
+    row_group_id: usize,
+    column_id: usize,
+) -> Element {
+    let statistics = metadata
+        .row_group(row_group_id)
+        .column(column_id)
+        .statistics()
+        .cloned();
     match &statistics {
         Some(stats) => {
-            let (min_val, max_val) = match stats {
-                Statistics::Int32(s) => (
-                    s.min_opt().map(|v| v.to_string()),
-                    s.max_opt().map(|v| v.to_string()),
-                ),
-                Statistics::Int64(s) => (
-                    s.min_opt().map(|v| v.to_string()),
-                    s.max_opt().map(|v| v.to_string()),
-                ),
-                Statistics::Int96(s) => (
-                    s.min_opt().map(|v| v.to_string()),
-                    s.max_opt().map(|v| v.to_string()),
-                ),
-                Statistics::Boolean(s) => (
-                    s.min_opt().map(|v| v.to_string()),
-                    s.max_opt().map(|v| v.to_string()),
-                ),
-                Statistics::Float(s) => (
-                    s.min_opt().map(|v| format!("{v:.2}")),
-                    s.max_opt().map(|v| format!("{v:.2}")),
-                ),
-                Statistics::Double(s) => (
-                    s.min_opt().map(|v| format!("{v:.2}")),
-                    s.max_opt().map(|v| format!("{v:.2}")),
-                ),
-                Statistics::ByteArray(s) => (
-                    s.min_opt()
-                        .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
-                    s.max_opt()
-                        .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
-                ),
-                Statistics::FixedLenByteArray(s) => (
-                    s.min_opt()
-                        .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
-                    s.max_opt()
-                        .and_then(|v| v.as_utf8().ok().map(|s| s.to_string())),
-                ),
-            };
+            let (min_val, max_val) = typed_min_max(&metadata, row_group_id, column_id)
+                .unwrap_or_else(|| physical_min_max(stats));
 
             let null_count = stats.null_count_opt();
             let distinct_count = stats.distinct_count_opt();
@@ -82,3 +142,68 @@ pub fn StatisticsDisplay(statistics: Option<Statistics>) -> Element {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{BinaryArray, Decimal128Array, RecordBatch, TimestampMillisecondArray};
+    use arrow_schema::{Field, Schema, TimeUnit};
+    use bytes::Bytes;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::metadata::ParquetMetaDataReader;
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test_typed_min_max() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("amount", DataType::Decimal128(10, 2), false),
+            Field::new("raw", DataType::Binary, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampMillisecondArray::from(vec![
+                    1_700_000_000_000,
+                    1_600_000_000_000,
+                ])),
+                Arc::new(
+                    Decimal128Array::from(vec![12345, -50])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                Arc::new(BinaryArray::from(vec![&b"\x01\xff"[..], &b"\x00"[..]])),
+            ],
+        )
+        .unwrap();
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&Bytes::from(buf))
+            .unwrap();
+
+        assert_eq!(
+            typed_min_max(&metadata, 0, 0),
+            Some((
+                Some("2020-09-13T12:26:40".to_string()),
+                Some("2023-11-14T22:13:20".to_string())
+            ))
+        );
+        assert_eq!(
+            typed_min_max(&metadata, 0, 1),
+            Some((Some("-0.50".to_string()), Some("123.45".to_string())))
+        );
+        assert_eq!(
+            typed_min_max(&metadata, 0, 2),
+            Some((Some("0x00".to_string()), Some("0x01ff".to_string())))
+        );
+    }
+}
@@ -75,3 +75,30 @@ pub fn SidebarIcon(icon: Element, active: bool, tooltip: String) -> Element {
         div { class: "{class}", title: "{tooltip}", {icon} }
     }
 }
+
+/// Pulsing placeholder shown while an async value is loading
+#[component]
+pub fn Skeleton(class: Option<String>) -> Element {
+    let extra = class.unwrap_or_else(|| "h-4 w-16".to_string());
+
+    rsx! {
+        span { class: "skeleton inline-block align-middle {extra}" }
+    }
+}
+
+/// Inline error for a failed async load, with an optional retry action
+#[component]
+pub fn LoadError(message: String, on_retry: Option<EventHandler<()>>) -> Element {
+    rsx! {
+        span { class: "inline-flex items-baseline gap-1 min-w-0",
+            span { class: "text-error truncate", title: "{message}", "{message}" }
+            if let Some(on_retry) = on_retry {
+                button {
+                    class: "link link-error shrink-0",
+                    onclick: move |_| on_retry.call(()),
+                    "retry"
+                }
+            }
+        }
+    }
+}
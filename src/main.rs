@@ -9,12 +9,14 @@ use views::parquet_rewriter::ParquetRewriter;
 
 mod components;
 mod nl_to_sql;
+mod page_header;
 mod parquet_ctx;
 mod storage;
 #[cfg(test)]
 mod tests;
 mod utils;
 mod views;
+mod worker;
 
 pub(crate) use parquet_ctx::ParquetResolved;
 
@@ -22,6 +24,7 @@ pub(crate) static SESSION_CTX: LazyLock<Arc<SessionContext>> = LazyLock::new(||
     let mut config = SessionConfig::new().with_target_partitions(1);
     config.options_mut().sql_parser.dialect = Dialect::PostgreSQL;
     config.options_mut().execution.parquet.pushdown_filters = true;
+    views::settings::apply_stored_session_config(&mut config);
     Arc::new(SessionContext::new_with_config(config))
 });
 
@@ -81,5 +84,10 @@ fn App() -> Element {
 }
 
 fn main() {
+    // The background worker instantiates this same binary, which runs `main`; it has no page
+    // to render and only serves the tasks posted to it.
+    if web_sys::window().is_none() {
+        return;
+    }
     dioxus::launch(App);
 }
@@ -12,23 +12,61 @@ fn nl_cache(key: &str, file_name: &str) -> Option<String> {
     None
 }
 
+/// Whether the input is SQL rather than natural language.
+pub(crate) fn is_sql_input(input: &str) -> bool {
+    input.starts_with("select") || input.starts_with("SELECT")
+}
+
+/// Points a query written against `from` at `to`, for re-running it on another file.
+/// Natural language input is returned as is, since SQL is generated per file anyway.
+pub(crate) fn retarget_query(query: &str, from: &ParquetResolved, to: &ParquetResolved) -> String {
+    if !is_sql_input(query) {
+        return query.to_string();
+    }
+    let mut sql = query.replace(from.registered_table_name(), to.registered_table_name());
+    for (old, new) in [
+        (
+            format!("\"{}\"", from.table_name()),
+            format!("\"{}\"", to.table_name()),
+        ),
+        (
+            format!(" {} ", from.table_name()),
+            format!(" {} ", to.table_name()),
+        ),
+        (
+            format!(" {}\n", from.table_name()),
+            format!(" {}\n", to.table_name()),
+        ),
+    ] {
+        sql = sql.replace(&old, &new);
+    }
+    if let Some(prefix) = sql.strip_suffix(&format!(" {}", from.table_name())) {
+        sql = format!("{prefix} {}", to.table_name());
+    }
+    sql
+}
+
+/// Replaces the file's table name in `sql` with the name it is registered under.
+pub(crate) fn qualify_table_names(sql: &str, context: &ParquetResolved) -> String {
+    let sql = sql.replace(
+        &format!("\"{}\"", context.table_name()),
+        &format!("\"{}\"", context.registered_table_name()),
+    );
+    // Also handle unquoted table names
+    let sql = sql.replace(
+        &format!(" {} ", context.table_name()),
+        &format!(" \"{}\" ", context.registered_table_name()),
+    );
+    sql.replace(
+        &format!(" {}\n", context.table_name()),
+        &format!(" \"{}\" ", context.registered_table_name()),
+    )
+}
+
 pub(crate) async fn user_input_to_sql(input: &str, context: &ParquetResolved) -> Result<String> {
     // if the input seems to be a SQL query, replace table names with registered names
-    if input.starts_with("select") || input.starts_with("SELECT") {
-        let sql = input.replace(
-            &format!("\"{}\"", context.table_name()),
-            &format!("\"{}\"", context.registered_table_name()),
-        );
-        // Also handle unquoted table names
-        let sql = sql.replace(
-            &format!(" {} ", context.table_name()),
-            &format!(" \"{}\" ", context.registered_table_name()),
-        );
-        let sql = sql.replace(
-            &format!(" {}\n", context.table_name()),
-            &format!(" \"{}\" ", context.registered_table_name()),
-        );
-        return Ok(sql);
+    if is_sql_input(input) {
+        return Ok(qualify_table_names(input, context));
     }
 
     // check if the input is in the cache
@@ -0,0 +1,246 @@
+//! Just enough of the Thrift compact protocol to read the size and CRC of each page header
+//! in a column chunk, which the page reader does not expose.
+
+use anyhow::{Result, anyhow};
+
+/// CRC status of a single page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageCrc {
+    /// The writer did not store a checksum.
+    Missing,
+    /// A checksum is stored but was not verified.
+    Present,
+    Valid,
+    Mismatch,
+}
+
+impl PageCrc {
+    pub fn label(self) -> &'static str {
+        match self {
+            PageCrc::Missing => "-",
+            PageCrc::Present => "present",
+            PageCrc::Valid => "ok",
+            PageCrc::Mismatch => "mismatch",
+        }
+    }
+}
+
+/// The page header fields needed to locate the page body and check its CRC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RawPageHeader {
+    compressed_page_size: i32,
+    crc: Option<i32>,
+}
+
+struct CompactReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CompactReader<'a> {
+    fn byte(&mut self) -> Result<u8> {
+        let b = *self
+            .buf
+            .get(self.pos)
+            .ok_or_else(|| anyhow!("Page header is truncated"))?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            value |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(anyhow!("Varint in page header is too long"))
+    }
+
+    fn zigzag(&mut self) -> Result<i64> {
+        let v = self.varint()?;
+        Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+    }
+
+    fn advance(&mut self, n: usize) -> Result<()> {
+        if self.pos + n > self.buf.len() {
+            return Err(anyhow!("Page header is truncated"));
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Reads a field header, returning `None` at the end of a struct.
+    fn field_header(&mut self, last_id: &mut i16) -> Result<Option<(i16, u8)>> {
+        let b = self.byte()?;
+        if b == 0 {
+            return Ok(None);
+        }
+        let field_type = b & 0x0f;
+        let delta = (b >> 4) as i16;
+        *last_id = if delta == 0 {
+            self.zigzag()? as i16
+        } else {
+            *last_id + delta
+        };
+        Ok(Some((*last_id, field_type)))
+    }
+
+    fn skip(&mut self, field_type: u8) -> Result<()> {
+        match field_type {
+            // Booleans carry their value in the type nibble.
+            1 | 2 => Ok(()),
+            3 => self.advance(1),
+            4..=6 => self.varint().map(|_| ()),
+            7 => self.advance(8),
+            8 => {
+                let len = self.varint()? as usize;
+                self.advance(len)
+            }
+            9 | 10 => {
+                let header = self.byte()?;
+                let size = match header >> 4 {
+                    15 => self.varint()? as usize,
+                    size => size as usize,
+                };
+                let element_type = header & 0x0f;
+                for _ in 0..size {
+                    self.skip_element(element_type)?;
+                }
+                Ok(())
+            }
+            11 => {
+                let size = self.varint()? as usize;
+                if size > 0 {
+                    let types = self.byte()?;
+                    for _ in 0..size {
+                        self.skip_element(types >> 4)?;
+                        self.skip_element(types & 0x0f)?;
+                    }
+                }
+                Ok(())
+            }
+            12 => self.skip_struct(),
+            other => Err(anyhow!("Unknown Thrift type {other} in page header")),
+        }
+    }
+
+    /// Like [`Self::skip`], but booleans inside collections take a byte each.
+    fn skip_element(&mut self, element_type: u8) -> Result<()> {
+        match element_type {
+            1 | 2 => self.advance(1),
+            other => self.skip(other),
+        }
+    }
+
+    fn skip_struct(&mut self) -> Result<()> {
+        let mut last_id = 0;
+        while let Some((_, field_type)) = self.field_header(&mut last_id)? {
+            self.skip(field_type)?;
+        }
+        Ok(())
+    }
+
+    fn page_header(&mut self) -> Result<RawPageHeader> {
+        let mut header = RawPageHeader {
+            compressed_page_size: -1,
+            crc: None,
+        };
+        let mut last_id = 0;
+        while let Some((id, field_type)) = self.field_header(&mut last_id)? {
+            match (id, field_type) {
+                (3, 5) => header.compressed_page_size = self.zigzag()? as i32,
+                (4, 5) => header.crc = Some(self.zigzag()? as i32),
+                _ => self.skip(field_type)?,
+            }
+        }
+        if header.compressed_page_size < 0 {
+            return Err(anyhow!("Page header has no compressed_page_size"));
+        }
+        Ok(header)
+    }
+}
+
+/// CRC-32 (IEEE), the checksum Parquet uses for pages.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Walks the page headers of a column chunk and reports the CRC status of each page, in order.
+/// When `verify` is false, stored checksums are only reported as present.
+pub fn page_crcs(chunk: &[u8], verify: bool) -> Result<Vec<PageCrc>> {
+    let mut reader = CompactReader { buf: chunk, pos: 0 };
+    let mut crcs = Vec::new();
+    while reader.pos < chunk.len() {
+        let header = reader.page_header()?;
+        let body_start = reader.pos;
+        reader.advance(header.compressed_page_size as usize)?;
+        let status = match header.crc {
+            None => PageCrc::Missing,
+            Some(_) if !verify => PageCrc::Present,
+            Some(expected) if crc32(&chunk[body_start..reader.pos]) == expected as u32 => {
+                PageCrc::Valid
+            }
+            Some(_) => PageCrc::Mismatch,
+        };
+        crcs.push(status);
+    }
+    Ok(crcs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A data page header with a 4 byte body, the given CRC bytes and a nested
+    /// `data_page_header` struct, followed by the body.
+    fn page(crc: &[u8], body: &[u8; 4]) -> Vec<u8> {
+        let mut bytes = vec![0x15, 0x00, 0x15, 0x08, 0x15, 0x08];
+        if crc.is_empty() {
+            // data_page_header is field 5, two after compressed_page_size
+            bytes.push(0x2c);
+        } else {
+            bytes.push(0x15);
+            bytes.extend_from_slice(crc);
+            bytes.push(0x1c);
+        }
+        // data_page_header { num_values: 1, encoding: 0 }
+        bytes.extend_from_slice(&[0x15, 0x02, 0x15, 0x00, 0x00, 0x00]);
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    #[test]
+    fn test_page_crcs() {
+        // zigzag varint of crc32("abcd") as i32
+        let crc = [0xdd, 0xcb, 0xe9, 0xa7, 0x02];
+        assert_eq!(crc32(b"abcd"), 0xed82_cd11);
+
+        let mut chunk = page(&crc, b"abcd");
+        chunk.extend(page(&crc, b"abce"));
+        chunk.extend(page(&[], b"abcd"));
+
+        assert_eq!(
+            page_crcs(&chunk, true).unwrap(),
+            vec![PageCrc::Valid, PageCrc::Mismatch, PageCrc::Missing]
+        );
+        assert_eq!(
+            page_crcs(&chunk, false).unwrap(),
+            vec![PageCrc::Present, PageCrc::Present, PageCrc::Missing]
+        );
+        assert!(page_crcs(&chunk[..chunk.len() - 1], true).is_err());
+    }
+}
@@ -1,15 +1,34 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeSet,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::Result;
 use arrow_schema::SchemaRef;
 use byte_unit::{Byte, UnitType};
+use bytes::Bytes;
+use datafusion::datasource::MemTable;
 use datafusion::execution::object_store::ObjectStoreUrl;
-use object_store::path::Path;
+use datafusion::prelude::SessionContext;
+use futures::TryStreamExt;
+use object_store::{ObjectStore, memory::InMemory, path::Path};
 use parquet::{
-    arrow::{async_reader::ParquetObjectReader, parquet_to_arrow_schema},
+    arrow::{
+        ParquetRecordBatchStreamBuilder, arrow_reader::ArrowReaderMetadata,
+        async_reader::ParquetObjectReader, parquet_to_arrow_schema,
+    },
     file::{metadata::ParquetMetaData, page_index::column_index::ColumnIndexMetaData},
 };
 
+use crate::storage::IoStats;
+use crate::utils::{format_bytes, format_rows, load_page_index};
+use crate::views::parquet_reader::ParquetUnresolved;
+
+pub(crate) const IN_MEMORY_SCHEME: &str = "memory";
+/// Largest file that can be loaded into memory with [`ParquetResolved::load_into_memory`].
+pub(crate) const MAX_IN_MEMORY_FILE_SIZE: u64 = 512 * 1024 * 1024;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct MetadataSummary {
     pub file_size: u64,
@@ -22,6 +41,9 @@ pub struct MetadataSummary {
     pub has_row_group_stats: bool,
     pub has_column_index: bool,
     pub has_offset_index: bool,
+    /// Whether the column/offset indexes were fetched along with the footer.
+    /// When `false`, `has_column_index` and `has_offset_index` are unknown.
+    pub page_index_preloaded: bool,
     pub has_bloom_filter: bool,
     pub total_bloom_filter_size: u64,
     pub schema: SchemaRef,
@@ -36,6 +58,7 @@ impl MetadataSummary {
         metadata_memory_size: u64,
         file_size: u64,
         footer_size: u64,
+        page_index_preloaded: bool,
     ) -> Result<Self> {
         let compressed_row_group_size = metadata
             .row_groups()
@@ -97,6 +120,7 @@ impl MetadataSummary {
                 .unwrap_or(false),
             has_column_index,
             has_offset_index,
+            page_index_preloaded,
             has_bloom_filter,
             total_bloom_filter_size,
             schema: Arc::new(schema),
@@ -111,6 +135,78 @@ impl MetadataSummary {
     }
 }
 
+/// Optional Parquet metadata features whose presence changes how queries read a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParquetFeature {
+    Statistics,
+    ColumnIndex,
+    OffsetIndex,
+    BloomFilter,
+}
+
+impl ParquetFeature {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            ParquetFeature::Statistics => "Row group statistics",
+            ParquetFeature::ColumnIndex => "Column index",
+            ParquetFeature::OffsetIndex => "Offset index",
+            ParquetFeature::BloomFilter => "Bloom filter",
+        }
+    }
+
+    /// What the feature does, whether `summary`'s file has it, and what that means for queries.
+    pub(crate) fn explain(self, summary: &MetadataSummary) -> String {
+        let row_groups = summary.row_group_count;
+        let rows_per_group = summary.row_count / row_groups.max(1);
+        let page_index_unknown = "Page index preloading is off in settings, so whether this file has one is only known once a page view loads it.";
+        let (what, state) = match self {
+            ParquetFeature::Statistics => (
+                "Row group statistics keep the min, max and null count of every column chunk in the footer, so readers can skip row groups whose range cannot match a filter.",
+                match (summary.has_row_group_stats, row_groups) {
+                    (false, _) => format!(
+                        "This file has none, so every filtered query reads all {row_groups} row groups."
+                    ),
+                    (true, 0 | 1) => "This file has them, but with a single row group there is nothing to skip; use the column index to prune within it.".to_string(),
+                    (true, _) => format!(
+                        "This file has them, so filters on sorted or clustered columns can skip some of its {row_groups} row groups."
+                    ),
+                },
+            ),
+            ParquetFeature::ColumnIndex => (
+                "The column index stores the min and max of every data page, so readers can skip pages inside a row group.",
+                match (summary.page_index_preloaded, summary.has_column_index) {
+                    (false, _) => page_index_unknown.to_string(),
+                    (true, true) => "This file has one, so selective filters decode only the pages that may match.".to_string(),
+                    (true, false) => format!(
+                        "This file lacks a page index, so point lookups will scan whole row groups of about {} rows each.",
+                        format_rows(rows_per_group)
+                    ),
+                },
+            ),
+            ParquetFeature::OffsetIndex => (
+                "The offset index records where each page starts and which row it begins with, so readers can fetch just the pages they need.",
+                match (summary.page_index_preloaded, summary.has_offset_index) {
+                    (false, _) => page_index_unknown.to_string(),
+                    (true, true) => "This file has one, so skipped pages are never fetched, which matters most over the network.".to_string(),
+                    (true, false) => "This file lacks one, so reading any row of a column chunk fetches the whole chunk.".to_string(),
+                },
+            ),
+            ParquetFeature::BloomFilter => (
+                "Bloom filters tell whether a value is definitely absent from a column chunk, which helps equality lookups on high-cardinality columns where min/max ranges overlap.",
+                if summary.has_bloom_filter {
+                    format!(
+                        "This file has bloom filters ({} in total), so `column = value` lookups can skip row groups that don't contain the value.",
+                        format_bytes(summary.total_bloom_filter_size)
+                    )
+                } else {
+                    "This file has none, so equality lookups rely on min/max statistics alone.".to_string()
+                },
+            ),
+        };
+        format!("{what} {state}")
+    }
+}
+
 impl std::fmt::Display for MetadataSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -156,6 +252,13 @@ pub struct ParquetResolved {
     path: Path,
     object_store_url: ObjectStoreUrl,
     metadata: MetadataSummary,
+    io_stats: Option<Arc<IoStats>>,
+    /// Metadata with the page index, loaded by the first view that needs it.
+    page_index: Arc<tokio::sync::OnceCell<Arc<ParquetMetaData>>>,
+    /// The Delta or Iceberg snapshot table registered along with this file.
+    snapshot_table: Option<String>,
+    /// Tables registered by [`Self::register_row_group_range`], shared by every clone.
+    range_tables: Arc<Mutex<BTreeSet<String>>>,
 }
 
 impl PartialEq for ParquetResolved {
@@ -174,6 +277,7 @@ impl ParquetResolved {
         path: Path,
         object_store_url: ObjectStoreUrl,
         display_info: MetadataSummary,
+        io_stats: Option<Arc<IoStats>>,
     ) -> Self {
         Self {
             reader,
@@ -182,9 +286,18 @@ impl ParquetResolved {
             path,
             object_store_url,
             metadata: display_info,
+            io_stats,
+            page_index: Default::default(),
+            snapshot_table: None,
+            range_tables: Default::default(),
         }
     }
 
+    pub(crate) fn with_snapshot_table(mut self, snapshot_table: Option<String>) -> Self {
+        self.snapshot_table = snapshot_table;
+        self
+    }
+
     pub fn table_name(&self) -> &str {
         &self.table_name
     }
@@ -200,4 +313,195 @@ impl ParquetResolved {
     pub fn reader(&self) -> &ParquetObjectReader {
         &self.reader
     }
+
+    /// Location of the file within its object store.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The object store `ctx` reads this file from.
+    pub(crate) fn object_store(&self, ctx: &SessionContext) -> Result<Arc<dyn ObjectStore>> {
+        Ok(ctx.runtime_env().object_store(&self.object_store_url)?)
+    }
+
+    /// Metadata with the column and offset indexes, fetched once per file and shared by every
+    /// view of it; the footer's metadata as is when the page index was preloaded.
+    pub(crate) async fn page_index_metadata(&self) -> Result<Arc<ParquetMetaData>> {
+        self.page_index
+            .get_or_try_init(|| async {
+                let mut reader = self.reader.clone();
+                load_page_index(&mut reader, self.metadata.metadata.clone()).await
+            })
+            .await
+            .cloned()
+    }
+
+    /// Byte counters of the object store backing this table, if it tracks them.
+    pub(crate) fn io_stats(&self) -> Option<&Arc<IoStats>> {
+        self.io_stats.as_ref()
+    }
+
+    /// Whether the file is read from a local or in-memory store rather than over the network.
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self.object_store_url.as_ref().scheme(),
+            "webfile" | IN_MEMORY_SCHEME
+        )
+    }
+
+    /// Whether the file was loaded from S3, so results can be written back to its bucket.
+    pub(crate) fn is_s3(&self) -> bool {
+        self.object_store_url.as_ref().scheme() == "s3"
+    }
+
+    /// A destination next to this file, for results written back to its store.
+    pub(crate) fn sibling_path(&self, file_name: &str) -> String {
+        let path = self.path.as_ref();
+        match path.rsplit_once('/') {
+            Some((dir, _)) => format!("{dir}/{file_name}"),
+            None => file_name.to_string(),
+        }
+    }
+
+    /// Writes `data` to `path` in the object store this file was read from, reusing its
+    /// bucket and credentials. Returns the URL of the written object.
+    pub(crate) async fn put_object(
+        &self,
+        ctx: &SessionContext,
+        path: &str,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        let path = Path::parse(path.trim_start_matches('/'))?;
+        if path.as_ref().is_empty() {
+            return Err(anyhow::anyhow!("Destination path is empty"));
+        }
+        if path == self.path {
+            return Err(anyhow::anyhow!(
+                "Refusing to overwrite {}, the file being viewed",
+                self.path
+            ));
+        }
+        let store = self.object_store(ctx)?;
+        store.put(&path, Bytes::from(data).into()).await?;
+        Ok(format!("{}{path}", self.object_store_url.as_str()))
+    }
+
+    /// Downloads the whole file into an [`InMemory`] store and registers it as a new table,
+    /// so repeated queries no longer fetch ranges over the network.
+    pub async fn load_into_memory(&self, ctx: &SessionContext) -> Result<ParquetResolved> {
+        if self.metadata.file_size > MAX_IN_MEMORY_FILE_SIZE {
+            return Err(anyhow::anyhow!(
+                "{} is {:.2}, larger than the {:.2} in-memory limit",
+                self.table_name,
+                Byte::from_u64(self.metadata.file_size).get_appropriate_unit(UnitType::Binary),
+                Byte::from_u64(MAX_IN_MEMORY_FILE_SIZE).get_appropriate_unit(UnitType::Binary),
+            ));
+        }
+        let source = self.object_store(ctx)?;
+        let bytes = source.get(&self.path).await?.bytes().await?;
+
+        let memory_store = Arc::new(InMemory::new());
+        memory_store.put(&self.path, bytes.into()).await?;
+        let object_store_url =
+            ObjectStoreUrl::parse(format!("{IN_MEMORY_SCHEME}://{}", uuid::Uuid::new_v4()))?;
+        let file_name = self
+            .path
+            .filename()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| self.table_name.clone());
+        ParquetUnresolved::try_new(file_name, self.path.clone(), object_store_url, memory_store)?
+            .try_into_resolved(ctx)
+            .await
+    }
+
+    /// Name under which [`Self::register_row_group_range`] registers `row_groups`.
+    pub fn row_group_range_table_name(&self, row_groups: &Range<usize>) -> String {
+        format!(
+            "{}_rg{}_{}",
+            self.registered_table_name,
+            row_groups.start,
+            row_groups.end.saturating_sub(1)
+        )
+    }
+
+    /// Decodes only `row_groups` of this file into memory and registers them as their own
+    /// table, so two ranges of one file can be queried side by side. Returns the table name.
+    pub async fn register_row_group_range(
+        &self,
+        ctx: &SessionContext,
+        row_groups: Range<usize>,
+    ) -> Result<String> {
+        let row_group_count = self.metadata.metadata.num_row_groups();
+        if row_groups.is_empty() || row_groups.end > row_group_count {
+            return Err(anyhow::anyhow!(
+                "Row groups {}..{} are not a range of the {row_group_count} row groups in {}",
+                row_groups.start,
+                row_groups.end,
+                self.table_name
+            ));
+        }
+        let decoded_size = row_groups
+            .clone()
+            .map(|i| self.metadata.metadata.row_group(i).total_byte_size() as u64)
+            .sum::<u64>();
+        if decoded_size > MAX_IN_MEMORY_FILE_SIZE {
+            return Err(anyhow::anyhow!(
+                "Row groups {}..{} decode to {}, larger than the {} in-memory limit",
+                row_groups.start,
+                row_groups.end,
+                format_bytes(decoded_size),
+                format_bytes(MAX_IN_MEMORY_FILE_SIZE),
+            ));
+        }
+
+        let reader_metadata =
+            ArrowReaderMetadata::try_new(self.metadata.metadata.clone(), Default::default())?;
+        let batches = ParquetRecordBatchStreamBuilder::new_with_metadata(
+            self.reader.clone(),
+            reader_metadata.clone(),
+        )
+        .with_row_groups(row_groups.clone().collect())
+        .build()?
+        .try_collect::<Vec<_>>()
+        .await?;
+        let table = MemTable::try_new(reader_metadata.schema().clone(), vec![batches])?;
+
+        let table_name = self.row_group_range_table_name(&row_groups);
+        let quoted = format!("\"{table_name}\"");
+        ctx.deregister_table(&quoted)?;
+        ctx.register_table(&quoted, Arc::new(table))?;
+        self.range_tables.lock().unwrap().insert(table_name.clone());
+        Ok(table_name)
+    }
+
+    /// Removes this table, its row group range tables, its snapshot table and its object store
+    /// from `ctx` once it is no longer shown. Anything a `kept` table registered under the same name or URL stays registered.
+    pub fn deregister<'a>(
+        &self,
+        ctx: &SessionContext,
+        kept: impl IntoIterator<Item = &'a ParquetResolved>,
+    ) -> Result<()> {
+        let (mut table_in_use, mut snapshot_in_use, mut store_in_use) = (false, false, false);
+        for other in kept {
+            table_in_use |= self.registered_table_name == other.registered_table_name;
+            snapshot_in_use |=
+                self.snapshot_table.is_some() && self.snapshot_table == other.snapshot_table;
+            store_in_use |= self.object_store_url == other.object_store_url;
+        }
+        if !table_in_use {
+            ctx.deregister_table(format!("\"{}\"", self.registered_table_name))?;
+            let range_tables = std::mem::take(&mut *self.range_tables.lock().unwrap());
+            for range_table in range_tables {
+                ctx.deregister_table(format!("\"{range_table}\""))?;
+            }
+        }
+        if !snapshot_in_use && let Some(snapshot_table) = &self.snapshot_table {
+            ctx.deregister_table(format!("\"{snapshot_table}\""))?;
+        }
+        if !store_in_use {
+            ctx.runtime_env()
+                .deregister_object_store(self.object_store_url.as_ref())?;
+        }
+        Ok(())
+    }
 }
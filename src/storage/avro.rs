@@ -0,0 +1,354 @@
+//! A small reader of Avro object container files, enough to decode Iceberg manifest lists and
+//! manifests. Records become JSON objects keyed by field name; bytes and fixed values become
+//! arrays of numbers.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use anyhow::{Result, anyhow};
+use serde_json::{Map, Value};
+
+const MAGIC: &[u8] = b"Obj\x01";
+const SYNC_LEN: usize = 16;
+
+/// Decodes every record of the Avro container file `data`, using the writer schema from its
+/// header. Supports the `null` and `deflate` codecs.
+pub(crate) fn read_container(data: &[u8]) -> Result<Vec<Value>> {
+    let mut header = Decoder { data, pos: 0 };
+    if header.take(MAGIC.len())? != MAGIC {
+        return Err(anyhow!("Not an Avro container file"));
+    }
+    let mut metadata = HashMap::new();
+    header.read_blocks(|decoder| {
+        let key = decoder.string()?;
+        let value = decoder.bytes()?.to_vec();
+        metadata.insert(key, value);
+        Ok(())
+    })?;
+    let schema: Value = serde_json::from_slice(
+        metadata
+            .get("avro.schema")
+            .ok_or_else(|| anyhow!("Avro file without a schema"))?,
+    )?;
+    let codec = metadata
+        .get("avro.codec")
+        .map(|codec| String::from_utf8_lossy(codec).into_owned())
+        .unwrap_or_else(|| "null".to_string());
+    let sync = header.take(SYNC_LEN)?.to_vec();
+
+    let mut names = HashMap::new();
+    collect_named_types(&schema, None, &mut names);
+
+    let mut records = Vec::new();
+    let mut blocks = Decoder {
+        data,
+        pos: header.pos,
+    };
+    while blocks.pos < data.len() {
+        let count = blocks.long()?;
+        let size = usize::try_from(blocks.long()?)?;
+        let block = blocks.take(size)?;
+        let block = match codec.as_str() {
+            "null" => block.to_vec(),
+            "deflate" => {
+                let mut inflated = Vec::new();
+                flate2::read::DeflateDecoder::new(block).read_to_end(&mut inflated)?;
+                inflated
+            }
+            codec => return Err(anyhow!("Unsupported Avro codec {codec}")),
+        };
+        if blocks.take(SYNC_LEN)? != sync {
+            return Err(anyhow!("Corrupt Avro file: sync marker mismatch"));
+        }
+        let mut decoder = Decoder {
+            data: &block,
+            pos: 0,
+        };
+        for _ in 0..count {
+            records.push(decoder.value(&schema, &names)?);
+        }
+    }
+    Ok(records)
+}
+
+/// Records, enums and fixed types by name and full name, so later fields can refer to them.
+fn collect_named_types<'a>(
+    schema: &'a Value,
+    namespace: Option<&str>,
+    names: &mut HashMap<String, &'a Value>,
+) {
+    match schema {
+        Value::Array(branches) => {
+            for branch in branches {
+                collect_named_types(branch, namespace, names);
+            }
+        }
+        Value::Object(object) => {
+            let namespace = object
+                .get("namespace")
+                .and_then(Value::as_str)
+                .or(namespace);
+            if let Some(name) = object.get("name").and_then(Value::as_str) {
+                names.insert(name.to_string(), schema);
+                if let Some(namespace) = namespace {
+                    names.insert(format!("{namespace}.{name}"), schema);
+                }
+            }
+            for field in object
+                .get("fields")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if let Some(field_type) = field.get("type") {
+                    collect_named_types(field_type, namespace, names);
+                }
+            }
+            for key in ["items", "values"] {
+                if let Some(inner) = object.get(key) {
+                    collect_named_types(inner, namespace, names);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow!("Truncated Avro data"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// A zig-zag encoded variable-length integer, used for both `int` and `long`.
+    fn long(&mut self) -> Result<i64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        Err(anyhow!("Avro integer is too long"))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = usize::try_from(self.long()?)?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        Ok(std::str::from_utf8(self.bytes()?)?.to_string())
+    }
+
+    /// Calls `item` for every item of an array or map, which are written as blocks of items
+    /// ending with an empty block. A negative count is followed by the block's byte size.
+    fn read_blocks(&mut self, mut item: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        loop {
+            let count = self.long()?;
+            if count == 0 {
+                return Ok(());
+            }
+            if count < 0 {
+                self.long()?;
+            }
+            for _ in 0..count.unsigned_abs() {
+                item(self)?;
+            }
+        }
+    }
+
+    fn value(&mut self, schema: &Value, names: &HashMap<String, &Value>) -> Result<Value> {
+        match schema {
+            Value::String(name) => match names.get(name.as_str()) {
+                Some(named) => self.value(named, names),
+                None => self.primitive(name),
+            },
+            Value::Array(branches) => {
+                let index = usize::try_from(self.long()?)?;
+                let branch = branches
+                    .get(index)
+                    .ok_or_else(|| anyhow!("Avro union branch {index} out of range"))?;
+                self.value(branch, names)
+            }
+            Value::Object(object) => {
+                let type_name = object
+                    .get("type")
+                    .ok_or_else(|| anyhow!("Avro schema without a type"))?;
+                match type_name.as_str() {
+                    Some("record") => {
+                        let mut record = Map::new();
+                        for field in object
+                            .get("fields")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                        {
+                            let name = field
+                                .get("name")
+                                .and_then(Value::as_str)
+                                .ok_or_else(|| anyhow!("Avro field without a name"))?;
+                            let field_type = field
+                                .get("type")
+                                .ok_or_else(|| anyhow!("Avro field {name} without a type"))?;
+                            record.insert(name.to_string(), self.value(field_type, names)?);
+                        }
+                        Ok(Value::Object(record))
+                    }
+                    Some("enum") => {
+                        let index = usize::try_from(self.long()?)?;
+                        object
+                            .get("symbols")
+                            .and_then(|symbols| symbols.get(index))
+                            .cloned()
+                            .ok_or_else(|| anyhow!("Avro enum symbol {index} out of range"))
+                    }
+                    Some("array") => {
+                        let items = object
+                            .get("items")
+                            .ok_or_else(|| anyhow!("Avro array without items"))?;
+                        let mut values = Vec::new();
+                        self.read_blocks(|decoder| {
+                            values.push(decoder.value(items, names)?);
+                            Ok(())
+                        })?;
+                        Ok(Value::Array(values))
+                    }
+                    Some("map") => {
+                        let value_type = object
+                            .get("values")
+                            .ok_or_else(|| anyhow!("Avro map without values"))?;
+                        let mut map = Map::new();
+                        self.read_blocks(|decoder| {
+                            let key = decoder.string()?;
+                            map.insert(key, decoder.value(value_type, names)?);
+                            Ok(())
+                        })?;
+                        Ok(Value::Object(map))
+                    }
+                    Some("fixed") => {
+                        let size = object
+                            .get("size")
+                            .and_then(Value::as_u64)
+                            .ok_or_else(|| anyhow!("Avro fixed without a size"))?;
+                        Ok(self.take(size as usize)?.into())
+                    }
+                    // A primitive with attributes, e.g. a logical type, or a nested type.
+                    _ => self.value(type_name, names),
+                }
+            }
+            _ => Err(anyhow!("Invalid Avro schema {schema}")),
+        }
+    }
+
+    fn primitive(&mut self, name: &str) -> Result<Value> {
+        Ok(match name {
+            "null" => Value::Null,
+            "boolean" => Value::Bool(self.take(1)?[0] != 0),
+            "int" | "long" => self.long()?.into(),
+            "float" => f32::from_le_bytes(self.take(4)?.try_into()?).into(),
+            "double" => f64::from_le_bytes(self.take(8)?.try_into()?).into(),
+            "bytes" => self.bytes()?.into(),
+            "string" => self.string()?.into(),
+            other => return Err(anyhow!("Unknown Avro type {other}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn long(value: i64) -> Vec<u8> {
+        let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+        let mut out = Vec::new();
+        loop {
+            let byte = (zigzag & 0x7f) as u8;
+            zigzag >>= 7;
+            if zigzag == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut out = long(value.len() as i64);
+        out.extend_from_slice(value.as_bytes());
+        out
+    }
+
+    /// An uncompressed container file holding `records`, already encoded with `schema`.
+    fn container(schema: &Value, records: &[Vec<u8>]) -> Vec<u8> {
+        let sync = [7u8; SYNC_LEN];
+        let mut out = MAGIC.to_vec();
+        out.extend(long(1));
+        out.extend(string("avro.schema"));
+        out.extend(string(&schema.to_string()));
+        out.extend(long(0));
+        out.extend(sync);
+        let block = records.concat();
+        out.extend(long(records.len() as i64));
+        out.extend(long(block.len() as i64));
+        out.extend(block);
+        out.extend(sync);
+        out
+    }
+
+    #[test]
+    fn test_read_container() {
+        let schema = json!({
+            "type": "record",
+            "name": "entry",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "path", "type": "string"},
+                {"name": "count", "type": ["null", "int"]},
+                {"name": "tags", "type": {"type": "array", "items": "string"}},
+                {"name": "kind", "type": {"type": "enum", "name": "kind", "symbols": ["A", "B"]}},
+            ]
+        });
+        let record = |id: i64, path: &str, count: Option<i64>| {
+            let mut out = long(id);
+            out.extend(string(path));
+            match count {
+                Some(count) => {
+                    out.extend(long(1));
+                    out.extend(long(count));
+                }
+                None => out.extend(long(0)),
+            }
+            out.extend(long(2));
+            out.extend(string("x"));
+            out.extend(string("y"));
+            out.extend(long(0));
+            out.extend(long(1));
+            out
+        };
+        let data = container(&schema, &[record(-3, "a", Some(300)), record(7, "b", None)]);
+        assert_eq!(
+            read_container(&data).unwrap(),
+            vec![
+                json!({"id": -3, "path": "a", "count": 300, "tags": ["x", "y"], "kind": "B"}),
+                json!({"id": 7, "path": "b", "count": null, "tags": ["x", "y"], "kind": "B"}),
+            ]
+        );
+
+        assert!(read_container(b"PAR1").is_err());
+        assert!(read_container(&data[..data.len() - 1]).is_err());
+    }
+}
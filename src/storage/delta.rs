@@ -0,0 +1,277 @@
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use datafusion::execution::object_store::ObjectStoreUrl;
+use datafusion::prelude::SessionContext;
+use object_store::path::Path;
+use object_store_opendal::OpendalStore;
+use opendal::{ErrorKind, Operator, services::Http};
+use percent_encoding::percent_decode_str;
+use serde_json::Value;
+use url::Url;
+
+use crate::storage::readers::http_endpoint;
+use crate::storage::{ObjectStoreCache, iceberg};
+use crate::views::parquet_reader::ParquetUnresolved;
+
+/// Reader features that don't change how data files are read.
+const SUPPORTED_READER_FEATURES: &[&str] = &["timestampNtz", "vacuumProtocolCheck"];
+
+/// The data files of a Delta table snapshot, relative to the table root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DeltaSnapshot {
+    pub version: u64,
+    pub data_files: Vec<String>,
+}
+
+/// Replays the JSON commits of a Delta log, oldest first, into the latest snapshot.
+/// Features that would make reading the bare data files wrong are rejected.
+pub(crate) fn replay_delta_log(commits: &[String]) -> Result<DeltaSnapshot> {
+    if commits.is_empty() {
+        return Err(anyhow!("The Delta log has no commits"));
+    }
+    let mut data_files = BTreeSet::new();
+    for (version, commit) in commits.iter().enumerate() {
+        for line in commit.lines().filter(|line| !line.trim().is_empty()) {
+            let action: Value = serde_json::from_str(line)
+                .map_err(|e| anyhow!("Invalid action in Delta commit {version}: {e}"))?;
+            if let Some(protocol) = action.get("protocol") {
+                check_protocol(protocol)?;
+            }
+            if let Some(metadata) = action.get("metaData") {
+                let partitioned = metadata
+                    .get("partitionColumns")
+                    .and_then(Value::as_array)
+                    .is_some_and(|columns| !columns.is_empty());
+                if partitioned {
+                    return Err(anyhow!("Partitioned Delta tables are not supported yet"));
+                }
+            }
+            if let Some(add) = action.get("add") {
+                if add.get("deletionVector").is_some_and(|dv| !dv.is_null()) {
+                    return Err(anyhow!(
+                        "Delta tables with deletion vectors are not supported"
+                    ));
+                }
+                data_files.insert(action_path(add)?);
+            }
+            if let Some(remove) = action.get("remove") {
+                data_files.remove(&action_path(remove)?);
+            }
+        }
+    }
+    Ok(DeltaSnapshot {
+        version: commits.len() as u64 - 1,
+        data_files: data_files.into_iter().collect(),
+    })
+}
+
+fn action_path(action: &Value) -> Result<String> {
+    action
+        .get("path")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Delta file action without a path"))
+}
+
+fn check_protocol(protocol: &Value) -> Result<()> {
+    let min_reader_version = protocol
+        .get("minReaderVersion")
+        .and_then(Value::as_u64)
+        .unwrap_or(1);
+    match min_reader_version {
+        1 => Ok(()),
+        2 => Err(anyhow!(
+            "Delta reader version 2 (column mapping) is not supported"
+        )),
+        3 => {
+            let unsupported = protocol
+                .get("readerFeatures")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .filter(|feature| !SUPPORTED_READER_FEATURES.contains(feature))
+                .collect::<Vec<_>>();
+            if unsupported.is_empty() {
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "Unsupported Delta reader features: {}",
+                    unsupported.join(", ")
+                ))
+            }
+        }
+        version => Err(anyhow!("Delta reader version {version} is not supported")),
+    }
+}
+
+/// A Delta or Iceberg table directory read over HTTP. Its object store is rooted at the table
+/// and registered under a URL unique to it, so it never replaces the store of another tab on
+/// the same host.
+pub(crate) struct TableRoot {
+    pub name: String,
+    pub op: Operator,
+    pub object_store_url: ObjectStoreUrl,
+}
+
+impl TableRoot {
+    pub(crate) fn parse(table_url: &str) -> Result<Self> {
+        let url = Url::parse(table_url)?;
+        let endpoint = http_endpoint(&url)?;
+        let root = percent_decode_str(url.path().trim_end_matches('/'))
+            .decode_utf8()?
+            .into_owned();
+        let name = root
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("table")
+            .to_string();
+        let op = Operator::new(Http::default().endpoint(&endpoint).root(&root))?.finish();
+
+        let mut hasher = DefaultHasher::new();
+        (&endpoint, &root).hash(&mut hasher);
+        let object_store_url = ObjectStoreUrl::parse(format!("table://{:016x}", hasher.finish()))?;
+        Ok(Self {
+            name,
+            op,
+            object_store_url,
+        })
+    }
+
+    /// Registers `data_files`, relative to the table root, as the table `{name}_{suffix}`.
+    /// Returns the first data file, to be shown in the metadata views, and the table name.
+    pub(crate) async fn register_snapshot(
+        self,
+        ctx: &SessionContext,
+        data_files: &[String],
+        suffix: &str,
+    ) -> Result<(ParquetUnresolved, String)> {
+        let Some(first_file) = data_files.first() else {
+            return Err(anyhow!("Table {} has no data files", self.name));
+        };
+        let object_store = Arc::new(ObjectStoreCache::new(OpendalStore::new(self.op)));
+        ctx.register_object_store(self.object_store_url.as_ref(), object_store.clone());
+
+        let file_urls = data_files
+            .iter()
+            .map(|file| ListingTableUrl::parse(format!("{}{file}", self.object_store_url)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let options =
+            ListingOptions::new(Arc::new(ParquetFormat::default())).with_file_extension("");
+        let config = ListingTableConfig::new_with_multi_paths(file_urls)
+            .with_listing_options(options)
+            .infer_schema(&ctx.state())
+            .await?;
+        let registered_table_name = format!("{}_{suffix}", self.name);
+        let quoted_table_name = format!("\"{registered_table_name}\"");
+        ctx.deregister_table(&quoted_table_name)?;
+        ctx.register_table(&quoted_table_name, Arc::new(ListingTable::try_new(config)?))?;
+
+        let first_file_path = Path::from_url_path(first_file)?;
+        let first_file_name = first_file_path
+            .filename()
+            .unwrap_or(first_file.as_str())
+            .to_string();
+        let first = ParquetUnresolved::try_new(
+            first_file_name,
+            first_file_path,
+            self.object_store_url,
+            object_store,
+        )?
+        .with_snapshot_table(registered_table_name.clone());
+        Ok((first, registered_table_name))
+    }
+}
+
+/// Reads the Delta or Iceberg table at `table_url` over HTTP and registers its latest snapshot
+/// as a single DataFusion table. Returns the first data file, to be shown in the metadata
+/// views, the registered table name and a summary of the snapshot.
+///
+/// Only JSON Delta commits are read, so a Delta log must still start at version 0.
+pub(crate) async fn read_table_from_url(
+    ctx: &SessionContext,
+    table_url: &str,
+) -> Result<(ParquetUnresolved, String, String)> {
+    if iceberg::is_metadata_file_url(table_url) {
+        return iceberg::read_iceberg_from_url(ctx, table_url).await;
+    }
+    let root = TableRoot::parse(table_url)?;
+    let mut commits = Vec::new();
+    loop {
+        let commit_path = format!("_delta_log/{:020}.json", commits.len());
+        match root.op.read(&commit_path).await {
+            Ok(buffer) => commits.push(String::from_utf8(buffer.to_vec())?),
+            Err(e) if e.kind() == ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if commits.is_empty() {
+        return iceberg::read_iceberg_from_url(ctx, table_url)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "No _delta_log/00000000000000000000.json under {table_url}, so not a Delta table (or its early commits were checkpointed away). Reading it as an Iceberg table failed too: {e}"
+                )
+            });
+    }
+    let snapshot = replay_delta_log(&commits)?;
+    let (first, table_name) = root
+        .register_snapshot(ctx, &snapshot.data_files, "delta")
+        .await?;
+    let summary = format!(
+        "all {} data files of Delta version {}",
+        snapshot.data_files.len(),
+        snapshot.version
+    );
+    Ok((first, table_name, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_delta_log() {
+        let commits = vec![
+            [
+                r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#,
+                r#"{"metaData":{"id":"x","partitionColumns":[]}}"#,
+                r#"{"add":{"path":"part-0.parquet","size":10,"dataChange":true}}"#,
+            ]
+            .join("\n"),
+            [
+                r#"{"commitInfo":{"operation":"WRITE"}}"#,
+                r#"{"add":{"path":"part-1.parquet","size":10,"dataChange":true}}"#,
+            ]
+            .join("\n"),
+            r#"{"remove":{"path":"part-0.parquet","dataChange":true}}"#.to_string(),
+        ];
+        let snapshot = replay_delta_log(&commits).unwrap();
+        assert_eq!(snapshot.version, 2);
+        assert_eq!(snapshot.data_files, vec!["part-1.parquet".to_string()]);
+
+        let deletion_vector = vec![
+            r#"{"add":{"path":"a.parquet","deletionVector":{"storageType":"u"}}}"#.to_string(),
+        ];
+        assert!(replay_delta_log(&deletion_vector).is_err());
+
+        let column_mapping =
+            vec![r#"{"protocol":{"minReaderVersion":2,"minWriterVersion":5}}"#.to_string()];
+        assert!(replay_delta_log(&column_mapping).is_err());
+
+        let partitioned =
+            vec![r#"{"metaData":{"id":"x","partitionColumns":["date"]}}"#.to_string()];
+        assert!(replay_delta_log(&partitioned).is_err());
+
+        assert!(replay_delta_log(&[]).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! Reads the current snapshot of an Iceberg table over HTTP. Only Parquet data files without
+//! delete files are supported, the same subset as Delta tables.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Result, anyhow};
+use datafusion::prelude::SessionContext;
+use opendal::{ErrorKind, Operator};
+use serde_json::Value;
+
+use crate::storage::avro;
+use crate::storage::delta::TableRoot;
+use crate::views::parquet_reader::ParquetUnresolved;
+
+/// Manifest entry status of a file removed by the snapshot.
+const STATUS_DELETED: i64 = 2;
+
+/// Where a snapshot lists its manifests: a manifest list file, or inline in format version 1.
+#[derive(Debug, PartialEq, Eq)]
+enum Manifests {
+    List(String),
+    Files(Vec<String>),
+}
+
+pub(crate) fn is_metadata_file_url(table_url: &str) -> bool {
+    table_url.ends_with(".metadata.json")
+}
+
+/// The current snapshot id of the table `metadata`, and where its manifests are listed.
+fn current_snapshot(metadata: &Value) -> Result<(i64, Manifests)> {
+    let snapshot_id = metadata
+        .get("current-snapshot-id")
+        .and_then(Value::as_i64)
+        .filter(|id| *id != -1)
+        .ok_or_else(|| anyhow!("The Iceberg table has no snapshots yet"))?;
+    let snapshot = metadata
+        .get("snapshots")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|snapshot| snapshot.get("snapshot-id").and_then(Value::as_i64) == Some(snapshot_id))
+        .ok_or_else(|| anyhow!("Iceberg snapshot {snapshot_id} is missing from the metadata"))?;
+    if let Some(list) = snapshot.get("manifest-list").and_then(Value::as_str) {
+        return Ok((snapshot_id, Manifests::List(list.to_string())));
+    }
+    let manifests = snapshot
+        .get("manifests")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Iceberg snapshot {snapshot_id} lists no manifests"))?
+        .iter()
+        .map(|manifest| {
+            manifest
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("Invalid manifest in Iceberg snapshot {snapshot_id}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((snapshot_id, Manifests::Files(manifests)))
+}
+
+/// Maps an absolute path written by the table's writer to one relative to the table root.
+fn relative_to_location(location: &str, path: &str) -> Result<String> {
+    path.strip_prefix(location.trim_end_matches('/'))
+        .and_then(|relative| relative.strip_prefix('/'))
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("{path} is outside the table location {location}"))
+}
+
+/// The Parquet data files still live in the manifest `entries`, relative to the table root.
+/// Delete files would need merge-on-read, so they are rejected.
+fn live_data_files(entries: &[Value], location: &str) -> Result<Vec<String>> {
+    let mut data_files = BTreeSet::new();
+    for entry in entries {
+        if entry.get("status").and_then(Value::as_i64) == Some(STATUS_DELETED) {
+            continue;
+        }
+        let data_file = entry
+            .get("data_file")
+            .ok_or_else(|| anyhow!("Iceberg manifest entry without a data file"))?;
+        if data_file
+            .get("content")
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+            != 0
+        {
+            return Err(anyhow!(
+                "Iceberg tables with delete files are not supported"
+            ));
+        }
+        let format = data_file
+            .get("file_format")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if !format.eq_ignore_ascii_case("parquet") {
+            return Err(anyhow!(
+                "Iceberg data files in {format} format are not supported"
+            ));
+        }
+        let path = data_file
+            .get("file_path")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Iceberg data file without a path"))?;
+        data_files.insert(relative_to_location(location, path)?);
+    }
+    Ok(data_files.into_iter().collect())
+}
+
+async fn read_avro(op: &Operator, path: &str) -> Result<Vec<Value>> {
+    avro::read_container(&op.read(path).await?.to_vec())
+}
+
+/// Reads the current snapshot of the Iceberg table at `table_url` over HTTP and registers it
+/// as a single DataFusion table. `table_url` is either the table directory, whose
+/// `metadata/version-hint.text` names the current metadata file, or that metadata file itself.
+pub(crate) async fn read_iceberg_from_url(
+    ctx: &SessionContext,
+    table_url: &str,
+) -> Result<(ParquetUnresolved, String, String)> {
+    let (root_url, metadata_file) = if is_metadata_file_url(table_url) {
+        let (root_url, file) = table_url
+            .rsplit_once("/metadata/")
+            .ok_or_else(|| anyhow!("{table_url} is not in a table's metadata directory"))?;
+        (root_url, Some(file.to_string()))
+    } else {
+        (table_url, None)
+    };
+    let root = TableRoot::parse(root_url)?;
+    let metadata_file = match metadata_file {
+        Some(file) => file,
+        None => {
+            let hint = root
+                .op
+                .read("metadata/version-hint.text")
+                .await
+                .map_err(|e| match e.kind() {
+                    ErrorKind::NotFound => anyhow!(
+                        "No metadata/version-hint.text under {table_url}; open the table's current .metadata.json instead"
+                    ),
+                    _ => e.into(),
+                })?;
+            format!(
+                "v{}.metadata.json",
+                String::from_utf8(hint.to_vec())?.trim()
+            )
+        }
+    };
+
+    let metadata: Value = serde_json::from_slice(
+        &root
+            .op
+            .read(&format!("metadata/{metadata_file}"))
+            .await?
+            .to_vec(),
+    )?;
+    let location = metadata
+        .get("location")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Iceberg metadata without a table location"))?;
+    let (snapshot_id, manifests) = current_snapshot(&metadata)?;
+    let manifests = match manifests {
+        Manifests::List(list) => read_avro(&root.op, &relative_to_location(location, &list)?)
+            .await?
+            .iter()
+            .map(|manifest| {
+                manifest
+                    .get("manifest_path")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("Iceberg manifest list entry without a path"))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Manifests::Files(manifests) => manifests,
+    };
+    let mut entries = Vec::new();
+    for manifest in manifests {
+        entries.extend(read_avro(&root.op, &relative_to_location(location, &manifest)?).await?);
+    }
+    let data_files = live_data_files(&entries, location)?;
+
+    let (first, table_name) = root.register_snapshot(ctx, &data_files, "iceberg").await?;
+    let summary = format!(
+        "all {} data files of Iceberg snapshot {snapshot_id}",
+        data_files.len()
+    );
+    Ok((first, table_name, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_current_snapshot() {
+        let metadata = json!({
+            "format-version": 2,
+            "location": "s3://bucket/db/t",
+            "current-snapshot-id": 2,
+            "snapshots": [
+                {"snapshot-id": 1, "manifest-list": "s3://bucket/db/t/metadata/snap-1.avro"},
+                {"snapshot-id": 2, "manifest-list": "s3://bucket/db/t/metadata/snap-2.avro"},
+            ]
+        });
+        assert_eq!(
+            current_snapshot(&metadata).unwrap(),
+            (
+                2,
+                Manifests::List("s3://bucket/db/t/metadata/snap-2.avro".to_string())
+            )
+        );
+
+        let v1 = json!({
+            "current-snapshot-id": 5,
+            "snapshots": [{"snapshot-id": 5, "manifests": ["s3://bucket/db/t/metadata/m0.avro"]}]
+        });
+        assert_eq!(
+            current_snapshot(&v1).unwrap(),
+            (
+                5,
+                Manifests::Files(vec!["s3://bucket/db/t/metadata/m0.avro".to_string()])
+            )
+        );
+
+        assert!(current_snapshot(&json!({"current-snapshot-id": -1, "snapshots": []})).is_err());
+        assert!(current_snapshot(&json!({"current-snapshot-id": 3, "snapshots": []})).is_err());
+    }
+
+    #[test]
+    fn test_live_data_files() {
+        let entry = |status: i64, path: &str, content: i64, format: &str| {
+            json!({
+                "status": status,
+                "data_file": {"content": content, "file_path": path, "file_format": format}
+            })
+        };
+        let location = "s3://bucket/db/t/";
+        let entries = vec![
+            entry(1, "s3://bucket/db/t/data/b.parquet", 0, "PARQUET"),
+            entry(0, "s3://bucket/db/t/data/a.parquet", 0, "PARQUET"),
+            entry(2, "s3://bucket/db/t/data/old.parquet", 0, "PARQUET"),
+            entry(2, "s3://bucket/db/t/data/old-deletes.parquet", 1, "PARQUET"),
+        ];
+        assert_eq!(
+            live_data_files(&entries, location).unwrap(),
+            vec!["data/a.parquet".to_string(), "data/b.parquet".to_string()]
+        );
+
+        let deletes = vec![entry(1, "s3://bucket/db/t/data/d.parquet", 1, "PARQUET")];
+        assert!(live_data_files(&deletes, location).is_err());
+        let orc = vec![entry(1, "s3://bucket/db/t/data/a.orc", 0, "ORC")];
+        assert!(live_data_files(&orc, location).is_err());
+        let elsewhere = vec![entry(1, "s3://other/a.parquet", 0, "PARQUET")];
+        assert!(live_data_files(&elsewhere, location).is_err());
+    }
+}
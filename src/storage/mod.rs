@@ -1,6 +1,18 @@
+mod avro;
+pub(crate) mod delta;
+mod iceberg;
 mod object_store_cache;
+pub(crate) mod persistent_cache;
 pub(crate) mod readers;
+mod signed_url_store;
+pub(crate) mod text_files;
 mod web_file_store;
 
-pub(crate) use object_store_cache::ObjectStoreCache;
-pub(crate) use web_file_store::WebFileObjectStore;
+pub(crate) use object_store_cache::{
+    FetchProgress, IoStats, ObjectStoreCache, decode_content_encoding,
+};
+pub(crate) use signed_url_store::{SignedUrlStore, is_signed_url, redact_signed_url};
+pub(crate) use web_file_store::{
+    SendWrapper, WebFileObjectStore, pick_file_handle, resolve_get_range,
+    supports_file_system_access,
+};
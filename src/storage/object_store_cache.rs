@@ -1,30 +1,439 @@
 use std::{
     collections::HashMap,
     fmt::{Display, Formatter},
+    io::Read,
     ops::Range,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
 };
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{lock::Mutex, stream::BoxStream};
+use futures::{
+    StreamExt,
+    future::{Either, select},
+    lock::Mutex,
+    stream::BoxStream,
+};
+use gloo_timers::future::TimeoutFuture;
 use object_store::{
-    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
-    PutMultipartOptions, PutOptions, PutPayload, PutResult, path::Path,
+    Attribute, Attributes, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta,
+    ObjectStore, PutMultipartOptions, PutOptions, PutPayload, PutResult, memory::InMemory,
+    path::Path,
 };
-use object_store_opendal::OpendalStore;
+use web_sys::js_sys;
+
+use super::persistent_cache::{self, range_key};
+use super::web_file_store::SendWrapper;
+use crate::views::settings::{persistent_range_cache_enabled, request_timeout};
+
+/// Largest file we are willing to download in full when the server ignores range requests.
+pub(crate) const MAX_FULL_DOWNLOAD_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Store name of the error returned when a request to the inner store times out.
+const TIMEOUT_STORE: &str = "ObjectStoreCache";
+
+/// Attempts per request to the inner store, the first one included.
+const MAX_REQUEST_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; it doubles with every retry after that.
+const RETRY_BASE_DELAY_MS: u32 = 200;
+
+/// Byte counters of an [`ObjectStoreCache`], shared so callers can read them after the store
+/// has been handed off to DataFusion.
+#[derive(Debug, Default)]
+pub(crate) struct IoStats {
+    /// Bytes of all ranges read through the store, including cache hits.
+    bytes_requested: AtomicU64,
+    /// Bytes actually fetched from the underlying store.
+    bytes_fetched: AtomicU64,
+    /// Bytes downloaded in full because range requests were ignored.
+    full_download_bytes: AtomicU64,
+    /// Bytes of the range requests currently in flight to the underlying store.
+    in_flight_bytes: AtomicU64,
+    /// Bytes of the in-flight range requests that have arrived so far.
+    in_flight_received: AtomicU64,
+    /// Size of the file last looked up with `head`.
+    file_size: AtomicU64,
+}
+
+impl IoStats {
+    pub(crate) fn bytes_requested(&self) -> u64 {
+        self.bytes_requested.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn bytes_fetched(&self) -> u64 {
+        self.bytes_fetched.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn full_download_bytes(&self) -> u64 {
+        self.full_download_bytes.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn fetch_progress(&self) -> FetchProgress {
+        let expected = self.in_flight_bytes.load(Ordering::Relaxed);
+        FetchProgress {
+            received: self
+                .in_flight_received
+                .load(Ordering::Relaxed)
+                .min(expected),
+            expected,
+            file_size: self.file_size.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Snapshot of the range requests in flight, for showing that a slow load is still moving.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct FetchProgress {
+    pub(crate) received: u64,
+    pub(crate) expected: u64,
+    /// Size of the file being read, 0 until it is known.
+    pub(crate) file_size: u64,
+}
+
+impl FetchProgress {
+    /// Share of the in-flight bytes received, `None` while nothing is being fetched.
+    pub(crate) fn fraction(&self) -> Option<f64> {
+        (self.expected > 0).then(|| self.received as f64 / self.expected as f64)
+    }
+}
+
+/// Counts a batch of range requests in [`IoStats`] while they are in flight.
+struct InFlight<'a> {
+    stats: &'a IoStats,
+    expected: u64,
+    received: AtomicU64,
+}
+
+impl<'a> InFlight<'a> {
+    fn new(stats: &'a IoStats, expected: u64) -> Self {
+        stats.in_flight_bytes.fetch_add(expected, Ordering::Relaxed);
+        Self {
+            stats,
+            expected,
+            received: AtomicU64::new(0),
+        }
+    }
+
+    fn received(&self, bytes: u64) {
+        self.received.fetch_add(bytes, Ordering::Relaxed);
+        self.stats
+            .in_flight_received
+            .fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Forgets the bytes received so far, before the batch is requested again.
+    fn restart(&self) {
+        let received = self.received.swap(0, Ordering::Relaxed);
+        self.stats
+            .in_flight_received
+            .fetch_sub(received, Ordering::Relaxed);
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.restart();
+        self.stats
+            .in_flight_bytes
+            .fetch_sub(self.expected, Ordering::Relaxed);
+    }
+}
+
+/// The `Content-Encoding` of a response, if it is not the identity encoding.
+fn content_encoding(attributes: &Attributes) -> Option<String> {
+    attributes
+        .get(&Attribute::ContentEncoding)
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .filter(|encoding| !encoding.is_empty() && encoding != "identity")
+}
+
+/// Whether `error` is the one [`ObjectStoreCache`] returns when a request times out.
+fn is_timeout(error: &object_store::Error) -> bool {
+    matches!(
+        error,
+        object_store::Error::Generic {
+            store: TIMEOUT_STORE,
+            ..
+        }
+    )
+}
+
+/// Whether `error` is likely transient (a timeout, a 5xx answer or a dropped connection), so
+/// the request is worth repeating. Missing files and rejected credentials fail right away.
+fn is_retryable(error: &object_store::Error) -> bool {
+    let object_store::Error::Generic { source, .. } = error else {
+        return false;
+    };
+    if is_timeout(error) {
+        return true;
+    }
+    if let Some(error) = source.downcast_ref::<opendal::Error>() {
+        return error.is_temporary();
+    }
+    let message = source.to_string().to_ascii_lowercase();
+    [
+        "status: 500",
+        "status: 502",
+        "status: 503",
+        "status: 504",
+        "timed out",
+        "connection reset",
+        "failed to fetch",
+        "networkerror",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+/// Milliseconds to wait before retry number `retry` (from 0): exponential backoff where the
+/// upper half of each step is scaled by `jitter` in `[0, 1)`, so concurrent readers spread out.
+fn retry_delay_ms(retry: u32, jitter: f64) -> u32 {
+    let step = RETRY_BASE_DELAY_MS.saturating_mul(1 << retry.min(16));
+    step / 2 + (step as f64 / 2.0 * jitter.clamp(0.0, 1.0)) as u32
+}
+
+/// Undoes a gzip/deflate `Content-Encoding` that is still applied to a full download, so
+/// parquet sees the stored bytes. Browsers usually decode responses themselves, in which case
+/// `bytes` is returned unchanged.
+pub(crate) fn decode_content_encoding(
+    encoding: Option<&str>,
+    bytes: Bytes,
+) -> object_store::Result<Bytes> {
+    let mut decoded = Vec::new();
+    let result = match encoding {
+        Some("gzip" | "x-gzip") if bytes.starts_with(&[0x1f, 0x8b]) => {
+            flate2::read::GzDecoder::new(bytes.as_ref()).read_to_end(&mut decoded)
+        }
+        Some("deflate") if bytes.first() == Some(&0x78) => {
+            flate2::read::ZlibDecoder::new(bytes.as_ref()).read_to_end(&mut decoded)
+        }
+        _ => return Ok(bytes),
+    };
+    result.map_err(|e| object_store::Error::Generic {
+        store: "ObjectStoreCache",
+        source: format!(
+            "failed to decode {} response: {e}",
+            encoding.unwrap_or_default()
+        )
+        .into(),
+    })?;
+    Ok(decoded.into())
+}
 
 #[derive(Debug)]
 pub(crate) struct ObjectStoreCache {
-    inner: OpendalStore,
+    inner: Box<dyn ObjectStore>,
     cache: Mutex<HashMap<(Path, Range<u64>), Bytes>>,
+    /// Whole files downloaded because the server answered range requests with the full body.
+    full_files: InMemory,
+    stats: Arc<IoStats>,
+    /// How long a GET/HEAD to `inner` may take before it fails, `None` waits forever.
+    request_timeout: Option<Duration>,
+    /// Identifies the remote store in the IndexedDB range cache; `None` keeps ranges in memory
+    /// only.
+    persistent_namespace: Option<String>,
+    /// [`object_version`] of each file as of its last HEAD, part of its IndexedDB range keys.
+    versions: Mutex<HashMap<Path, String>>,
+}
+
+/// Identifies the content of an object, so cached ranges of an overwritten file are not mixed
+/// with its new bytes.
+fn object_version(meta: &ObjectMeta) -> String {
+    format!(
+        "{}-{}-{}",
+        meta.size,
+        meta.e_tag.as_deref().unwrap_or_default(),
+        meta.last_modified.timestamp_millis()
+    )
 }
 
 impl ObjectStoreCache {
-    pub(crate) fn new(inner: OpendalStore) -> Self {
+    pub(crate) fn new(inner: impl ObjectStore) -> Self {
         Self {
-            inner,
+            inner: Box::new(inner),
             cache: Mutex::new(HashMap::new()),
+            full_files: InMemory::new(),
+            stats: Arc::new(IoStats::default()),
+            request_timeout: request_timeout(),
+            persistent_namespace: None,
+            versions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn with_request_timeout(mut self, request_timeout: Option<Duration>) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Also keeps fetched ranges in IndexedDB under `namespace`, which must identify the remote
+    /// store, so they survive page reloads. Only files that have been HEADed use it, as their
+    /// version is part of the key. No-op when the cache is turned off in settings.
+    pub(crate) fn with_persistent_cache(mut self, namespace: impl Into<String>) -> Self {
+        if persistent_range_cache_enabled() {
+            self.persistent_namespace = Some(namespace.into());
         }
+        self
+    }
+
+    pub(crate) fn stats(&self) -> Arc<IoStats> {
+        self.stats.clone()
+    }
+
+    /// Races `request` against the request timeout, so a stalled connection fails instead of
+    /// leaving the caller waiting forever.
+    async fn timed<T>(
+        &self,
+        location: &Path,
+        request: impl Future<Output = object_store::Result<T>> + Send,
+    ) -> object_store::Result<T> {
+        let Some(timeout) = self.request_timeout else {
+            return request.await;
+        };
+        let timer = SendWrapper {
+            inner: TimeoutFuture::new(timeout.as_millis().try_into().unwrap_or(u32::MAX)),
+        };
+        match select(pin!(request), timer).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(object_store::Error::Generic {
+                store: TIMEOUT_STORE,
+                source: format!(
+                    "request timed out after {:.1}s reading {location}",
+                    timeout.as_secs_f64()
+                )
+                .into(),
+            }),
+        }
+    }
+
+    /// [`Self::timed`], repeating `request` up to [`MAX_REQUEST_ATTEMPTS`] times with backoff
+    /// while it fails with a retryable error.
+    async fn retried<T, F>(
+        &self,
+        location: &Path,
+        mut request: impl FnMut() -> F,
+    ) -> object_store::Result<T>
+    where
+        F: Future<Output = object_store::Result<T>> + Send,
+    {
+        let mut retry = 0;
+        loop {
+            match self.timed(location, request()).await {
+                Err(e) if retry + 1 < MAX_REQUEST_ATTEMPTS && is_retryable(&e) => {
+                    let delay = retry_delay_ms(retry, js_sys::Math::random());
+                    tracing::warn!(
+                        "Attempt {} of {MAX_REQUEST_ATTEMPTS} reading {location} failed, retrying in {delay}ms: {e}",
+                        retry + 1
+                    );
+                    SendWrapper {
+                        inner: TimeoutFuture::new(delay),
+                    }
+                    .await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Fetches `range` of `location` as a stream, so `in_flight` sees the bytes as they arrive.
+    async fn fetch_range(
+        &self,
+        location: &Path,
+        range: Range<u64>,
+        in_flight: &InFlight<'_>,
+    ) -> object_store::Result<Bytes> {
+        let options = GetOptions {
+            range: Some(range.into()),
+            ..Default::default()
+        };
+        let mut stream = self.inner.get_opts(location, options).await?.into_stream();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            in_flight.received(chunk.len() as u64);
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes.into())
+    }
+
+    async fn head_uncached(&self, location: &Path) -> Result<ObjectMeta, object_store::Error> {
+        let options = GetOptions {
+            head: true,
+            ..Default::default()
+        };
+        let result = match self
+            .retried(location, || self.inner.get_opts(location, options.clone()))
+            .await
+        {
+            Ok(result) => result,
+            Err(e) if is_retryable(&e) => return Err(e),
+            Err(_) => {
+                return self.retried(location, || self.inner.head(location)).await;
+            }
+        };
+        if content_encoding(&result.attributes).is_none() {
+            return Ok(result.meta);
+        }
+        // The size of a transport-encoded body is not the size of the file. Servers that encode
+        // full responses often answer ranges with the plain bytes and the real size, so probe
+        // one, and only decode the whole file when the range is encoded too or ignored.
+        let probe = GetOptions {
+            range: Some((0..1).into()),
+            ..Default::default()
+        };
+        let probe = self
+            .retried(location, || self.inner.get_opts(location, probe.clone()))
+            .await?;
+        let probe_encoded = content_encoding(&probe.attributes).is_some();
+        let meta = probe.meta.clone();
+        let probe_len = probe.bytes().await?.len();
+        if !probe_encoded && probe_len == 1 {
+            return Ok(meta);
+        }
+        self.download_full_file(location).await?;
+        self.full_files.head(location).await
+    }
+
+    async fn has_full_file(&self, location: &Path) -> bool {
+        self.full_files.head(location).await.is_ok()
+    }
+
+    async fn download_full_file(&self, location: &Path) -> object_store::Result<()> {
+        if self.has_full_file(location).await {
+            return Ok(());
+        }
+        let size = self
+            .retried(location, || self.inner.head(location))
+            .await?
+            .size;
+        if size > MAX_FULL_DOWNLOAD_SIZE {
+            return Err(object_store::Error::NotSupported {
+                source: format!(
+                    "{location} is served without range support and is {size} bytes, \
+                     larger than the {MAX_FULL_DOWNLOAD_SIZE} byte download limit"
+                )
+                .into(),
+            });
+        }
+        tracing::warn!("Downloading {location} in full");
+        let result = self.retried(location, || self.inner.get(location)).await?;
+        let encoding = content_encoding(&result.attributes);
+        let bytes = result.bytes().await?;
+        self.stats
+            .full_download_bytes
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        self.stats
+            .bytes_fetched
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        let bytes = decode_content_encoding(encoding.as_deref(), bytes)?;
+        self.full_files.put(location, bytes.into()).await?;
+        Ok(())
     }
 }
 
@@ -54,11 +463,24 @@ impl ObjectStore for ObjectStoreCache {
     }
 
     async fn get(&self, location: &Path) -> Result<GetResult, object_store::Error> {
-        self.inner.get(location).await
+        self.retried(location, || self.inner.get(location)).await
     }
 
     async fn head(&self, location: &Path) -> Result<ObjectMeta, object_store::Error> {
-        self.inner.head(location).await
+        let meta = if self.has_full_file(location).await {
+            self.full_files.head(location).await?
+        } else {
+            let meta = self.head_uncached(location).await?;
+            if self.persistent_namespace.is_some() {
+                self.versions
+                    .lock()
+                    .await
+                    .insert(location.clone(), object_version(&meta));
+            }
+            meta
+        };
+        self.stats.file_size.store(meta.size, Ordering::Relaxed);
+        Ok(meta)
     }
 
     async fn get_opts(
@@ -66,7 +488,12 @@ impl ObjectStore for ObjectStoreCache {
         location: &Path,
         options: GetOptions,
     ) -> Result<GetResult, object_store::Error> {
-        return self.inner.get_opts(location, options).await;
+        if self.has_full_file(location).await {
+            return self.full_files.get_opts(location, options).await;
+        }
+        return self
+            .retried(location, || self.inner.get_opts(location, options.clone()))
+            .await;
     }
 
     async fn get_range(
@@ -84,6 +511,15 @@ impl ObjectStore for ObjectStoreCache {
         location: &Path,
         ranges: &[Range<u64>],
     ) -> object_store::Result<Vec<Bytes>> {
+        let requested = ranges.iter().map(|r| r.end - r.start).sum::<u64>();
+        self.stats
+            .bytes_requested
+            .fetch_add(requested, Ordering::Relaxed);
+
+        if self.has_full_file(location).await {
+            return self.full_files.get_ranges(location, ranges).await;
+        }
+
         // Check cache for all ranges
         let cache = self.cache.lock().await;
         let mut missing_ranges = Vec::new();
@@ -103,19 +539,113 @@ impl ObjectStore for ObjectStoreCache {
         // Release lock before making network requests
         drop(cache);
 
+        let persistent = match &self.persistent_namespace {
+            Some(namespace) => self
+                .versions
+                .lock()
+                .await
+                .get(location)
+                .map(|version| (namespace.clone(), version.clone())),
+            None => None,
+        };
+
+        if let Some((namespace, version)) = &persistent
+            && !missing_ranges.is_empty()
+        {
+            let keys = missing_ranges
+                .iter()
+                .map(|range| range_key(namespace, location, version, range))
+                .collect::<Vec<_>>();
+            let persisted = SendWrapper {
+                inner: async move {
+                    let mut persisted = Vec::with_capacity(keys.len());
+                    for key in &keys {
+                        persisted.push(persistent_cache::get(key).await);
+                    }
+                    persisted
+                },
+            }
+            .await;
+
+            let mut cache = self.cache.lock().await;
+            let mut still_missing = Vec::new();
+            for (range, bytes) in missing_ranges.into_iter().zip(persisted) {
+                let Some(bytes) = bytes.filter(|b| b.len() as u64 == range.end - range.start)
+                else {
+                    still_missing.push(range);
+                    continue;
+                };
+                tracing::info!("Request hit persistent cache, path {location}, range: {range:?}");
+                for (i, r) in ranges.iter().enumerate() {
+                    if *r == range && results[i].is_none() {
+                        results[i] = Some(bytes.clone());
+                    }
+                }
+                cache.insert((location.clone(), range), bytes);
+            }
+            missing_ranges = still_missing;
+        }
+
         // Fetch all missing ranges in parallel
         if !missing_ranges.is_empty() {
-            let fetch_tasks: Vec<_> = missing_ranges
+            let in_flight = InFlight::new(
+                &self.stats,
+                missing_ranges.iter().map(|r| r.end - r.start).sum(),
+            );
+            let fetched = self
+                .retried(location, || {
+                    in_flight.restart();
+                    let fetch_tasks: Vec<_> = missing_ranges
+                        .iter()
+                        .map(|range| self.fetch_range(location, range.clone(), &in_flight))
+                        .collect();
+                    async move {
+                        futures::future::join_all(fetch_tasks)
+                            .await
+                            .into_iter()
+                            .collect::<object_store::Result<Vec<_>>>()
+                    }
+                })
+                .await?;
+
+            // A server without range support answers with the whole file instead.
+            let range_ignored = missing_ranges
                 .iter()
-                .map(|range| self.inner.get_range(location, range.clone()))
-                .collect();
+                .zip(fetched.iter())
+                .any(|(range, bytes)| bytes.len() as u64 != range.end - range.start);
+            if range_ignored {
+                self.download_full_file(location).await?;
+                return self.full_files.get_ranges(location, ranges).await;
+            }
+
+            let fetched_bytes = fetched.iter().map(|b| b.len() as u64).sum::<u64>();
+            self.stats
+                .bytes_fetched
+                .fetch_add(fetched_bytes, Ordering::Relaxed);
 
-            let fetched = futures::future::join_all(fetch_tasks).await;
+            if let Some((namespace, version)) = &persistent {
+                let entries = missing_ranges
+                    .iter()
+                    .zip(fetched.iter())
+                    .map(|(range, bytes)| {
+                        (
+                            range_key(namespace, location, version, range),
+                            bytes.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                wasm_bindgen_futures::spawn_local(async move {
+                    for (key, bytes) in entries {
+                        if let Err(e) = persistent_cache::put(&key, &bytes).await {
+                            tracing::warn!("Failed to persist cached range {key}: {e:?}");
+                        }
+                    }
+                });
+            }
 
             // Update cache with fetched results
             let mut cache = self.cache.lock().await;
-            for (range, fetch_result) in missing_ranges.iter().zip(fetched.into_iter()) {
-                let bytes = fetch_result?;
+            for (range, bytes) in missing_ranges.iter().zip(fetched.into_iter()) {
                 let key = (location.clone(), range.clone());
                 cache.insert(key, bytes.clone());
 
@@ -158,3 +688,69 @@ impl ObjectStore for ObjectStoreCache {
         self.inner.copy_if_not_exists(from, to).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_retryable() {
+        let generic = |store: &'static str, message: &str| object_store::Error::Generic {
+            store,
+            source: message.to_string().into(),
+        };
+        assert!(is_retryable(&generic(TIMEOUT_STORE, "request timed out")));
+        assert!(is_retryable(&generic(
+            "SignedUrlStore",
+            "status: 503 reading a.parquet"
+        )));
+        assert!(!is_retryable(&generic(
+            "SignedUrlStore",
+            "status: 400 reading a.parquet"
+        )));
+        assert!(!is_retryable(&object_store::Error::NotFound {
+            path: "a.parquet".to_string(),
+            source: "status: 404".into(),
+        }));
+
+        let temporary =
+            opendal::Error::new(opendal::ErrorKind::Unexpected, "bad gateway").set_temporary();
+        assert!(is_retryable(&object_store::Error::Generic {
+            store: "Unexpected",
+            source: Box::new(temporary),
+        }));
+        let permanent = opendal::Error::new(opendal::ErrorKind::Unexpected, "bad request");
+        assert!(!is_retryable(&object_store::Error::Generic {
+            store: "Unexpected",
+            source: Box::new(permanent),
+        }));
+    }
+
+    #[test]
+    fn test_in_flight_progress() {
+        let stats = IoStats::default();
+        assert_eq!(stats.fetch_progress().fraction(), None);
+        {
+            let first = InFlight::new(&stats, 100);
+            let second = InFlight::new(&stats, 300);
+            first.received(60);
+            second.received(40);
+            assert_eq!(stats.fetch_progress().fraction(), Some(0.25));
+
+            first.restart();
+            assert_eq!(stats.fetch_progress().received, 40);
+            // A server ignoring the range sends more than asked for.
+            second.received(1000);
+            assert_eq!(stats.fetch_progress().fraction(), Some(1.0));
+        }
+        assert_eq!(stats.fetch_progress(), FetchProgress::default());
+    }
+
+    #[test]
+    fn test_retry_delay_ms() {
+        assert_eq!(retry_delay_ms(0, 0.0), RETRY_BASE_DELAY_MS / 2);
+        assert_eq!(retry_delay_ms(1, 0.0), RETRY_BASE_DELAY_MS);
+        assert_eq!(retry_delay_ms(1, 0.999), 2 * RETRY_BASE_DELAY_MS - 1);
+        assert!(retry_delay_ms(40, 1.0) >= retry_delay_ms(16, 0.0));
+    }
+}
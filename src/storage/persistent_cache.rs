@@ -0,0 +1,245 @@
+//! Byte ranges of remote files kept in IndexedDB, so reopening a file after a reload reads its
+//! footer and recently queried column chunks without going back to the network.
+//!
+//! Range bytes live in one object store and a `[size, last used]` entry per range in another.
+//! The entries are read once per session into a [`RangeIndex`], which eviction then consults
+//! without touching IndexedDB again.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+use bytes::Bytes;
+use object_store::path::Path;
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{self, Array, Promise, Uint8Array};
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+const DB_NAME: &str = "parquet-viewer-range-cache";
+const DB_VERSION: u32 = 1;
+const DATA_STORE: &str = "ranges";
+const ENTRY_STORE: &str = "entries";
+
+/// Bytes kept across reloads before the least recently used ranges are evicted.
+pub(crate) const MAX_PERSISTENT_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+
+thread_local! {
+    static DATABASE: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+    static INDEX: RefCell<Option<RangeIndex>> = const { RefCell::new(None) };
+}
+
+/// Key of `range` of `location` in the store identified by `namespace`. `version` identifies
+/// the object's content, so ranges of a file that has since been overwritten are never read
+/// back; they age out through eviction instead.
+pub(crate) fn range_key(
+    namespace: &str,
+    location: &Path,
+    version: &str,
+    range: &Range<u64>,
+) -> String {
+    format!(
+        "{namespace}|{location}|{version}|{}-{}",
+        range.start, range.end
+    )
+}
+
+/// Size and last use of every cached range, ordered by recency for eviction.
+#[derive(Debug, Default)]
+pub(crate) struct RangeIndex {
+    entries: HashMap<String, (u64, u64)>,
+    by_use: BTreeSet<(u64, String)>,
+    total_bytes: u64,
+}
+
+impl RangeIndex {
+    /// Records `key` as `size` bytes last used at `used` (ms since epoch).
+    pub(crate) fn touch(&mut self, key: &str, size: u64, used: u64) {
+        self.remove(key);
+        self.entries.insert(key.to_string(), (size, used));
+        self.by_use.insert((used, key.to_string()));
+        self.total_bytes += size;
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        if let Some((size, used)) = self.entries.remove(key) {
+            self.by_use.remove(&(used, key.to_string()));
+            self.total_bytes -= size;
+        }
+    }
+
+    /// Drops the least recently used entries until the rest fit in `max_bytes`, returning
+    /// their keys.
+    pub(crate) fn evict(&mut self, max_bytes: u64) -> Vec<String> {
+        let mut victims = Vec::new();
+        while self.total_bytes > max_bytes {
+            let Some((_, key)) = self.by_use.pop_first() else {
+                break;
+            };
+            if let Some((size, _)) = self.entries.remove(&key) {
+                self.total_bytes -= size;
+            }
+            victims.push(key);
+        }
+        victims
+    }
+}
+
+/// Resolves with the result of `request` once it succeeds.
+async fn request_result(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await?;
+    request.result()
+}
+
+async fn open_database() -> Result<IdbDatabase, JsValue> {
+    if let Some(database) = DATABASE.with_borrow(|database| database.clone()) {
+        return Ok(database);
+    }
+    let factory = web_sys::window()
+        .ok_or("no window")?
+        .indexed_db()?
+        .ok_or("IndexedDB is not available")?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+    let upgrade_request = request.clone();
+    let on_upgrade = Closure::<dyn FnMut()>::new(move || {
+        if let Ok(database) = upgrade_request
+            .result()
+            .and_then(|database| database.dyn_into::<IdbDatabase>())
+        {
+            let _ = database.create_object_store(DATA_STORE);
+            let _ = database.create_object_store(ENTRY_STORE);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+    let database: IdbDatabase = request_result(&request).await?.dyn_into()?;
+    DATABASE.set(Some(database.clone()));
+    Ok(database)
+}
+
+async fn transaction(mode: IdbTransactionMode) -> Result<IdbTransaction, JsValue> {
+    let stores = Array::of2(&DATA_STORE.into(), &ENTRY_STORE.into());
+    open_database()
+        .await?
+        .transaction_with_str_sequence_and_mode(&stores, mode)
+}
+
+fn entry(size: usize, used: u64) -> JsValue {
+    Array::of2(&(size as f64).into(), &(used as f64).into()).into()
+}
+
+fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Builds the [`RangeIndex`] from the entry store, once per session.
+async fn load_index(entries: &IdbObjectStore) -> Result<(), JsValue> {
+    if INDEX.with_borrow(|index| index.is_some()) {
+        return Ok(());
+    }
+    let keys = Array::from(&request_result(&entries.get_all_keys()?).await?);
+    let values = Array::from(&request_result(&entries.get_all()?).await?);
+    let mut index = RangeIndex::default();
+    for (key, value) in keys.iter().zip(values.iter()) {
+        let value = Array::from(&value);
+        if let (Some(key), Some(size), Some(used)) = (
+            key.as_string(),
+            value.get(0).as_f64(),
+            value.get(1).as_f64(),
+        ) {
+            index.touch(&key, size as u64, used as u64);
+        }
+    }
+    INDEX.with_borrow_mut(|loaded| {
+        if loaded.is_none() {
+            *loaded = Some(index);
+        }
+    });
+    Ok(())
+}
+
+/// The cached bytes under `key`, marking them as recently used.
+pub(crate) async fn get(key: &str) -> Option<Bytes> {
+    let transaction = transaction(IdbTransactionMode::Readwrite).await.ok()?;
+    let data = transaction.object_store(DATA_STORE).ok()?;
+    let value = request_result(&data.get(&key.into()).ok()?).await.ok()?;
+    let bytes = value.dyn_into::<Uint8Array>().ok()?.to_vec();
+    let entries = transaction.object_store(ENTRY_STORE).ok()?;
+    let used = now();
+    let _ = entries.put_with_key(&entry(bytes.len(), used), &key.into());
+    INDEX.with_borrow_mut(|index| {
+        if let Some(index) = index {
+            index.touch(key, bytes.len() as u64, used);
+        }
+    });
+    Some(Bytes::from(bytes))
+}
+
+/// Stores `bytes` under `key`, then evicts the least recently used ranges past
+/// [`MAX_PERSISTENT_CACHE_BYTES`].
+pub(crate) async fn put(key: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    if bytes.len() as u64 > MAX_PERSISTENT_CACHE_BYTES / 4 {
+        return Ok(());
+    }
+    let transaction = transaction(IdbTransactionMode::Readwrite).await?;
+    let data = transaction.object_store(DATA_STORE)?;
+    request_result(&data.put_with_key(&Uint8Array::from(bytes), &key.into())?).await?;
+    let entries = transaction.object_store(ENTRY_STORE)?;
+    let used = now();
+    request_result(&entries.put_with_key(&entry(bytes.len(), used), &key.into())?).await?;
+
+    load_index(&entries).await?;
+    let victims = INDEX.with_borrow_mut(|index| {
+        let index = index.get_or_insert_default();
+        index.touch(key, bytes.len() as u64, used);
+        index.evict(MAX_PERSISTENT_CACHE_BYTES)
+    });
+    for victim in victims {
+        data.delete(&victim.as_str().into())?;
+        entries.delete(&victim.as_str().into())?;
+    }
+    Ok(())
+}
+
+/// Drops every cached range.
+pub(crate) async fn clear() -> Result<(), JsValue> {
+    let transaction = transaction(IdbTransactionMode::Readwrite).await?;
+    request_result(&transaction.object_store(DATA_STORE)?.clear()?).await?;
+    request_result(&transaction.object_store(ENTRY_STORE)?.clear()?).await?;
+    INDEX.set(Some(RangeIndex::default()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_index_evicts_least_recently_used() {
+        let mut index = RangeIndex::default();
+        index.touch("new", 40, 3);
+        index.touch("old", 30, 1);
+        index.touch("mid", 50, 2);
+        assert!(index.evict(120).is_empty());
+        assert_eq!(index.evict(100), vec!["old"]);
+
+        // Using "mid" again makes "new" the oldest.
+        index.touch("mid", 50, 4);
+        assert_eq!(index.evict(60), vec!["new"]);
+        index.remove("mid");
+        assert_eq!(index.total_bytes, 0);
+        assert!(index.evict(0).is_empty());
+    }
+
+    #[test]
+    fn test_range_key() {
+        let path = Path::parse("data/a.parquet").unwrap();
+        assert_eq!(
+            range_key("https://example.com", &path, "16-\"abc\"-0", &(4..16)),
+            "https://example.com|data/a.parquet|16-\"abc\"-0|4-16"
+        );
+    }
+}
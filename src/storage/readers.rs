@@ -1,31 +1,53 @@
 use anyhow::Result;
+use bytes::Bytes;
 use datafusion::execution::object_store::ObjectStoreUrl;
 use dioxus::prelude::*;
+use gloo_net::http::Request;
 use object_store::path::Path;
+use object_store::{ListResult, ObjectStore};
 use object_store_opendal::OpendalStore;
-use opendal::{Operator, services::Http, services::S3};
+use opendal::{Operator, services::Gcs, services::Http, services::S3};
+use serde_json::Value;
 use std::sync::Arc;
 use url::Url;
 use web_sys::js_sys;
 
-use crate::storage::ObjectStoreCache;
+use crate::storage::text_files::{CsvOptions, is_csv_file_name, stage_csv};
+use crate::storage::{ObjectStoreCache, SignedUrlStore, is_signed_url, redact_signed_url};
 use crate::utils::get_stored_value;
-use crate::views::parquet_reader::ParquetUnresolved;
+use crate::views::parquet_reader::{ParquetUnresolved, is_parquet_file_name};
+use crate::views::settings::GCS_SERVICE_ACCOUNT_KEY;
 use crate::views::settings::S3_ACCESS_KEY_ID_KEY;
 use crate::views::settings::S3_ENDPOINT_KEY;
 use crate::views::settings::S3_SECRET_KEY_KEY;
+use crate::views::settings::S3_SESSION_TOKEN_KEY;
+use crate::views::settings::s3_virtual_host_style_enabled;
+
+/// The `scheme://host[:port]` part of `url`, used as the HTTP object store endpoint.
+pub(crate) fn http_endpoint(url: &Url) -> Result<String> {
+    if url.scheme() == "file" {
+        return Err(anyhow::anyhow!(
+            "The browser cannot read local paths like {url}. Open the file with the file picker \
+             or drag and drop, or serve it with `parquet-viewer-cli <path>`."
+        ));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("URL has no host: {url}"))?;
+    Ok(format!(
+        "{}://{}{}",
+        url.scheme(),
+        host,
+        url.port().map_or("".to_string(), |p| format!(":{p}"))
+    ))
+}
 
 /// Reads a parquet file from a URL and returns a ParquetInfo object.
 /// This function parses the URL, creates an HTTP object store, and returns
 /// the necessary information to read the parquet file.
 pub fn read_from_url(url_str: &str) -> Result<ParquetUnresolved> {
     let url = Url::parse(url_str)?;
-    let endpoint = format!(
-        "{}://{}{}",
-        url.scheme(),
-        url.host_str().ok_or(anyhow::anyhow!("Empty host"))?,
-        url.port().map_or("".to_string(), |p| format!(":{p}"))
-    );
+    let endpoint = http_endpoint(&url)?;
     let path = url.path().to_string();
 
     let table_name = path
@@ -34,41 +56,279 @@ pub fn read_from_url(url_str: &str) -> Result<ParquetUnresolved> {
         .unwrap_or("uploaded.parquet")
         .to_string();
 
-    let builder = {
-        let mut http_builder = Http::default().endpoint(&endpoint);
-        let username = url.username();
-        if !username.is_empty() {
-            http_builder = http_builder.username(username);
-        }
-        if let Some(password) = url.password() {
-            http_builder = http_builder.password(password);
-        }
-        http_builder
-    };
+    // Each signed URL gets its own store, which only ever serves that one object. So do URLs
+    // with `user:password@`, whose credentials are sent as an explicit `Authorization` header.
+    let has_credentials = !url.username().is_empty() || url.password().is_some();
+    if is_signed_url(&url) || has_credentials {
+        let store = if has_credentials {
+            SignedUrlStore::with_basic_auth(url.clone())
+        } else {
+            SignedUrlStore::new(url_str.to_string())
+        };
+        return read_from_exact_url(table_name, &path, ObjectStoreCache::new(store));
+    }
+
+    let builder = Http::default().endpoint(&endpoint);
     let op = Operator::new(builder)?;
     let op = op.finish();
-    let object_store = Arc::new(ObjectStoreCache::new(OpendalStore::new(op)));
+    let object_store =
+        ObjectStoreCache::new(OpendalStore::new(op)).with_persistent_cache(endpoint.as_str());
+    let io_stats = object_store.stats();
     let object_store_url = ObjectStoreUrl::parse(&endpoint)?;
-    ParquetUnresolved::try_new(
+    Ok(ParquetUnresolved::try_new(
         table_name.clone(),
         Path::parse(path)?,
         object_store_url,
-        object_store,
-    )
+        Arc::new(object_store),
+    )?
+    .with_io_stats(io_stats))
 }
 
-pub(crate) fn read_from_s3(
-    s3_bucket: &str,
-    s3_region: &str,
-    s3_file_path: &str,
+/// The file name of a `.csv` or `.tsv` URL. Such files have no footer to range-read, so they
+/// are downloaded whole and converted to Parquet instead.
+pub(crate) fn csv_url_file_name(url_str: &str) -> Option<String> {
+    let url = Url::parse(url_str).ok()?;
+    let file_name = url.path_segments()?.next_back()?;
+    let file_name = percent_encoding::percent_decode_str(file_name)
+        .decode_utf8_lossy()
+        .into_owned();
+    is_csv_file_name(&file_name).then_some(file_name)
+}
+
+/// Opens the file at `url_str`: CSV and TSV files are downloaded and converted like a picked
+/// CSV file, anything else is read as Parquet with [`read_from_url`].
+pub(crate) async fn open_url(url_str: &str) -> Result<ParquetUnresolved> {
+    let Some(file_name) = csv_url_file_name(url_str) else {
+        return read_from_url(url_str);
+    };
+    read_csv_from_url(url_str, &file_name, CsvOptions::for_file_name(&file_name)).await
+}
+
+/// Downloads the CSV file `file_name` at `url_str` and stages it as an in-memory Parquet file.
+async fn read_csv_from_url(
+    url_str: &str,
+    file_name: &str,
+    options: CsvOptions,
+) -> Result<ParquetUnresolved> {
+    http_endpoint(&Url::parse(url_str)?)?;
+    let response = Request::get(url_str).send().await?;
+    if !response.ok() {
+        return Err(anyhow::anyhow!(
+            "Reading {} failed with status {}",
+            redact_signed_url(url_str),
+            response.status()
+        ));
+    }
+    let data = Bytes::from(response.binary().await?);
+    stage_csv(file_name, data, options).await
+}
+
+/// A table over `object_store`, which serves a single object by its exact URL.
+fn read_from_exact_url(
+    table_name: String,
+    path: &str,
+    object_store: ObjectStoreCache,
+) -> Result<ParquetUnresolved> {
+    let io_stats = object_store.stats();
+    let object_store_url = ObjectStoreUrl::parse(format!("signed://{}", uuid::Uuid::new_v4()))?;
+    Ok(ParquetUnresolved::try_new(
+        table_name,
+        Path::parse(path)?,
+        object_store_url,
+        Arc::new(object_store),
+    )?
+    .with_io_stats(io_stats))
+}
+
+/// Revision as a single path segment, so `refs/convert/parquet` becomes
+/// `refs%2Fconvert%2Fparquet`. Defaults to `main`.
+fn huggingface_revision(revision: &str) -> String {
+    let revision = match revision.trim() {
+        "" => "main",
+        revision => revision,
+    };
+    url::form_urlencoded::byte_serialize(revision.as_bytes()).collect()
+}
+
+/// Builds the `resolve` URL of a file in a HuggingFace dataset repository.
+/// `revision` may be a branch, tag, or commit, and defaults to `main` when empty.
+pub(crate) fn huggingface_resolve_url(
+    dataset: &str,
+    revision: &str,
+    file_path: &str,
+) -> Result<String> {
+    let dataset = dataset.trim().trim_matches('/');
+    let file_path = file_path.trim().trim_start_matches('/');
+    let revision = huggingface_revision(revision);
+    if dataset.split('/').count() != 2 || dataset.split('/').any(str::is_empty) {
+        return Err(anyhow::anyhow!(
+            "Dataset must be in the form `org/dataset`, got `{dataset}`"
+        ));
+    }
+    if file_path.is_empty() {
+        return Err(anyhow::anyhow!("File path is required"));
+    }
+    Ok(format!(
+        "https://huggingface.co/datasets/{dataset}/resolve/{revision}/{file_path}"
+    ))
+}
+
+/// Builds the Hub API URL listing every file of a dataset at `revision` under `path`.
+pub(crate) fn huggingface_tree_url(dataset: &str, revision: &str, path: &str) -> Result<String> {
+    let dataset = dataset.trim().trim_matches('/');
+    if dataset.split('/').count() != 2 || dataset.split('/').any(str::is_empty) {
+        return Err(anyhow::anyhow!(
+            "Dataset must be in the form `org/dataset`, got `{dataset}`"
+        ));
+    }
+    let revision = huggingface_revision(revision);
+    let path = path.trim().trim_matches('/');
+    Ok(format!(
+        "https://huggingface.co/api/datasets/{dataset}/tree/{revision}/{path}?recursive=true"
+    ))
+}
+
+/// Parquet files of a Hub tree listing that belong to `split`, which may be a folder, as in
+/// `default/train/0000.parquet`, or a file name prefix, as in `data/train-00000-of-00001.parquet`.
+/// An empty split keeps every Parquet file.
+fn huggingface_split_files(tree: &Value, split: &str) -> Vec<ListingEntry> {
+    let split = split.trim();
+    let mut files = tree
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.get("type").and_then(|t| t.as_str()) == Some("file"))
+        .filter_map(|entry| {
+            let path = entry.get("path")?.as_str()?;
+            let size = entry.get("size").and_then(|s| s.as_u64()).unwrap_or(0);
+            Some((path, size))
+        })
+        .filter(|(path, _)| path.ends_with(".parquet"))
+        .filter(|(path, _)| {
+            let (folders, file_name) = path.rsplit_once('/').unwrap_or(("", path));
+            split.is_empty()
+                || folders.split('/').any(|folder| folder == split)
+                || file_name
+                    .strip_prefix(split)
+                    .is_some_and(|rest| rest.starts_with(['-', '_', '.']))
+        })
+        .map(|(path, size)| ListingEntry::File {
+            path: path.to_string(),
+            size,
+        })
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.path().cmp(b.path()));
+    files
+}
+
+/// Lists the Parquet parts of `split` in a HuggingFace dataset, under the `config` folder when
+/// one is given.
+pub(crate) async fn list_huggingface_parts(
+    dataset: &str,
+    revision: &str,
+    config: &str,
+    split: &str,
+    token: &str,
+) -> Result<Vec<ListingEntry>> {
+    // The tree API is paginated, with the next page in the `Link` header.
+    let mut next_url = Some(huggingface_tree_url(dataset, revision, config)?);
+    let mut tree = Vec::new();
+    while let Some(url) = next_url {
+        let mut request = Request::get(&url);
+        if !token.is_empty() {
+            request = request.header("Authorization", &format!("Bearer {token}"));
+        }
+        let response = request.send().await?;
+        if !response.ok() {
+            return Err(anyhow::anyhow!(
+                "Listing {dataset} failed with status {}",
+                response.status()
+            ));
+        }
+        next_url = response
+            .headers()
+            .get("link")
+            .and_then(|link| next_page_url(&link));
+        if let Value::Array(entries) = response.json().await? {
+            tree.extend(entries);
+        }
+    }
+    Ok(huggingface_split_files(&Value::Array(tree), split))
+}
+
+/// The `rel="next"` URL of an HTTP `Link` header, as in
+/// `<https://example.com/?cursor=abc>; rel="next"`.
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|param| matches!(param.trim(), "rel=\"next\"" | "rel=next"));
+        let url = url.trim().strip_prefix('<')?.strip_suffix('>')?;
+        is_next.then(|| url.to_string())
+    })
+}
+
+pub(crate) fn read_from_huggingface(
+    dataset: &str,
+    revision: &str,
+    file_path: &str,
+    token: &str,
 ) -> Result<ParquetUnresolved> {
+    let url = huggingface_resolve_url(dataset, revision, file_path)?;
+    let table_name = file_path
+        .split('/')
+        .next_back()
+        .unwrap_or("uploaded.parquet")
+        .to_string();
+    // opendal's HTTP service would encode the `%` of the encoded revision again, so the file
+    // is fetched by its exact URL instead.
+    let store = match token.trim() {
+        "" => SignedUrlStore::new(url.clone()),
+        token => SignedUrlStore::with_bearer_token(url.clone(), token),
+    };
+    let object_store = ObjectStoreCache::new(store).with_persistent_cache("https://huggingface.co");
+    read_from_exact_url(table_name, Url::parse(&url)?.path(), object_store)
+}
+
+/// An S3 operator for `s3_bucket` with the endpoint and credentials saved in settings, and the
+/// endpoint it talks to.
+fn s3_operator(s3_bucket: &str, s3_region: &str) -> Result<(Operator, String)> {
     let endpoint =
         get_stored_value(S3_ENDPOINT_KEY).unwrap_or("https://s3.amazonaws.com".to_string());
     let access_key_id = get_stored_value(S3_ACCESS_KEY_ID_KEY).unwrap_or_default();
     let secret_key = get_stored_value(S3_SECRET_KEY_KEY).unwrap_or_default();
+    let session_token = get_stored_value(S3_SESSION_TOKEN_KEY).unwrap_or_default();
+
+    if endpoint.is_empty() || s3_bucket.is_empty() {
+        return Err(anyhow::anyhow!("Endpoint and bucket are required"));
+    }
+
+    let mut cfg = S3::default()
+        .endpoint(&endpoint)
+        .access_key_id(&access_key_id)
+        .secret_access_key(&secret_key)
+        .bucket(s3_bucket)
+        .region(s3_region);
+    // Temporary (STS) credentials come with a session token.
+    if !session_token.is_empty() {
+        cfg = cfg.session_token(&session_token);
+    }
+    // Path-style (`endpoint/bucket/key`) is the default, which MinIO and most gateways expect.
+    if s3_virtual_host_style_enabled() {
+        cfg = cfg.enable_virtual_host_style();
+    }
+
+    Ok((Operator::new(cfg)?.finish(), endpoint))
+}
 
+pub(crate) fn read_from_s3(
+    s3_bucket: &str,
+    s3_region: &str,
+    s3_file_path: &str,
+) -> Result<ParquetUnresolved> {
     // Validate inputs
-    if endpoint.is_empty() || s3_bucket.is_empty() || s3_file_path.is_empty() {
+    if s3_bucket.is_empty() || s3_file_path.is_empty() {
         return Err(anyhow::anyhow!("All fields except region are required",));
     }
     let file_name = s3_file_path
@@ -77,24 +337,125 @@ pub(crate) fn read_from_s3(
         .unwrap_or("uploaded.parquet")
         .to_string();
 
-    let cfg = S3::default()
-        .endpoint(&endpoint)
-        .access_key_id(&access_key_id)
-        .secret_access_key(&secret_key)
-        .bucket(s3_bucket)
-        .region(s3_region);
-
+    let (op, endpoint) = s3_operator(s3_bucket, s3_region)?;
     let path = format!("s3://{s3_bucket}");
-
-    let op = Operator::new(cfg)?.finish();
-    let object_store = Arc::new(ObjectStoreCache::new(OpendalStore::new(op)));
+    let object_store = ObjectStoreCache::new(OpendalStore::new(op))
+        .with_persistent_cache(format!("{endpoint}/{s3_bucket}"));
+    let io_stats = object_store.stats();
     let object_store_url = ObjectStoreUrl::parse(&path)?;
-    ParquetUnresolved::try_new(
+    Ok(ParquetUnresolved::try_new(
         file_name.clone(),
         Path::parse(s3_file_path)?,
         object_store_url,
-        object_store.clone(),
-    )
+        Arc::new(object_store),
+    )?
+    .with_io_stats(io_stats))
+}
+
+/// A folder or Parquet file directly under a listed prefix, by its path in the bucket.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ListingEntry {
+    Folder(String),
+    File { path: String, size: u64 },
+}
+
+impl ListingEntry {
+    pub(crate) fn path(&self) -> &str {
+        match self {
+            ListingEntry::Folder(path) | ListingEntry::File { path, .. } => path,
+        }
+    }
+}
+
+/// Whether a listed object is shown as a Parquet file: the file picker's check, minus the
+/// `_SUCCESS` markers and other files that Spark and Hive hide with a `_` or `.` prefix.
+fn is_listed_parquet_file(location: &Path) -> bool {
+    location
+        .filename()
+        .is_some_and(|name| !name.starts_with(['_', '.']) && is_parquet_file_name(name))
+}
+
+/// Folders, then Parquet files, each sorted by path; other files are left out.
+fn listing_entries(listing: ListResult) -> Vec<ListingEntry> {
+    let mut folders = listing
+        .common_prefixes
+        .iter()
+        .map(|prefix| ListingEntry::Folder(prefix.to_string()))
+        .collect::<Vec<_>>();
+    folders.sort_by(|a, b| a.path().cmp(b.path()));
+    let mut files = listing
+        .objects
+        .into_iter()
+        .filter(|object| is_listed_parquet_file(&object.location))
+        .map(|object| ListingEntry::File {
+            path: object.location.to_string(),
+            size: object.size,
+        })
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.path().cmp(b.path()));
+    folders.extend(files);
+    folders
+}
+
+/// Lists the folders and Parquet files directly under `prefix` of an S3 bucket; an empty
+/// prefix lists the bucket root.
+pub(crate) async fn list_s3_prefix(
+    s3_bucket: &str,
+    s3_region: &str,
+    prefix: &str,
+) -> Result<Vec<ListingEntry>> {
+    let (op, _) = s3_operator(s3_bucket, s3_region)?;
+    let store = OpendalStore::new(op);
+    let prefix = prefix.trim_matches('/');
+    let prefix = (!prefix.is_empty())
+        .then(|| Path::parse(prefix))
+        .transpose()?;
+    let listing = store.list_with_delimiter(prefix.as_ref()).await?;
+    Ok(listing_entries(listing))
+}
+
+/// Reads `file_path` from a GCS bucket, signing requests with the service account JSON saved
+/// in settings. Without one, the bucket is read anonymously, which works for public buckets.
+pub(crate) fn read_from_gcs(bucket: &str, file_path: &str) -> Result<ParquetUnresolved> {
+    let service_account = get_stored_value(GCS_SERVICE_ACCOUNT_KEY).unwrap_or_default();
+    let file_path = file_path.trim_start_matches('/');
+    if bucket.is_empty() || file_path.is_empty() {
+        return Err(anyhow::anyhow!("Bucket and file path are required"));
+    }
+    let file_name = file_path
+        .split('/')
+        .next_back()
+        .unwrap_or("uploaded.parquet")
+        .to_string();
+
+    // There is no metadata server or gcloud config to fall back on in the browser.
+    let mut cfg = Gcs::default()
+        .bucket(bucket)
+        .disable_vm_metadata()
+        .disable_config_load();
+    cfg = match service_account.trim() {
+        "" => cfg.allow_anonymous(),
+        service_account => {
+            let encoded = web_sys::window()
+                .ok_or_else(|| anyhow::anyhow!("No window to encode the service account in"))?
+                .btoa(service_account)
+                .map_err(|_| anyhow::anyhow!("The GCS service account JSON is not valid text"))?;
+            cfg.credential(&encoded)
+        }
+    };
+
+    let op = Operator::new(cfg)?.finish();
+    let object_store = ObjectStoreCache::new(OpendalStore::new(op))
+        .with_persistent_cache(format!("gs://{bucket}"));
+    let io_stats = object_store.stats();
+    let object_store_url = ObjectStoreUrl::parse(format!("gs://{bucket}"))?;
+    Ok(ParquetUnresolved::try_new(
+        file_name,
+        Path::parse(file_path)?,
+        object_store_url,
+        Arc::new(object_store),
+    )?
+    .with_io_stats(io_stats))
 }
 
 pub(crate) fn read_from_vscode(
@@ -113,12 +474,7 @@ pub(crate) fn read_from_vscode(
         async move {
             let result = async {
                 let url = Url::parse(&url)?;
-                let endpoint = format!(
-                    "{}://{}{}",
-                    url.scheme(),
-                    url.host_str().ok_or(anyhow::anyhow!("Empty host"))?,
-                    url.port().map_or("".to_string(), |p| format!(":{p}"))
-                );
+                let endpoint = http_endpoint(&url)?;
                 let path = url.path().to_string();
 
                 let builder = Http::default().endpoint(&endpoint);
@@ -142,7 +498,12 @@ pub(crate) fn read_from_vscode(
 
 #[cfg(test)]
 mod tests {
-    use crate::storage::readers::read_from_url;
+    use object_store::{ListResult, ObjectMeta, path::Path};
+
+    use crate::storage::readers::{
+        ListingEntry, huggingface_resolve_url, huggingface_split_files, huggingface_tree_url,
+        listing_entries, next_page_url, read_from_url,
+    };
 
     #[test]
     fn test_read_from_url_non_parquet() {
@@ -150,7 +511,7 @@ mod tests {
         let result = read_from_url(url);
         assert!(result.is_err(), "Should fail for an invalid URL");
 
-        let url = "https://example.com/file.csv";
+        let url = "https://example.com/file.txt";
         let result = read_from_url(url);
 
         assert!(result.is_err(), "Should fail for non-parquet files");
@@ -158,7 +519,75 @@ mod tests {
         let url = "file:///path/to/file.parquet";
         let result = read_from_url(url);
 
-        assert!(result.is_err(), "Should fail for URLs without a host");
+        let err = result
+            .err()
+            .expect("Should fail for local file URLs")
+            .to_string();
+        assert!(
+            err.contains("file picker"),
+            "Should explain how to open local files"
+        );
+    }
+
+    #[test]
+    fn test_csv_url_file_name() {
+        assert_eq!(
+            csv_url_file_name("https://example.com/data/file.csv").as_deref(),
+            Some("file.csv")
+        );
+        assert_eq!(
+            csv_url_file_name("https://example.com/data/My%20Data.TSV?download=1").as_deref(),
+            Some("My Data.TSV")
+        );
+        assert_eq!(
+            csv_url_file_name("https://example.com/data/file.parquet"),
+            None
+        );
+        assert_eq!(csv_url_file_name("https://example.com/"), None);
+        assert_eq!(csv_url_file_name("not-a-url"), None);
+    }
+
+    #[test]
+    fn test_read_from_url_parquet_extensions() {
+        let result = read_from_url("https://example.com/data/file.pq").unwrap();
+        assert_eq!(result.table_name.as_str(), "file");
+
+        let result = read_from_url("https://example.com/data/file.PARQ").unwrap();
+        assert_eq!(result.table_name.as_str(), "file");
+
+        let result = read_from_url("https://example.com/output/part-00000").unwrap();
+        assert_eq!(result.table_name.as_str(), "part-00000");
+
+        let result = read_from_url("https://example.com/output/");
+        assert!(result.is_err(), "Should fail for URLs without a file name");
+    }
+
+    #[test]
+    fn test_huggingface_resolve_url() {
+        let url = huggingface_resolve_url(
+            "open-r1/OpenR1-Math-220k",
+            "",
+            "data/train-00003-of-00010.parquet",
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://huggingface.co/datasets/open-r1/OpenR1-Math-220k/resolve/main/data/train-00003-of-00010.parquet",
+        );
+
+        let url = huggingface_resolve_url(
+            "org/dataset",
+            "refs/convert/parquet",
+            "/default/train/0000.parquet",
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://huggingface.co/datasets/org/dataset/resolve/refs%2Fconvert%2Fparquet/default/train/0000.parquet",
+        );
+
+        assert!(huggingface_resolve_url("dataset", "main", "a.parquet").is_err());
+        assert!(huggingface_resolve_url("org/dataset", "main", "").is_err());
     }
 
     #[test]
@@ -179,4 +608,110 @@ mod tests {
             "https://raw.githubusercontent.com/",
         );
     }
+
+    #[test]
+    fn test_next_page_url() {
+        assert_eq!(
+            next_page_url(
+                "<https://huggingface.co/api/datasets/org/d/tree/main?cursor=abc>; rel=\"next\""
+            ),
+            Some("https://huggingface.co/api/datasets/org/d/tree/main?cursor=abc".to_string())
+        );
+        assert_eq!(
+            next_page_url(
+                "<https://a.example/1>; rel=\"prev\", <https://a.example/3>; rel=\"next\""
+            ),
+            Some("https://a.example/3".to_string())
+        );
+        assert_eq!(next_page_url("<https://a.example/1>; rel=\"prev\""), None);
+        assert_eq!(next_page_url(""), None);
+    }
+
+    #[test]
+    fn test_huggingface_split_files() {
+        let tree = serde_json::json!([
+            { "type": "directory", "path": "data" },
+            { "type": "file", "path": "README.md", "size": 1 },
+            { "type": "file", "path": "data/train-00001-of-00002.parquet", "size": 20 },
+            { "type": "file", "path": "data/train-00000-of-00002.parquet", "size": 10 },
+            { "type": "file", "path": "data/test-00000-of-00001.parquet", "size": 5 },
+            { "type": "file", "path": "default/train/0000.parquet", "size": 30 },
+            { "type": "file", "path": "data/trainer-00000.parquet", "size": 7 },
+        ]);
+        let paths = |split: &str| {
+            huggingface_split_files(&tree, split)
+                .iter()
+                .map(|entry| entry.path().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths("train"),
+            vec![
+                "data/train-00000-of-00002.parquet",
+                "data/train-00001-of-00002.parquet",
+                "default/train/0000.parquet",
+            ]
+        );
+        assert_eq!(paths("test"), vec!["data/test-00000-of-00001.parquet"]);
+        assert_eq!(paths("").len(), 5);
+
+        assert_eq!(
+            huggingface_tree_url("org/dataset", "refs/convert/parquet", "default").unwrap(),
+            "https://huggingface.co/api/datasets/org/dataset/tree/refs%2Fconvert%2Fparquet/default?recursive=true",
+        );
+        assert!(huggingface_tree_url("dataset", "", "").is_err());
+        assert_eq!(
+            huggingface_split_files(&tree, "test"),
+            vec![ListingEntry::File {
+                path: "data/test-00000-of-00001.parquet".to_string(),
+                size: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_listing_entries() {
+        let object = |location: &str, size: u64| ObjectMeta {
+            location: Path::from(location),
+            last_modified: Default::default(),
+            size,
+            e_tag: None,
+            version: None,
+        };
+        let listing = ListResult {
+            common_prefixes: vec![Path::from("data/year=2024"), Path::from("data/year=2023")],
+            objects: vec![
+                object("data/part-1.parquet", 20),
+                object("data/_SUCCESS", 0),
+                object("data/part-0.parquet", 10),
+                object("data/part-2", 30),
+                object("data/PART-3.PQ", 40),
+                object("data/.part-0.parquet.crc", 1),
+                object("data/notes.txt", 5),
+            ],
+        };
+        assert_eq!(
+            listing_entries(listing),
+            vec![
+                ListingEntry::Folder("data/year=2023".to_string()),
+                ListingEntry::Folder("data/year=2024".to_string()),
+                ListingEntry::File {
+                    path: "data/PART-3.PQ".to_string(),
+                    size: 40,
+                },
+                ListingEntry::File {
+                    path: "data/part-0.parquet".to_string(),
+                    size: 10,
+                },
+                ListingEntry::File {
+                    path: "data/part-1.parquet".to_string(),
+                    size: 20,
+                },
+                ListingEntry::File {
+                    path: "data/part-2".to_string(),
+                    size: 30,
+                },
+            ]
+        );
+    }
 }
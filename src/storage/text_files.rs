@@ -0,0 +1,152 @@
+//! Converts CSV and newline-delimited JSON files to Parquet. Opened files become an in-memory
+//! Parquet file, so the metadata, schema and query views all work on them unchanged.
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use bytes::Bytes;
+use datafusion::dataframe::DataFrame;
+use datafusion::execution::object_store::ObjectStoreUrl;
+use datafusion::prelude::{CsvReadOptions, NdJsonReadOptions, SessionContext};
+use futures::StreamExt;
+use object_store::ObjectStore;
+use object_store::memory::InMemory;
+use object_store::path::Path;
+use parquet::arrow::ArrowWriter;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys;
+
+use crate::parquet_ctx::IN_MEMORY_SCHEME;
+use crate::views::parquet_reader::ParquetUnresolved;
+
+/// File extensions opened as CSV, matched case-insensitively.
+const CSV_EXTENSIONS: &[&str] = &[".csv", ".tsv"];
+/// File extensions read as newline-delimited JSON, matched case-insensitively.
+const JSON_EXTENSIONS: &[&str] = &[".json", ".jsonl", ".ndjson"];
+
+/// How to parse a CSV file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CsvOptions {
+    pub has_header: bool,
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            has_header: true,
+            delimiter: b',',
+        }
+    }
+}
+
+impl CsvOptions {
+    /// The defaults, with a tab delimiter for `.tsv` files.
+    pub(crate) fn for_file_name(file_name: &str) -> Self {
+        let delimiter = if file_name.to_ascii_lowercase().ends_with(".tsv") {
+            b'\t'
+        } else {
+            b','
+        };
+        Self {
+            delimiter,
+            ..Self::default()
+        }
+    }
+}
+
+fn has_extension(file_name: &str, extensions: &[&str]) -> bool {
+    let lowercase = file_name.to_ascii_lowercase();
+    extensions
+        .iter()
+        .any(|extension| lowercase.ends_with(extension))
+}
+
+pub(crate) fn is_csv_file_name(file_name: &str) -> bool {
+    has_extension(file_name, CSV_EXTENSIONS)
+}
+
+pub(crate) fn is_json_file_name(file_name: &str) -> bool {
+    has_extension(file_name, JSON_EXTENSIONS)
+}
+
+/// A private context holding `data` as `memory://text/<file_name>`, so the source file never
+/// shows up as a table next to the converted one.
+async fn staging_context(file_name: &str, data: Bytes) -> Result<(SessionContext, String)> {
+    let ctx = SessionContext::new();
+    let store = Arc::new(InMemory::new());
+    store.put(&Path::from(file_name), data.into()).await?;
+    ctx.register_object_store(ObjectStoreUrl::parse("memory://text")?.as_ref(), store);
+    Ok((ctx, format!("memory://text/{file_name}")))
+}
+
+/// Writes `df` as a Parquet file batch by batch, so decoded rows never pile up in memory.
+async fn write_parquet(df: DataFrame) -> Result<Vec<u8>> {
+    let schema = df.schema().inner().clone();
+    let mut stream = df.execute_stream().await?;
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buf, schema, None)?;
+    while let Some(batch) = stream.next().await {
+        writer.write(&batch?)?;
+    }
+    writer.close()?;
+    Ok(buf)
+}
+
+/// Parses `data` as CSV with an inferred schema and writes it back out as a Parquet file.
+pub(crate) async fn csv_to_parquet(data: Bytes, options: CsvOptions) -> Result<Vec<u8>> {
+    let (ctx, path) = staging_context("data.csv", data).await?;
+    let df = ctx
+        .read_csv(
+            path,
+            CsvReadOptions::new()
+                .has_header(options.has_header)
+                .delimiter(options.delimiter),
+        )
+        .await?;
+    write_parquet(df).await
+}
+
+/// Parses `data` as newline-delimited JSON, one object per line, with an inferred schema and
+/// writes it back out as a Parquet file.
+pub(crate) async fn json_to_parquet(data: Bytes) -> Result<Vec<u8>> {
+    let (ctx, path) = staging_context("data.json", data).await?;
+    let df = ctx.read_json(path, NdJsonReadOptions::default()).await?;
+    write_parquet(df).await
+}
+
+/// Reads a picked CSV file and stages it as an in-memory Parquet file named after it.
+pub(crate) async fn read_csv_file(
+    file: web_sys::File,
+    options: CsvOptions,
+) -> Result<ParquetUnresolved> {
+    let file_name = file.name();
+    let array_buffer = JsFuture::from(file.array_buffer())
+        .await
+        .map_err(|e| anyhow!("Failed to read {file_name}: {e:?}"))?;
+    let data = Bytes::from(js_sys::Uint8Array::new(&array_buffer).to_vec());
+    stage_csv(&file_name, data, options).await
+}
+
+/// Converts the CSV `data` of `file_name` and stages it as an in-memory Parquet file named
+/// after it.
+pub(crate) async fn stage_csv(
+    file_name: &str,
+    data: Bytes,
+    options: CsvOptions,
+) -> Result<ParquetUnresolved> {
+    let parquet = csv_to_parquet(data, options)
+        .await
+        .map_err(|e| anyhow!("Failed to parse {file_name} as CSV: {e}"))?;
+
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    let parquet_name = format!("{stem}.parquet");
+    let path = Path::parse(&parquet_name)?;
+    let store = Arc::new(InMemory::new());
+    store.put(&path, Bytes::from(parquet).into()).await?;
+    let object_store_url =
+        ObjectStoreUrl::parse(format!("{IN_MEMORY_SCHEME}://{}", uuid::Uuid::new_v4()))?;
+    ParquetUnresolved::try_new(parquet_name, path, object_store_url, store)
+}
@@ -1,7 +1,9 @@
 use std::{
+    cell::RefCell,
     fmt::{Debug, Display, Formatter},
     ops::{Deref, DerefMut, Range},
     pin::Pin,
+    rc::Rc,
     task,
 };
 
@@ -14,8 +16,11 @@ use object_store::{
     MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOptions, PutOptions, PutPayload,
     PutResult, path::Path,
 };
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::js_sys::Uint8Array;
+use web_sys::js_sys::{Array, Function, Object, Reflect, Uint8Array};
+
+use crate::views::parquet_reader::PARQUET_EXTENSIONS;
 
 #[derive(Debug)]
 pub struct WebFileObjectStore {
@@ -28,6 +33,16 @@ impl WebFileObjectStore {
             inner: WebFileReader::new(file),
         }
     }
+
+    /// Reads through a File System Access handle instead of a one-off `File` snapshot.
+    /// Ranges are sliced from a cached `File`; once the file changes on disk and that
+    /// snapshot stops being readable, a fresh one is taken from the handle, so reads stay
+    /// valid for the lifetime of the handle and never buffer the whole file.
+    pub fn from_handle(handle: web_sys::FileSystemFileHandle, file: web_sys::File) -> Self {
+        Self {
+            inner: WebFileReader::from_handle(handle, file),
+        }
+    }
 }
 
 impl Display for WebFileObjectStore {
@@ -70,25 +85,7 @@ impl ObjectStore for WebFileObjectStore {
             });
         }
 
-        let range = match options.range {
-            Some(GetRange::Bounded(r)) => {
-                if r.start >= r.end || r.start >= meta.size {
-                    0..0
-                } else {
-                    let end = r.end.min(meta.size);
-                    r.start..end
-                }
-            }
-            Some(GetRange::Offset(r)) => {
-                if r < meta.size {
-                    r..meta.size
-                } else {
-                    0..0
-                }
-            }
-            Some(GetRange::Suffix(r)) if r < meta.size => (meta.size - r)..meta.size,
-            _ => 0..meta.size,
-        };
+        let range = resolve_get_range(options.range, meta.size);
 
         let web_file_reader_cloned = self.inner.clone();
         let range_for_stream = range.clone();
@@ -148,7 +145,9 @@ impl ObjectStore for WebFileObjectStore {
 
 #[derive(Debug, Clone)]
 pub struct WebFileReader {
-    file: web_sys::File,
+    /// The snapshot ranges are read from, refreshed from `handle` when it goes stale.
+    file: Rc<RefCell<web_sys::File>>,
+    handle: Option<web_sys::FileSystemFileHandle>,
     file_name: String,
 }
 
@@ -158,7 +157,18 @@ unsafe impl Sync for WebFileReader {}
 impl WebFileReader {
     pub fn new(file: web_sys::File) -> Self {
         let file_name = file.name();
-        Self { file, file_name }
+        Self {
+            file: Rc::new(RefCell::new(file)),
+            handle: None,
+            file_name,
+        }
+    }
+
+    pub fn from_handle(handle: web_sys::FileSystemFileHandle, file: web_sys::File) -> Self {
+        Self {
+            handle: Some(handle),
+            ..Self::new(file)
+        }
     }
 
     pub fn file_name(&self) -> &str {
@@ -167,39 +177,157 @@ impl WebFileReader {
 
     /// Get a slice of the file
     pub async fn get_range(&self, range: Range<u64>) -> Result<Bytes, String> {
-        tracing::info!(
-            "get_range: [f64] Fetching range {}...{} from file",
-            range.start as f64,
-            range.end as f64
-        );
-        // Use the slice method to get only the requested range
-        let blob = self
-            .file
-            .slice_with_f64_and_f64(range.start as f64, range.end as f64)
-            .map_err(|e| format!("Failed to slice file: {e:?}"))?;
-
-        let array_buffer = JsFuture::from(blob.array_buffer()).await.unwrap();
-
-        // Convert to Uint8Array and then to a Rust Vec<u8>
-        let uint8_array = Uint8Array::new(&array_buffer);
-        let bytes = Bytes::from(uint8_array.to_vec());
-
-        Ok(bytes)
+        let (start, end) = blob_slice_bounds(&range)?;
+        tracing::info!("get_range: [f64] Fetching range {start}...{end} from file");
+        let file = self.file.borrow().clone();
+        let result = read_blob_slice(&file, start, end).await;
+        let (Err(_), Some(handle)) = (&result, &self.handle) else {
+            return result;
+        };
+        // A `File` snapshot is no longer readable once the file is modified on disk.
+        let file = JsFuture::from(handle.get_file())
+            .await
+            .map_err(|e| format!("Failed to open file handle: {e:?}"))?
+            .unchecked_into::<web_sys::File>();
+        *self.file.borrow_mut() = file.clone();
+        read_blob_slice(&file, start, end).await
     }
 
     pub fn head(&self) -> ObjectMeta {
+        let file = self.file.borrow();
         ObjectMeta {
             location: Path::from(self.file_name.clone()),
-            last_modified: DateTime::from_timestamp(self.file.last_modified() as i64, 0).unwrap(),
-            size: self.file.size() as u64,
+            last_modified: DateTime::from_timestamp(file.last_modified() as i64, 0).unwrap(),
+            size: file.size() as u64,
             e_tag: None,
             version: None,
         }
     }
 }
 
-struct SendWrapper<T> {
-    inner: T,
+async fn read_blob_slice(file: &web_sys::File, start: f64, end: f64) -> Result<Bytes, String> {
+    // Use the slice method to get only the requested range
+    let blob = file
+        .slice_with_f64_and_f64(start, end)
+        .map_err(|e| format!("Failed to slice file: {e:?}"))?;
+
+    let array_buffer = JsFuture::from(blob.array_buffer())
+        .await
+        .map_err(|e| format!("Failed to read file range: {e:?}"))?;
+
+    // Convert to Uint8Array and then to a Rust Vec<u8>
+    let uint8_array = Uint8Array::new(&array_buffer);
+    Ok(Bytes::from(uint8_array.to_vec()))
+}
+
+/// Whether the browser exposes the File System Access API (`window.showOpenFilePicker`).
+pub fn supports_file_system_access() -> bool {
+    web_sys::window()
+        .map(|window| {
+            Reflect::has(&window, &JsValue::from_str("showOpenFilePicker")).unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Prompts for a single Parquet file and returns its handle along with a `File` snapshot.
+/// Returns `Ok(None)` when the user dismisses the picker.
+pub async fn pick_file_handle()
+-> Result<Option<(web_sys::FileSystemFileHandle, web_sys::File)>, String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    let picker = Reflect::get(&window, &JsValue::from_str("showOpenFilePicker"))
+        .ok()
+        .and_then(|f| f.dyn_into::<Function>().ok())
+        .ok_or("File System Access API is not supported by this browser")?;
+
+    let accept = Object::new();
+    let extensions = PARQUET_EXTENSIONS
+        .iter()
+        .map(|extension| JsValue::from_str(extension))
+        .collect::<Array>();
+    let _ = Reflect::set(
+        &accept,
+        &JsValue::from_str("application/vnd.apache.parquet"),
+        &extensions,
+    );
+    let file_type = Object::new();
+    let _ = Reflect::set(
+        &file_type,
+        &JsValue::from_str("description"),
+        &JsValue::from_str("Parquet"),
+    );
+    let _ = Reflect::set(&file_type, &JsValue::from_str("accept"), &accept);
+    let options = Object::new();
+    let _ = Reflect::set(
+        &options,
+        &JsValue::from_str("types"),
+        &Array::of1(&file_type),
+    );
+
+    let promise = picker
+        .call1(&window, &options)
+        .map_err(|e| format!("Failed to open file picker: {e:?}"))?;
+    let handles = match JsFuture::from(web_sys::js_sys::Promise::from(promise)).await {
+        Ok(handles) => Array::from(&handles),
+        // The picker rejects with `AbortError` when dismissed.
+        Err(_) => return Ok(None),
+    };
+    let Some(handle) = handles
+        .iter()
+        .next()
+        .and_then(|h| h.dyn_into::<web_sys::FileSystemFileHandle>().ok())
+    else {
+        return Ok(None);
+    };
+    let file = JsFuture::from(handle.get_file())
+        .await
+        .map_err(|e| format!("Failed to open file handle: {e:?}"))?
+        .unchecked_into::<web_sys::File>();
+    Ok(Some((handle, file)))
+}
+
+/// Largest offset a JS number holds exactly (`Number.MAX_SAFE_INTEGER`).
+const MAX_SAFE_OFFSET: u64 = (1 << 53) - 1;
+
+/// `Blob.slice` bounds for `range`. Offsets are passed as JS numbers rather than `i32`, which
+/// would overflow past 2 GiB. Offsets past 2^53 would be rounded to a neighbouring byte, so
+/// they are rejected rather than read from the wrong place.
+fn blob_slice_bounds(range: &Range<u64>) -> Result<(f64, f64), String> {
+    if range.end > MAX_SAFE_OFFSET {
+        return Err(format!(
+            "Range {}..{} is past the largest offset a browser can read exactly",
+            range.start, range.end
+        ));
+    }
+    Ok((range.start as f64, range.end as f64))
+}
+
+/// The bytes of a `size`-byte object that `range` asks for, clamped to the object; an
+/// out-of-bounds range reads nothing.
+pub(crate) fn resolve_get_range(range: Option<GetRange>, size: u64) -> Range<u64> {
+    match range {
+        Some(GetRange::Bounded(r)) => {
+            if r.start >= r.end || r.start >= size {
+                0..0
+            } else {
+                let end = r.end.min(size);
+                r.start..end
+            }
+        }
+        Some(GetRange::Offset(r)) => {
+            if r < size {
+                r..size
+            } else {
+                0..0
+            }
+        }
+        Some(GetRange::Suffix(r)) if r < size => (size - r)..size,
+        _ => 0..size,
+    }
+}
+
+/// Marks a browser future as `Send`, which is sound on single-threaded wasm.
+pub(crate) struct SendWrapper<T> {
+    pub(crate) inner: T,
 }
 
 unsafe impl<T> Send for SendWrapper<T> {}
@@ -227,3 +355,29 @@ impl<T: Future> Future for SendWrapper<T> {
         unsafe { self.map_unchecked_mut(Self::deref_mut) }.poll(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_slice_bounds_past_2gib() {
+        let start = 5 * 1024 * 1024 * 1024 + 7;
+        let (lo, hi) = blob_slice_bounds(&(start..start + 4096)).unwrap();
+        assert_eq!(lo, 5_368_709_127.0);
+        assert_eq!(hi - lo, 4096.0);
+        assert!(lo > i32::MAX as f64);
+    }
+
+    #[test]
+    fn test_blob_slice_bounds_past_2_pow_53() {
+        let (lo, hi) = blob_slice_bounds(&(MAX_SAFE_OFFSET - 1..MAX_SAFE_OFFSET)).unwrap();
+        assert_eq!(lo as u64, MAX_SAFE_OFFSET - 1);
+        assert_eq!(hi as u64, MAX_SAFE_OFFSET);
+
+        // 2^53 + 1 has no exact f64; it would silently read the byte before it.
+        assert_eq!((MAX_SAFE_OFFSET + 2) as f64 as u64, MAX_SAFE_OFFSET + 1);
+        assert!(blob_slice_bounds(&(MAX_SAFE_OFFSET..MAX_SAFE_OFFSET + 2)).is_err());
+        assert!(blob_slice_bounds(&(0..u64::MAX)).is_err());
+    }
+}
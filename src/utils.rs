@@ -1,21 +1,41 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
-use anyhow::Result;
-use arrow_array::RecordBatch;
-use arrow_schema::{DataType, Field};
+use anyhow::{Result, anyhow};
+use arrow::compute::concat_batches;
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BinaryViewArray, Int64Array, LargeBinaryArray, LargeStringArray,
+    RecordBatch, StringArray, StringViewArray, UInt64Array,
+    cast::AsArray,
+    types::{
+        DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType,
+        DurationSecondType, Float32Type, Float64Type, IntervalDayTimeType,
+        IntervalMonthDayNanoType, IntervalYearMonthType,
+    },
+};
+use arrow_cast::display::array_value_to_string;
+use arrow_schema::{DataType, Field, IntervalUnit, Schema, SchemaRef, TimeUnit};
+use byte_unit::{Byte, UnitType};
 use bytes::{Buf, Bytes};
 use datafusion::{
     dataframe::DataFrame,
     physical_plan::{ExecutionPlan, SendableRecordBatchStream, collect, execute_stream},
     prelude::SessionContext,
 };
-use futures::StreamExt;
+use futures::{Stream, StreamExt, TryStreamExt};
 use parquet::{
-    arrow::{ArrowWriter, async_reader::AsyncFileReader},
+    arrow::{
+        ArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask,
+        arrow_reader::{ArrowReaderMetadata, RowSelection, RowSelector},
+        async_reader::AsyncFileReader,
+    },
+    basic::{PageType, Type as PhysicalType},
+    bloom_filter::Sbbf,
     errors::ParquetError,
     file::{
-        metadata::ParquetMetaData,
+        metadata::{PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader},
+        properties::WriterProperties,
         reader::{ChunkReader, Length, SerializedPageReader},
+        statistics::Statistics,
     },
 };
 use web_sys::{
@@ -23,14 +43,308 @@ use web_sys::{
     wasm_bindgen::{JsCast, JsValue},
 };
 
+use crate::{
+    page_header::{PageCrc, page_crcs},
+    views::settings::number_locale,
+};
+
+/// Digit grouping and decimal marks used when displaying numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum NumberLocale {
+    /// `1,234.5`
+    #[default]
+    English,
+    /// `1.234,5`
+    German,
+    /// `1 234,5`, grouped with a non-breaking space.
+    French,
+    /// `1'234.5`
+    Swiss,
+}
+
+impl NumberLocale {
+    pub(crate) const ALL: [NumberLocale; 4] = [
+        NumberLocale::English,
+        NumberLocale::German,
+        NumberLocale::French,
+        NumberLocale::Swiss,
+    ];
+
+    /// Value saved in settings.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            NumberLocale::English => "en",
+            NumberLocale::German => "de",
+            NumberLocale::French => "fr",
+            NumberLocale::Swiss => "ch",
+        }
+    }
+
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|locale| locale.key() == key)
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            NumberLocale::English => "1,234.5 (English)",
+            NumberLocale::German => "1.234,5 (German)",
+            NumberLocale::French => "1 234,5 (French)",
+            NumberLocale::Swiss => "1'234.5 (Swiss)",
+        }
+    }
+
+    fn group_separator(self) -> char {
+        match self {
+            NumberLocale::English => ',',
+            NumberLocale::German => '.',
+            NumberLocale::French => '\u{a0}',
+            NumberLocale::Swiss => '\'',
+        }
+    }
+
+    fn decimal_separator(self) -> char {
+        match self {
+            NumberLocale::English | NumberLocale::Swiss => '.',
+            NumberLocale::German | NumberLocale::French => ',',
+        }
+    }
+
+    /// Rewrites a plain number such as `-1234.5` with this locale's decimal mark, grouping
+    /// thousands when `group` is set. Anything that is not a plain number is returned as is.
+    pub(crate) fn format_number(self, number: &str, group: bool) -> String {
+        let (sign, unsigned) = match number.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", number),
+        };
+        let (int_part, frac_part) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+        if int_part.is_empty() || !int_part.bytes().all(|b| b.is_ascii_digit()) {
+            return number.to_string();
+        }
+        let mut result = sign.to_string();
+        for (i, digit) in int_part.chars().enumerate() {
+            if group && i > 0 && (int_part.len() - i) % 3 == 0 {
+                result.push(self.group_separator());
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = frac_part.strip_prefix('.') {
+            result.push(self.decimal_separator());
+            result.push_str(fraction);
+        }
+        result
+    }
+}
+
+/// Row and value counts with thousands grouped per the locale in settings.
 pub fn format_rows(rows: u64) -> String {
-    let mut result = rows.to_string();
-    let mut i = result.len();
-    while i > 3 {
-        i -= 3;
-        result.insert(i, ',');
+    number_locale().format_number(&rows.to_string(), true)
+}
+
+/// `value` with `places` decimals, in the locale from settings.
+pub(crate) fn format_decimal(value: f64, places: usize) -> String {
+    number_locale().format_number(&format!("{value:.places$}"), false)
+}
+
+/// Byte size in binary units with two decimals, e.g. `1.50 MiB`, in the locale from settings.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let size = format!(
+        "{:.2}",
+        Byte::from_u64(bytes).get_appropriate_unit(UnitType::Binary)
+    );
+    match size.split_once(' ') {
+        Some((value, unit)) => format!("{} {unit}", number_locale().format_number(value, false)),
+        None => size,
+    }
+}
+
+/// How numeric cells in query results are displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct NumberFormat {
+    pub thousands_separator: bool,
+    /// Fixed number of decimal places for floats, `None` keeps the shortest representation.
+    pub decimal_places: Option<usize>,
+    pub locale: NumberLocale,
+}
+
+impl NumberFormat {
+    pub(crate) fn is_raw(&self) -> bool {
+        !self.thousands_separator
+            && self.decimal_places.is_none()
+            && self.locale == NumberLocale::English
+    }
+}
+
+/// Formats a numeric cell according to `format`; returns `None` for nulls and non-numeric
+/// types so callers can fall back to `array_value_to_string`.
+pub(crate) fn format_numeric_value(
+    array: &dyn Array,
+    row: usize,
+    format: NumberFormat,
+) -> Option<String> {
+    if format.is_raw() || array.is_null(row) {
+        return None;
+    }
+    let data_type = array.data_type();
+    let value = match data_type {
+        DataType::Float32 | DataType::Float64 => {
+            let value = match data_type {
+                DataType::Float32 => array.as_primitive::<Float32Type>().value(row) as f64,
+                _ => array.as_primitive::<Float64Type>().value(row),
+            };
+            if !value.is_finite() {
+                return None;
+            }
+            match format.decimal_places {
+                Some(places) => format!("{value:.places$}"),
+                None => array_value_to_string(array, row).ok()?,
+            }
+        }
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            array_value_to_string(array, row).ok()?
+        }
+        data_type if data_type.is_integer() => array_value_to_string(array, row).ok()?,
+        _ => return None,
+    };
+    Some(
+        format
+            .locale
+            .format_number(&value, format.thousands_separator),
+    )
+}
+
+/// Bytes of binary values shown before [`bytes_display`] truncates them.
+const BYTES_DISPLAY_LIMIT: usize = 16;
+
+/// Lowercase hex of `bytes`, prefixed with `0x`.
+pub(crate) fn bytes_to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for b in bytes {
+        hex.push_str(&format!("{b:02x}"));
+    }
+    hex
+}
+
+/// Shows `bytes` as text when they are valid UTF-8, otherwise as hex truncated to
+/// [`BYTES_DISPLAY_LIMIT`] bytes; use [`bytes_to_hex`] for the full value.
+pub(crate) fn bytes_display(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) if bytes.len() > BYTES_DISPLAY_LIMIT => {
+            format!("{}…", bytes_to_hex(&bytes[..BYTES_DISPLAY_LIMIT]))
+        }
+        Err(_) => bytes_to_hex(bytes),
+    }
+}
+
+fn time_unit_suffix(unit: &TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Second => "s",
+        TimeUnit::Millisecond => "ms",
+        TimeUnit::Microsecond => "us",
+        TimeUnit::Nanosecond => "ns",
+    }
+}
+
+/// Formats a signed span of nanoseconds as e.g. `3d 4h 5m 6.25s`, omitting zero components.
+pub(crate) fn format_duration_nanos(nanos: i128) -> String {
+    const NANOS_PER_SECOND: i128 = 1_000_000_000;
+    if nanos == 0 {
+        return "0s".to_string();
     }
-    result
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.unsigned_abs();
+    let total_seconds = nanos / NANOS_PER_SECOND as u128;
+    let subsec_nanos = nanos % NANOS_PER_SECOND as u128;
+    let (days, hours, minutes, seconds) = (
+        total_seconds / 86_400,
+        total_seconds % 86_400 / 3_600,
+        total_seconds % 3_600 / 60,
+        total_seconds % 60,
+    );
+
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{days}d"));
+    }
+    if hours > 0 {
+        parts.push(format!("{hours}h"));
+    }
+    if minutes > 0 {
+        parts.push(format!("{minutes}m"));
+    }
+    if subsec_nanos > 0 {
+        let fraction = format!("{subsec_nanos:09}");
+        parts.push(format!("{seconds}.{}s", fraction.trim_end_matches('0')));
+    } else if seconds > 0 {
+        parts.push(format!("{seconds}s"));
+    }
+    format!("{sign}{}", parts.join(" "))
+}
+
+/// Formats calendar months as e.g. `1y 2mo`.
+fn format_months(months: i32) -> Option<String> {
+    if months == 0 {
+        return None;
+    }
+    let sign = if months < 0 { "-" } else { "" };
+    let months = months.unsigned_abs();
+    let parts = [(months / 12, "y"), (months % 12, "mo")]
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, suffix)| format!("{value}{suffix}"))
+        .collect::<Vec<_>>();
+    Some(format!("{sign}{}", parts.join(" ")))
+}
+
+/// Joins the calendar and fixed-length parts of an interval, e.g. `1y 2mo 3d 4h`.
+fn format_interval(months: i32, days: i32, nanos: i128) -> String {
+    let mut parts = Vec::new();
+    parts.extend(format_months(months));
+    if days != 0 {
+        parts.push(format!("{days}d"));
+    }
+    if nanos != 0 || parts.is_empty() {
+        parts.push(format_duration_nanos(nanos));
+    }
+    parts.join(" ")
+}
+
+/// Formats `Duration` and `Interval` cells in human-readable units; returns `None` for nulls
+/// and other types so callers can fall back to `array_value_to_string`.
+pub(crate) fn format_temporal_value(array: &dyn Array, row: usize) -> Option<String> {
+    if array.is_null(row) {
+        return None;
+    }
+    let value = match array.data_type() {
+        DataType::Duration(TimeUnit::Second) => format_duration_nanos(
+            array.as_primitive::<DurationSecondType>().value(row) as i128 * 1_000_000_000,
+        ),
+        DataType::Duration(TimeUnit::Millisecond) => format_duration_nanos(
+            array.as_primitive::<DurationMillisecondType>().value(row) as i128 * 1_000_000,
+        ),
+        DataType::Duration(TimeUnit::Microsecond) => format_duration_nanos(
+            array.as_primitive::<DurationMicrosecondType>().value(row) as i128 * 1_000,
+        ),
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            format_duration_nanos(array.as_primitive::<DurationNanosecondType>().value(row) as i128)
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => format_interval(
+            array.as_primitive::<IntervalYearMonthType>().value(row),
+            0,
+            0,
+        ),
+        DataType::Interval(IntervalUnit::DayTime) => {
+            let value = array.as_primitive::<IntervalDayTimeType>().value(row);
+            format_interval(0, value.days, value.milliseconds as i128 * 1_000_000)
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            let value = array.as_primitive::<IntervalMonthDayNanoType>().value(row);
+            format_interval(value.months, value.days, value.nanoseconds as i128)
+        }
+        _ => return None,
+    };
+    Some(value)
 }
 
 pub(crate) fn get_stored_value(key: &str) -> Option<String> {
@@ -53,6 +367,14 @@ pub fn format_arrow_type(data_type: &DataType) -> String {
         DataType::Utf8 => "String".to_string(),
         DataType::Struct(fields) => format_struct_type(fields),
         DataType::List(child) => format!("List<{}>", format_arrow_type(child.data_type())),
+        DataType::Decimal128(precision, scale) => format!("Decimal({precision}, {scale})"),
+        DataType::Decimal256(precision, scale) => format!("Decimal256({precision}, {scale})"),
+        DataType::Duration(unit) => format!("Duration[{}]", time_unit_suffix(unit)),
+        DataType::Interval(unit) => match unit {
+            IntervalUnit::YearMonth => "Interval[year-month]".to_string(),
+            IntervalUnit::DayTime => "Interval[day-time]".to_string(),
+            IntervalUnit::MonthDayNano => "Interval[month-day-nano]".to_string(),
+        },
         _ => data_type.to_string(),
     }
 }
@@ -70,6 +392,13 @@ pub fn format_struct_type(fields: &[Arc<Field>]) -> String {
     format!("Struct{{{}}}", field_strs.join(", "))
 }
 
+/// Lets the browser handle input and paint before continuing. Futures that are always ready,
+/// e.g. reads served from the cache, otherwise run back to back without the page updating, so
+/// long scans await this between steps.
+pub(crate) async fn yield_to_browser() {
+    gloo_timers::future::TimeoutFuture::new(0).await;
+}
+
 pub(crate) async fn execute_query_inner(
     query: &str,
     ctx: &SessionContext,
@@ -138,7 +467,7 @@ pub(crate) fn send_message_to_vscode(message_type: &str, vscode: &JsValue) {
     }
 }
 
-fn download_data(file_name: &str, data: Vec<u8>) {
+pub(crate) fn download_data(file_name: &str, data: Vec<u8>) {
     let blob =
         web_sys::Blob::new_with_u8_array_sequence(&js_sys::Array::of1(&data.into())).unwrap();
     let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
@@ -154,63 +483,474 @@ fn download_data(file_name: &str, data: Vec<u8>) {
     web_sys::Url::revoke_object_url(&url).unwrap();
 }
 
-pub(crate) fn export_to_csv_inner(query_result: &[RecordBatch]) {
+/// Writes `text` to the system clipboard; failures are ignored.
+pub(crate) fn copy_to_clipboard(text: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.navigator().clipboard().write_text(text);
+    }
+}
+
+/// Delimiter, header, quoting and null handling for CSV export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CsvExportOptions {
+    pub delimiter: u8,
+    pub header: bool,
+    pub null_value: String,
+    /// Quote every field instead of only those containing a delimiter, quote or newline.
+    pub quote_all: bool,
+    /// Longer string and binary cells are cut to this many characters (bytes for binary).
+    pub max_value_len: Option<usize>,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            header: true,
+            null_value: String::new(),
+            quote_all: false,
+            max_value_len: None,
+        }
+    }
+}
+
+pub(crate) fn write_csv(
+    query_result: &[RecordBatch],
+    options: &CsvExportOptions,
+) -> Result<Vec<u8>> {
     let mut data = Vec::new();
-    let mut writer = arrow::csv::WriterBuilder::new().build(&mut data);
+    let mut builder = arrow::csv::WriterBuilder::new()
+        .with_delimiter(options.delimiter)
+        .with_header(options.header)
+        .with_null(options.null_value.clone());
+    if options.quote_all {
+        builder = builder.with_quote_style(arrow::csv::QuoteStyle::Always);
+    }
+    let mut writer = builder.build(&mut data);
     for batch in query_result {
-        writer.write(batch).unwrap();
+        match options.max_value_len {
+            Some(max_len) => writer.write(&truncate_long_values(batch, max_len)?)?,
+            None => writer.write(batch)?,
+        }
     }
     drop(writer);
-    download_data("query_results.csv", data);
+    Ok(data)
 }
 
-pub(crate) fn export_to_parquet_inner(query_result: &[RecordBatch]) {
+/// Downloads `query_result` as CSV, or TSV when tab-delimited. Fails on columns CSV cannot
+/// hold, e.g. nested types.
+pub(crate) fn export_to_csv_inner(
+    query_result: &[RecordBatch],
+    options: &CsvExportOptions,
+) -> Result<()> {
+    let data = write_csv(query_result, options)?;
+    let file_name = if options.delimiter == b'\t' {
+        "query_results.tsv"
+    } else {
+        "query_results.csv"
+    };
+    download_data(file_name, data);
+    Ok(())
+}
+
+fn truncate_str(value: &str, max_len: usize) -> Cow<'_, str> {
+    match value.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}…", &value[..end]).into(),
+        None => value.into(),
+    }
+}
+
+fn truncate_array(array: &ArrayRef, max_len: usize) -> ArrayRef {
+    match array.data_type() {
+        DataType::Utf8 => Arc::new(
+            array
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.map(|v| truncate_str(v, max_len)))
+                .collect::<StringArray>(),
+        ),
+        DataType::LargeUtf8 => Arc::new(
+            array
+                .as_string::<i64>()
+                .iter()
+                .map(|v| v.map(|v| truncate_str(v, max_len)))
+                .collect::<LargeStringArray>(),
+        ),
+        DataType::Utf8View => Arc::new(
+            array
+                .as_string_view()
+                .iter()
+                .map(|v| v.map(|v| truncate_str(v, max_len)))
+                .collect::<StringViewArray>(),
+        ),
+        DataType::Binary => Arc::new(
+            array
+                .as_binary::<i32>()
+                .iter()
+                .map(|v| v.map(|v| &v[..v.len().min(max_len)]))
+                .collect::<BinaryArray>(),
+        ),
+        DataType::LargeBinary => Arc::new(
+            array
+                .as_binary::<i64>()
+                .iter()
+                .map(|v| v.map(|v| &v[..v.len().min(max_len)]))
+                .collect::<LargeBinaryArray>(),
+        ),
+        DataType::BinaryView => Arc::new(
+            array
+                .as_binary_view()
+                .iter()
+                .map(|v| v.map(|v| &v[..v.len().min(max_len)]))
+                .collect::<BinaryViewArray>(),
+        ),
+        _ => array.clone(),
+    }
+}
+
+/// Cuts string cells longer than `max_len` characters (marked with a trailing "…") and binary
+/// cells longer than `max_len` bytes. Nested and other columns are kept as is.
+pub(crate) fn truncate_long_values(batch: &RecordBatch, max_len: usize) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| truncate_array(column, max_len))
+        .collect();
+    Ok(RecordBatch::try_new(batch.schema(), columns)?)
+}
+
+/// Serializes `query_result` as an LZ4-compressed Parquet file.
+pub(crate) fn write_parquet(query_result: &[RecordBatch]) -> Result<Vec<u8>> {
     let mut buf = Vec::new();
 
     let props = parquet::file::properties::WriterProperties::builder()
         .set_compression(parquet::basic::Compression::LZ4)
         .build();
 
-    let mut writer = ArrowWriter::try_new(&mut buf, query_result[0].schema(), Some(props))
-        .expect("Failed to create parquet writer");
+    let mut writer = ArrowWriter::try_new(&mut buf, query_result[0].schema(), Some(props))?;
 
     // Write all record batches
     for batch in query_result {
-        writer.write(batch).expect("Failed to write record batch");
+        writer.write(batch)?;
     }
 
-    writer.close().expect("Failed to close writer");
+    writer.close()?;
+    Ok(buf)
+}
 
+pub(crate) fn export_to_parquet_inner(query_result: &[RecordBatch]) {
+    let buf = write_parquet(query_result).expect("Failed to write parquet");
     download_data("query_results.parquet", buf);
 }
 
-/// Counts the number of pages in a column chunk by reading and iterating through all pages.
-pub async fn count_column_chunk_pages(
+/// Stored min and max of a column chunk as text; floats keep full precision and binary values
+/// that are not UTF-8 become hex.
+fn statistics_min_max(stats: &Statistics) -> (Option<String>, Option<String>) {
+    fn bytes(value: &[u8]) -> String {
+        std::str::from_utf8(value)
+            .map(str::to_string)
+            .unwrap_or_else(|_| bytes_to_hex(value))
+    }
+    match stats {
+        Statistics::Boolean(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Int32(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Int64(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Int96(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Float(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::Double(s) => (
+            s.min_opt().map(|v| v.to_string()),
+            s.max_opt().map(|v| v.to_string()),
+        ),
+        Statistics::ByteArray(s) => (
+            s.min_opt().map(|v| bytes(v.data())),
+            s.max_opt().map(|v| bytes(v.data())),
+        ),
+        Statistics::FixedLenByteArray(s) => (
+            s.min_opt().map(|v| bytes(v.data())),
+            s.max_opt().map(|v| bytes(v.data())),
+        ),
+    }
+}
+
+/// One row per (row group, column chunk) with its stored statistics and sizes, for
+/// analyzing data layout outside the viewer.
+pub(crate) fn row_group_statistics_batch(metadata: &ParquetMetaData) -> Result<RecordBatch> {
+    let mut row_group = Vec::new();
+    let mut column = Vec::new();
+    let mut physical_type = Vec::new();
+    let mut num_values = Vec::new();
+    let mut null_count = Vec::new();
+    let mut distinct_count = Vec::new();
+    let mut min = Vec::new();
+    let mut max = Vec::new();
+    let mut compressed_size = Vec::new();
+    let mut uncompressed_size = Vec::new();
+
+    for (rg_index, rg) in metadata.row_groups().iter().enumerate() {
+        for chunk in rg.columns() {
+            let stats = chunk.statistics();
+            let (chunk_min, chunk_max) = stats.map(statistics_min_max).unwrap_or_default();
+            row_group.push(rg_index as u64);
+            column.push(chunk.column_path().string());
+            physical_type.push(chunk.column_type().to_string());
+            num_values.push(chunk.num_values());
+            null_count.push(stats.and_then(|s| s.null_count_opt()));
+            distinct_count.push(stats.and_then(|s| s.distinct_count_opt()));
+            min.push(chunk_min);
+            max.push(chunk_max);
+            compressed_size.push(chunk.compressed_size());
+            uncompressed_size.push(chunk.uncompressed_size());
+        }
+    }
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("row_group", DataType::UInt64, false),
+        Field::new("column", DataType::Utf8, false),
+        Field::new("physical_type", DataType::Utf8, false),
+        Field::new("num_values", DataType::Int64, false),
+        Field::new("null_count", DataType::UInt64, true),
+        Field::new("distinct_count", DataType::UInt64, true),
+        Field::new("min", DataType::Utf8, true),
+        Field::new("max", DataType::Utf8, true),
+        Field::new("compressed_size", DataType::Int64, false),
+        Field::new("uncompressed_size", DataType::Int64, false),
+    ]));
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(UInt64Array::from(row_group)),
+            Arc::new(StringArray::from(column)),
+            Arc::new(StringArray::from(physical_type)),
+            Arc::new(Int64Array::from(num_values)),
+            Arc::new(UInt64Array::from(null_count)),
+            Arc::new(UInt64Array::from(distinct_count)),
+            Arc::new(StringArray::from(min)),
+            Arc::new(StringArray::from(max)),
+            Arc::new(Int64Array::from(compressed_size)),
+            Arc::new(Int64Array::from(uncompressed_size)),
+        ],
+    )?)
+}
+
+/// Serializes `query_result` as a JSON array of row objects, truncating long values to
+/// `max_value_len` if set.
+pub(crate) fn write_json(
+    query_result: &[RecordBatch],
+    max_value_len: Option<usize>,
+) -> Result<Vec<u8>> {
+    write_json_format::<arrow::json::writer::JsonArray>(query_result, max_value_len)
+}
+
+/// Same as [`write_json`], but one JSON object per line (JSON Lines) instead of an array.
+pub(crate) fn write_json_lines(
+    query_result: &[RecordBatch],
+    max_value_len: Option<usize>,
+) -> Result<Vec<u8>> {
+    write_json_format::<arrow::json::writer::LineDelimited>(query_result, max_value_len)
+}
+
+fn write_json_format<F: arrow::json::writer::JsonFormat>(
+    query_result: &[RecordBatch],
+    max_value_len: Option<usize>,
+) -> Result<Vec<u8>> {
+    let mut writer = arrow::json::Writer::<_, F>::new(Vec::new());
+    for batch in query_result {
+        match max_value_len {
+            Some(max_len) => writer.write(&truncate_long_values(batch, max_len)?)?,
+            None => writer.write(batch)?,
+        }
+    }
+    writer.finish()?;
+    Ok(writer.into_inner())
+}
+
+pub(crate) fn export_to_json_inner(
+    query_result: &[RecordBatch],
+    max_value_len: Option<usize>,
+) -> Result<()> {
+    let data = write_json(query_result, max_value_len)?;
+    download_data("query_results.json", data);
+    Ok(())
+}
+
+pub(crate) fn export_to_json_lines_inner(
+    query_result: &[RecordBatch],
+    max_value_len: Option<usize>,
+) -> Result<()> {
+    let data = write_json_lines(query_result, max_value_len)?;
+    download_data("query_results.jsonl", data);
+    Ok(())
+}
+
+/// Serializes `schema` as an Arrow IPC stream holding only the schema message.
+pub(crate) fn schema_to_ipc(schema: &SchemaRef) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut writer = arrow::ipc::writer::StreamWriter::try_new(&mut data, schema)?;
+    writer.finish()?;
+    drop(writer);
+    Ok(data)
+}
+
+/// Serializes `schema` with Arrow's serde representation.
+pub(crate) fn schema_to_json(schema: &SchemaRef) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec_pretty(schema.as_ref())?)
+}
+
+/// Writes a zero-row parquet file with `schema`, to be used as a schema template.
+pub(crate) fn empty_parquet_with_schema(
+    schema: SchemaRef,
+    props: WriterProperties,
+) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buf, schema.clone(), Some(props))?;
+    writer.write(&RecordBatch::new_empty(schema))?;
+    writer.close()?;
+    Ok(buf)
+}
+
+/// Reads the bytes of `column_id`'s chunk in `row_group_id`.
+async fn read_column_chunk(
     column_reader: &mut impl AsyncFileReader,
     metadata: &ParquetMetaData,
     row_group_id: usize,
     column_id: usize,
-) -> Result<usize> {
-    let row_group = metadata.row_group(row_group_id);
-    let column_chunk = row_group.column(column_id);
-    let byte_range = column_chunk.byte_range();
-
-    let bytes = column_reader
-        .get_bytes(byte_range.0..(byte_range.0 + byte_range.1))
-        .await?;
+) -> Result<Bytes> {
+    let (start, length) = metadata
+        .row_group(row_group_id)
+        .column(column_id)
+        .byte_range();
+    Ok(column_reader.get_bytes(start..(start + length)).await?)
+}
 
-    let chunk = ColumnChunk::new(bytes, byte_range);
+/// Fetches `column_id`'s chunk in every row group, keeping up to `concurrency` range reads
+/// in flight. Chunks are yielded in row group order.
+pub fn prefetch_column_chunks<'a, R>(
+    reader: &R,
+    metadata: &'a ParquetMetaData,
+    column_id: usize,
+    concurrency: usize,
+) -> impl Stream<Item = Result<(usize, Bytes)>> + 'a
+where
+    R: AsyncFileReader + Clone + 'a,
+{
+    let reader = reader.clone();
+    futures::stream::iter(0..metadata.num_row_groups())
+        .map(move |row_group_id| {
+            let mut reader = reader.clone();
+            async move {
+                let bytes = read_column_chunk(&mut reader, metadata, row_group_id, column_id)
+                    .await
+                    .map_err(|e| anyhow!("row group {row_group_id}: {e}"))?;
+                Ok((row_group_id, bytes))
+            }
+        })
+        .buffered(concurrency.max(1))
+}
 
-    // Create a page reader
-    let page_reader = SerializedPageReader::new(
+fn chunk_page_reader(
+    metadata: &ParquetMetaData,
+    row_group_id: usize,
+    column_id: usize,
+    bytes: Bytes,
+) -> Result<SerializedPageReader<ColumnChunk>> {
+    let row_group = metadata.row_group(row_group_id);
+    let column_chunk = row_group.column(column_id);
+    let chunk = ColumnChunk::new(bytes, column_chunk.byte_range());
+    Ok(SerializedPageReader::new(
         Arc::new(chunk),
         column_chunk,
         row_group.num_rows() as usize,
         None,
-    )?;
+    )?)
+}
+
+/// Counts the number of pages in a column chunk by reading and iterating through all pages.
+pub async fn count_column_chunk_pages(
+    column_reader: &mut impl AsyncFileReader,
+    metadata: &ParquetMetaData,
+    row_group_id: usize,
+    column_id: usize,
+) -> Result<usize> {
+    let bytes = read_column_chunk(column_reader, metadata, row_group_id, column_id).await?;
+    let page_reader = chunk_page_reader(metadata, row_group_id, column_id, bytes)?;
+    Ok(page_reader.flatten().count())
+}
+
+/// Returns metadata with the column and offset indexes loaded, fetching them if they
+/// were not preloaded with the footer.
+pub async fn load_page_index(
+    reader: &mut impl AsyncFileReader,
+    metadata: Arc<ParquetMetaData>,
+) -> Result<Arc<ParquetMetaData>> {
+    if metadata.column_index().is_some() || metadata.offset_index().is_some() {
+        return Ok(metadata);
+    }
+
+    let mut metadata_reader = ParquetMetaDataReader::new_with_metadata(metadata.as_ref().clone())
+        .with_page_index_policy(PageIndexPolicy::Optional);
+    metadata_reader.load_page_index(reader).await?;
+    Ok(Arc::new(metadata_reader.finish()?))
+}
+
+/// Checks `value` against the bloom filter of `column_id` in `row_group_id`. `None` if the
+/// chunk has no bloom filter, otherwise whether the value may be present.
+pub async fn probe_bloom_filter<R: AsyncFileReader + Send + 'static>(
+    reader: R,
+    metadata: Arc<ParquetMetaData>,
+    row_group_id: usize,
+    column_id: usize,
+    value: &str,
+) -> Result<Option<bool>> {
+    let physical_type = metadata
+        .row_group(row_group_id)
+        .column(column_id)
+        .column_type();
+    let reader_metadata = ArrowReaderMetadata::try_new(metadata, Default::default())?;
+    let mut builder = ParquetRecordBatchStreamBuilder::new_with_metadata(reader, reader_metadata);
+    let Some(sbbf) = builder
+        .get_row_group_column_bloom_filter(row_group_id, column_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    bloom_filter_check(&sbbf, physical_type, value).map(Some)
+}
 
-    let page_count = page_reader.flatten().count();
-    Ok(page_count)
+/// Parses `value` as the column's physical type and checks it against `sbbf`, since the filter
+/// hashes the plain-encoded physical value.
+pub(crate) fn bloom_filter_check(
+    sbbf: &Sbbf,
+    physical_type: PhysicalType,
+    value: &str,
+) -> Result<bool> {
+    let parse_error =
+        |e: &dyn std::fmt::Display| anyhow!("'{value}' is not a valid {physical_type}: {e}");
+    Ok(match physical_type {
+        PhysicalType::BOOLEAN => sbbf.check(&value.parse::<bool>().map_err(|e| parse_error(&e))?),
+        PhysicalType::INT32 => sbbf.check(&value.parse::<i32>().map_err(|e| parse_error(&e))?),
+        PhysicalType::INT64 => sbbf.check(&value.parse::<i64>().map_err(|e| parse_error(&e))?),
+        PhysicalType::FLOAT => sbbf.check(&value.parse::<f32>().map_err(|e| parse_error(&e))?),
+        PhysicalType::DOUBLE => sbbf.check(&value.parse::<f64>().map_err(|e| parse_error(&e))?),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => sbbf.check(&value),
+        PhysicalType::INT96 => {
+            return Err(anyhow!("Probing INT96 columns is not supported"));
+        }
+    })
 }
 
 /// Information about all pages in a column chunk, for `get_column_chunk_page_info`
@@ -220,46 +960,153 @@ pub struct PageInfo {
     pub size_bytes: u64,
     pub num_values: u32,
     pub encoding: parquet::basic::Encoding,
+    pub crc: PageCrc,
 }
 
 /// Gets detailed information about all pages in a column chunk.
+/// With `verify_crc`, page checksums are recomputed and compared against the stored ones.
 pub async fn get_column_chunk_page_info(
     column_reader: &mut impl AsyncFileReader,
     metadata: &ParquetMetaData,
     row_group_id: usize,
     column_id: usize,
+    verify_crc: bool,
 ) -> Result<Vec<PageInfo>> {
-    let row_group = metadata.row_group(row_group_id);
-    let column_chunk = row_group.column(column_id);
-    let byte_range = column_chunk.byte_range();
-
-    let bytes = column_reader
-        .get_bytes(byte_range.0..(byte_range.0 + byte_range.1))
-        .await?;
-
-    let chunk = ColumnChunk::new(bytes, byte_range);
+    let bytes = read_column_chunk(column_reader, metadata, row_group_id, column_id).await?;
+    column_chunk_page_info(metadata, row_group_id, column_id, bytes, verify_crc)
+}
 
-    // Create a page reader
-    let page_reader = SerializedPageReader::new(
-        Arc::new(chunk),
-        column_chunk,
-        row_group.num_rows() as usize,
-        None,
-    )?;
+/// Page information of an already fetched column chunk, see `prefetch_column_chunks`.
+pub fn column_chunk_page_info(
+    metadata: &ParquetMetaData,
+    row_group_id: usize,
+    column_id: usize,
+    bytes: Bytes,
+    verify_crc: bool,
+) -> Result<Vec<PageInfo>> {
+    let crcs = page_crcs(&bytes, verify_crc).unwrap_or_default();
+    let page_reader = chunk_page_reader(metadata, row_group_id, column_id, bytes)?;
 
     let mut pages = Vec::new();
-    for page in page_reader.flatten() {
+    for (i, page) in page_reader.flatten().enumerate() {
         pages.push(PageInfo {
             page_type: page.page_type(),
             size_bytes: page.buffer().len() as u64,
             num_values: page.num_values(),
             encoding: page.encoding(),
+            crc: crcs.get(i).copied().unwrap_or(PageCrc::Missing),
         });
     }
 
     Ok(pages)
 }
 
+/// Decoded values of a single data page, see `decode_data_page`.
+#[derive(Debug, Clone)]
+pub struct DataPagePreview {
+    /// Row within the row group where the page starts.
+    pub first_row: usize,
+    /// Rows stored in the page; `values` holds at most the requested limit.
+    pub num_rows: usize,
+    pub values: RecordBatch,
+}
+
+/// Rows `[start, end)` of the `data_page_index`-th data page of a column chunk. Located through
+/// the offset index when present; otherwise by page header value counts, which only equal row
+/// counts for non-repeated columns.
+async fn data_page_rows(
+    column_reader: &mut impl AsyncFileReader,
+    metadata: &ParquetMetaData,
+    row_group_id: usize,
+    column_id: usize,
+    data_page_index: usize,
+) -> Result<(usize, usize)> {
+    let num_rows = metadata.row_group(row_group_id).num_rows() as usize;
+    if let Some(chunk) = metadata
+        .offset_index()
+        .and_then(|index| index.get(row_group_id))
+        .and_then(|row_group| row_group.get(column_id))
+    {
+        let locations = chunk.page_locations();
+        let page = locations
+            .get(data_page_index)
+            .ok_or_else(|| anyhow!("column chunk has no data page {data_page_index}"))?;
+        let end = locations
+            .get(data_page_index + 1)
+            .map(|next| next.first_row_index as usize)
+            .unwrap_or(num_rows);
+        return Ok((page.first_row_index as usize, end));
+    }
+
+    if metadata
+        .file_metadata()
+        .schema_descr()
+        .column(column_id)
+        .max_rep_level()
+        > 0
+    {
+        return Err(anyhow!(
+            "repeated column pages can only be located with an offset index, which this file does not have"
+        ));
+    }
+    let pages =
+        get_column_chunk_page_info(column_reader, metadata, row_group_id, column_id, false).await?;
+    let mut start = 0;
+    for (i, page) in pages
+        .iter()
+        .filter(|page| matches!(page.page_type, PageType::DATA_PAGE | PageType::DATA_PAGE_V2))
+        .enumerate()
+    {
+        let end = start + page.num_values as usize;
+        if i == data_page_index {
+            return Ok((start, end));
+        }
+        start = end;
+    }
+    Err(anyhow!("column chunk has no data page {data_page_index}"))
+}
+
+/// Decodes the first `limit` values of one data page. Only the page's rows are selected, so
+/// with an offset index just that page (and the dictionary page) is fetched.
+pub async fn decode_data_page<R>(
+    reader: R,
+    metadata: Arc<ParquetMetaData>,
+    row_group_id: usize,
+    column_id: usize,
+    data_page_index: usize,
+    limit: usize,
+) -> Result<DataPagePreview>
+where
+    R: AsyncFileReader + Clone + Send + 'static,
+{
+    let (start, end) = data_page_rows(
+        &mut reader.clone(),
+        &metadata,
+        row_group_id,
+        column_id,
+        data_page_index,
+    )
+    .await?;
+    let selection = RowSelection::from(vec![
+        RowSelector::skip(start),
+        RowSelector::select((end - start).min(limit)),
+    ]);
+    let projection = ProjectionMask::leaves(metadata.file_metadata().schema_descr(), [column_id]);
+    let reader_metadata = ArrowReaderMetadata::try_new(metadata, Default::default())?;
+    let builder = ParquetRecordBatchStreamBuilder::new_with_metadata(reader, reader_metadata)
+        .with_row_groups(vec![row_group_id])
+        .with_projection(projection)
+        .with_row_selection(selection);
+    let stream = builder.build()?;
+    let schema = stream.schema().clone();
+    let batches = stream.try_collect::<Vec<_>>().await?;
+    Ok(DataPagePreview {
+        first_row: start,
+        num_rows: end - start,
+        values: concat_batches(&schema, &batches)?,
+    })
+}
+
 pub struct ColumnChunk {
     data: Bytes,
     byte_range: (u64, u64),
@@ -0,0 +1,218 @@
+//! Turns file load failures into short, actionable messages instead of raw error dumps.
+
+use super::parquet_reader::NotParquetError;
+
+/// Common reasons a file fails to load.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LoadErrorKind {
+    NotFound,
+    AccessDenied,
+    Cors,
+    RangeNotSupported,
+    NotParquet,
+    TooLarge,
+    Other,
+}
+
+impl LoadErrorKind {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            LoadErrorKind::NotFound => "File not found",
+            LoadErrorKind::AccessDenied => "Access denied",
+            LoadErrorKind::Cors => "Request blocked by the browser",
+            LoadErrorKind::RangeNotSupported => "Range requests not supported",
+            LoadErrorKind::NotParquet => "Not a Parquet file",
+            LoadErrorKind::TooLarge => "File too large",
+            LoadErrorKind::Other => "Failed to load file",
+        }
+    }
+
+    /// What the user can do about it, empty when there is nothing specific to suggest.
+    pub(crate) fn hint(self) -> &'static str {
+        match self {
+            LoadErrorKind::NotFound => "Check the URL or path; the server has no file there.",
+            LoadErrorKind::AccessDenied => {
+                "The server rejected the request. Check the S3 credentials in Settings, or use a \
+                 public or pre-signed URL."
+            }
+            LoadErrorKind::Cors => {
+                "The server must allow this site with CORS headers (Access-Control-Allow-Origin, \
+                 and the Range header). Otherwise download the file and open it locally."
+            }
+            LoadErrorKind::RangeNotSupported => {
+                "The server does not answer byte-range requests. Download the file and open it \
+                 locally."
+            }
+            LoadErrorKind::NotParquet => "Open a .parquet file, or convert the file to Parquet.",
+            LoadErrorKind::TooLarge => {
+                "Browsers cannot hold files this large (about 2 GB) in memory. Serve it from a \
+                 host with range request support, or open it with the file picker in a \
+                 Chromium browser so it is read in pieces."
+            }
+            LoadErrorKind::Other => "",
+        }
+    }
+}
+
+/// A classified load failure: a concise summary, plus the raw error for a details view.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LoadError {
+    pub kind: LoadErrorKind,
+    pub summary: String,
+    pub details: String,
+}
+
+/// Longest summary shown before the details expander.
+const MAX_SUMMARY_LEN: usize = 240;
+
+impl LoadError {
+    pub(crate) fn from_error(e: &anyhow::Error) -> Self {
+        let details = format!("{e:#?}");
+        let kind = classify(e, &details);
+        let summary = match e.chain().find_map(|c| c.downcast_ref::<NotParquetError>()) {
+            Some(not_parquet) => not_parquet.to_string(),
+            None => {
+                let message = format!("{e:#}");
+                let first_line = message.lines().next().unwrap_or_default();
+                match first_line.char_indices().nth(MAX_SUMMARY_LEN) {
+                    Some((end, _)) => format!("{}…", &first_line[..end]),
+                    None => first_line.to_string(),
+                }
+            }
+        };
+        Self {
+            kind,
+            summary,
+            details,
+        }
+    }
+}
+
+fn classify(e: &anyhow::Error, details: &str) -> LoadErrorKind {
+    if e.chain().any(|c| c.is::<NotParquetError>()) {
+        return LoadErrorKind::NotParquet;
+    }
+    match e
+        .chain()
+        .find_map(|c| c.downcast_ref::<object_store::Error>())
+    {
+        Some(object_store::Error::NotFound { .. }) => return LoadErrorKind::NotFound,
+        Some(
+            object_store::Error::PermissionDenied { .. }
+            | object_store::Error::Unauthenticated { .. },
+        ) => return LoadErrorKind::AccessDenied,
+        _ => {}
+    }
+    classify_message(&details.to_lowercase())
+}
+
+/// Falls back to the error text, which is all we get from errors wrapped by DataFusion or
+/// OpenDAL.
+fn classify_message(text: &str) -> LoadErrorKind {
+    let has = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+    if has(&[
+        "download limit",
+        "allocation failed",
+        "rangeerror",
+        "too large",
+    ]) {
+        LoadErrorKind::TooLarge
+    } else if has(&[
+        "status: 416",
+        "range not satisfiable",
+        "rangenotsatisfied",
+        "range request",
+    ]) {
+        LoadErrorKind::RangeNotSupported
+    } else if has(&[
+        "status: 404",
+        "404 not found",
+        "notfound",
+        "no such key",
+        "nosuchkey",
+    ]) {
+        LoadErrorKind::NotFound
+    } else if has(&[
+        "status: 401",
+        "status: 403",
+        "unauthorized",
+        "forbidden",
+        "access denied",
+        "accessdenied",
+        "permissiondenied",
+        "permission denied",
+        "invalidaccesskeyid",
+        "signaturedoesnotmatch",
+    ]) {
+        LoadErrorKind::AccessDenied
+    } else if has(&["cors", "failed to fetch", "networkerror", "load failed"]) {
+        LoadErrorKind::Cors
+    } else {
+        LoadErrorKind::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_load_errors() {
+        let not_found = anyhow::Error::new(object_store::Error::NotFound {
+            path: "data/missing.parquet".to_string(),
+            source: "404".into(),
+        });
+        assert_eq!(
+            LoadError::from_error(&not_found).kind,
+            LoadErrorKind::NotFound
+        );
+
+        let not_parquet = anyhow::Error::new(NotParquetError {
+            file_name: "data.csv".to_string(),
+            detected_format: Some("CSV"),
+        });
+        let error = LoadError::from_error(&not_parquet);
+        assert_eq!(error.kind, LoadErrorKind::NotParquet);
+        assert!(
+            error
+                .summary
+                .starts_with("data.csv is not a valid Parquet file")
+        );
+
+        let cases = [
+            (
+                "Generic { store: \"HTTP\", source: Error { kind: PermissionDenied, \
+                 context: [\"response: Parts { status: 403, version: HTTP/1.1 }\"] } }",
+                LoadErrorKind::AccessDenied,
+            ),
+            ("JsValue(TypeError: Failed to fetch)", LoadErrorKind::Cors),
+            (
+                "data.parquet is served without range support and is 3000000000 bytes, larger \
+                 than the 536870912 byte download limit",
+                LoadErrorKind::TooLarge,
+            ),
+            (
+                "Unexpected (permanent) at read, context: { response: Parts { status: 416 } }",
+                LoadErrorKind::RangeNotSupported,
+            ),
+            ("URL has no host: data:text", LoadErrorKind::Other),
+        ];
+        for (message, kind) in cases {
+            assert_eq!(
+                LoadError::from_error(&anyhow::anyhow!(message)).kind,
+                kind,
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_error_summary_is_concise() {
+        let error = LoadError::from_error(
+            &anyhow::anyhow!("{}\nsecond line", "x".repeat(1000)).context("loading file"),
+        );
+        assert_eq!(error.summary.chars().count(), MAX_SUMMARY_LEN + 1);
+        assert!(!error.summary.contains("second line"));
+        assert!(error.details.contains("second line"));
+    }
+}
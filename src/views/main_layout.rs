@@ -2,54 +2,84 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use dioxus::prelude::*;
+use dioxus_primitives::toast::{ToastOptions, use_toast};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::js_sys;
 
-use crate::components::{QueryInput, Theme, use_theme};
-use crate::parquet_ctx::ParquetResolved;
-use crate::storage::readers;
-use crate::utils::{send_message_to_vscode, vscode_env};
+use crate::components::{
+    QueryHistory, QueryInput, Theme, clear_query_history, load_query_history, poll_fetch_progress,
+    record_query, stored_sql_mode, use_theme,
+};
+use crate::nl_to_sql::retarget_query;
+use crate::parquet_ctx::{MAX_IN_MEMORY_FILE_SIZE, ParquetResolved};
+use crate::storage::{FetchProgress, readers};
+use crate::utils::{format_bytes, format_rows, send_message_to_vscode, vscode_env};
 use crate::{Route, SESSION_CTX};
 
+use super::load_error::LoadError;
 use super::metadata::MetadataView;
-use super::parquet_reader::{ParquetReader, ParquetUnresolved};
+use super::parquet_reader::{NotParquetError, ParquetReader, ParquetUnresolved};
+use super::pivot::PivotSection;
 use super::query_results::QueryResultView;
 use super::schema::SchemaSection;
 use super::settings::Settings;
 
 const DEFAULT_URL: &str = "https://parquet-viewer.xiangpeng.systems/?url=https%3A%2F%2Fhuggingface.co%2Fdatasets%2Fopen-r1%2FOpenR1-Math-220k%2Fresolve%2Fmain%2Fdata%2Ftrain-00003-of-00010.parquet";
+/// Files above this size get a warning before being loaded into memory.
+const LARGE_IN_MEMORY_FILE_SIZE: u64 = 100 * 1024 * 1024;
 pub(crate) const DEFAULT_QUERY: &str = "show first 10 rows";
 
-fn format_rows(count: u64) -> String {
-    let mut result = count.to_string();
-    let mut i = result.len();
-    while i > 3 {
-        i -= 3;
-        result.insert(i, ',');
-    }
-    result
-}
-
 #[derive(Clone)]
 struct QueryResultEntry {
     id: usize,
+    /// The tab the query was run from.
+    tab: usize,
     query: String,
+    /// Run `query` as SQL as typed, skipping natural language translation.
+    raw_sql: bool,
     display: bool,
     table: Arc<ParquetResolved>,
 }
 
+/// An open file, with the query input it had when the user switched away from it.
+#[derive(Clone)]
+struct ViewerTab {
+    id: usize,
+    table: Arc<ParquetResolved>,
+    query_input: String,
+}
+
 #[component]
 pub(crate) fn MainLayout() -> Element {
-    let error_message = use_signal(|| None::<String>);
-    let loaded_files = use_signal(Vec::<Arc<ParquetResolved>>::new);
+    let error_message = use_signal(|| None::<LoadError>);
+    // Progress of the remote file being opened, `None` when nothing is loading.
+    let fetch_progress = use_signal(|| None::<FetchProgress>);
+    let tabs = use_signal(Vec::<ViewerTab>::new);
+    let active_tab = use_signal(|| None::<usize>);
+    let next_tab_id = use_signal(|| 0usize);
+    // Input of the active tab; the other tabs keep theirs in `ViewerTab::query_input`.
     let query_input = use_signal(|| DEFAULT_QUERY.to_string());
     let query_results = use_signal(Vec::<QueryResultEntry>::new);
+    // Closing a tab drops its results, so ids can't be derived from the list length.
+    let next_result_id = use_signal(|| 0usize);
+    let sql_mode = use_signal(stored_sql_mode);
+    // Bumped when query history is written, so the panel re-reads it from storage.
+    let history_version = use_signal(|| 0u64);
+    // The last query the user ran themselves, whether it was raw SQL, and the file it ran against.
+    let last_user_query = use_signal(|| None::<(String, bool, Arc<ParquetResolved>)>);
+
+    let active_table = move || {
+        let active = active_tab()?;
+        tabs().into_iter().find(|t| t.id == active).map(|t| t.table)
+    };
 
     // Theme management
     let (theme, toggle_theme) = use_theme();
 
+    let toast_api = use_toast();
+
     // Settings modal state
     let mut show_settings = use_signal(|| false);
 
@@ -64,22 +94,81 @@ pub(crate) fn MainLayout() -> Element {
         }
     };
 
+    let on_switch_tab = move |id: usize| {
+        let mut tabs = tabs;
+        let mut active_tab = active_tab;
+        let mut query_input = query_input;
+        if active_tab() == Some(id) {
+            return;
+        }
+        let mut next = tabs();
+        if let Some(current) = next.iter_mut().find(|t| Some(t.id) == active_tab()) {
+            current.query_input = query_input();
+        }
+        if let Some(target) = next.iter().find(|t| t.id == id) {
+            query_input.set(target.query_input.clone());
+        }
+        tabs.set(next);
+        active_tab.set(Some(id));
+    };
+
+    let on_close_tab = move |id: usize| {
+        let mut tabs = tabs;
+        let mut active_tab = active_tab;
+        let mut query_input = query_input;
+        let mut query_results = query_results;
+        let mut next = tabs();
+        let Some(index) = next.iter().position(|t| t.id == id) else {
+            return;
+        };
+        let closed = next.remove(index);
+        // The same file may still be open in another tab.
+        if let Err(e) = closed
+            .table
+            .deregister(SESSION_CTX.as_ref(), next.iter().map(|t| t.table.as_ref()))
+        {
+            tracing::warn!(
+                "Failed to deregister {}: {e}",
+                closed.table.registered_table_name()
+            );
+        }
+        if active_tab() == Some(id) {
+            let neighbour = next.get(index).or(next.last());
+            query_input.set(
+                neighbour
+                    .map(|t| t.query_input.clone())
+                    .unwrap_or_else(|| DEFAULT_QUERY.to_string()),
+            );
+            active_tab.set(neighbour.map(|t| t.id));
+        }
+        tabs.set(next);
+        let mut results = query_results();
+        results.retain(|r| r.tab != id);
+        query_results.set(results);
+    };
+
     let on_submit_query = {
-        move |query: String| {
+        move |query: String, raw_sql: bool| {
             let mut query_input = query_input;
             let mut query_results = query_results;
-            let files = loaded_files();
 
             query_input.set(query.clone());
-            // Use the most recently loaded file for queries
-            let Some(table) = files.last().cloned() else {
+            let (Some(tab), Some(table)) = (active_tab(), active_table()) else {
                 return;
             };
+            if query != DEFAULT_QUERY {
+                let mut last_user_query = last_user_query;
+                last_user_query.set(Some((query.clone(), raw_sql, table.clone())));
+            }
+            let mut next_result_id = next_result_id;
+            let id = next_result_id();
+            next_result_id.set(id + 1);
             let mut next = query_results();
-            let id = next.len();
             next.push(QueryResultEntry {
                 id,
+                tab,
                 query,
+                raw_sql,
                 display: true,
                 table,
             });
@@ -91,44 +180,146 @@ pub(crate) fn MainLayout() -> Element {
         move |parquet_info: Result<ParquetUnresolved>| match parquet_info {
             Ok(parquet_info) => {
                 let mut error_message = error_message;
-                let mut loaded_files = loaded_files;
+                let mut tabs = tabs;
+                let mut next_tab_id = next_tab_id;
                 let mut query_results = query_results;
+                let mut next_result_id = next_result_id;
                 let mut query_input = query_input;
+                let mut fetch_progress = fetch_progress;
+                if let Some(stats) = parquet_info.io_stats.clone() {
+                    fetch_progress.set(Some(stats.fetch_progress()));
+                    spawn_local(poll_fetch_progress(stats, fetch_progress));
+                }
                 spawn_local({
                     async move {
-                        match parquet_info.try_into_resolved(SESSION_CTX.as_ref()).await {
+                        let resolved = parquet_info.try_into_resolved(SESSION_CTX.as_ref()).await;
+                        fetch_progress.set(None);
+                        match resolved {
                             Ok(table) => {
+                                let downloaded = table
+                                    .io_stats()
+                                    .map(|stats| stats.full_download_bytes())
+                                    .unwrap_or(0);
+                                if downloaded > 0 {
+                                    toast_api.warning(
+                                        "Range requests not supported".to_string(),
+                                        ToastOptions::new().description(format!(
+                                            "The server ignored range requests, so the whole file ({}) was downloaded into memory.",
+                                            format_bytes(downloaded)
+                                        )),
+                                    );
+                                }
                                 let table = Arc::new(table);
-                                // Add to list of loaded files
-                                let mut files = loaded_files();
-                                files.push(table.clone());
-                                loaded_files.set(files);
-
+                                // Reopening a file that already has a tab reuses it, since
+                                // both would be registered under the same table name.
+                                let existing = tabs().into_iter().find(|t| {
+                                    t.table.registered_table_name() == table.registered_table_name()
+                                });
+                                let tab = match existing {
+                                    Some(existing) => {
+                                        let mut next = tabs();
+                                        for t in next.iter_mut().filter(|t| t.id == existing.id) {
+                                            t.table = table.clone();
+                                        }
+                                        tabs.set(next);
+                                        existing.id
+                                    }
+                                    None => {
+                                        let id = next_tab_id();
+                                        next_tab_id.set(id + 1);
+                                        let mut next = tabs();
+                                        next.push(ViewerTab {
+                                            id,
+                                            table: table.clone(),
+                                            query_input: DEFAULT_QUERY.to_string(),
+                                        });
+                                        tabs.set(next);
+                                        id
+                                    }
+                                };
+                                on_switch_tab(tab);
                                 query_input.set(DEFAULT_QUERY.to_string());
 
                                 // Add default query for the new file
+                                let id = next_result_id();
+                                next_result_id.set(id + 1);
                                 let mut results = query_results();
-                                let id = results.len();
                                 results.push(QueryResultEntry {
                                     id,
+                                    tab,
                                     query: DEFAULT_QUERY.to_string(),
+                                    raw_sql: false,
                                     display: true,
                                     table,
                                 });
                                 query_results.set(results);
                             }
-                            Err(e) => error_message.set(Some(format!("{e:#?}"))),
+                            Err(e) => match e.downcast_ref::<NotParquetError>() {
+                                Some(not_parquet) => toast_api.error(
+                                    "Not a valid Parquet file".to_string(),
+                                    ToastOptions::new().description(not_parquet.to_string()),
+                                ),
+                                None => error_message.set(Some(LoadError::from_error(&e))),
+                            },
                         }
                     }
                 });
             }
             Err(e) => {
                 let mut error_message = error_message;
-                error_message.set(Some(format!("{e:#?}")))
+                error_message.set(Some(LoadError::from_error(&e)))
             }
         }
     };
 
+    let loading_into_memory = use_signal(|| false);
+    let on_load_into_memory = move |table: Arc<ParquetResolved>| {
+        let mut tabs = tabs;
+        let mut loading_into_memory = loading_into_memory;
+        loading_into_memory.set(true);
+        if table.metadata().file_size > LARGE_IN_MEMORY_FILE_SIZE {
+            toast_api.warning(
+                "Large download".to_string(),
+                ToastOptions::new().description(format!(
+                    "Downloading {} into browser memory.",
+                    format_bytes(table.metadata().file_size)
+                )),
+            );
+        }
+        spawn_local(async move {
+            match table.load_into_memory(SESSION_CTX.as_ref()).await {
+                Ok(in_memory) => {
+                    let in_memory = Arc::new(in_memory);
+                    let mut next = tabs();
+                    for t in next.iter_mut().filter(|t| t.table == table) {
+                        t.table = in_memory.clone();
+                    }
+                    if let Err(e) = table
+                        .deregister(SESSION_CTX.as_ref(), next.iter().map(|t| t.table.as_ref()))
+                    {
+                        tracing::warn!(
+                            "Failed to deregister {}: {e}",
+                            table.registered_table_name()
+                        );
+                    }
+                    tabs.set(next);
+                    toast_api.success(
+                        "Loaded into memory".to_string(),
+                        ToastOptions::new().description(format!(
+                            "Queries now read from table {}.",
+                            in_memory.registered_table_name()
+                        )),
+                    );
+                }
+                Err(e) => toast_api.error(
+                    "Failed to load into memory".to_string(),
+                    ToastOptions::new().description(format!("{e:#}")),
+                ),
+            }
+            loading_into_memory.set(false);
+        });
+    };
+
     // Get the URL parameter from the route
     let route = use_route::<Route>();
     let url_param = match &route {
@@ -297,18 +488,22 @@ pub(crate) fn MainLayout() -> Element {
                                     "Parquet Viewer"
                                 }
 
-                                // Loaded files indicator
-                                if !loaded_files().is_empty() {
-                                    div { class: "flex items-center gap-1.5 flex-wrap",
-                                        for file in loaded_files().iter() {
+                                // Open file tabs
+                                if !tabs().is_empty() {
+                                    div {
+                                        class: "flex items-center gap-1.5 flex-wrap",
+                                        role: "tablist",
+                                        for (tab_id, file) in tabs().into_iter().map(|t| (t.id, t.table)) {
                                             div {
-                                                key: "{file.registered_table_name()}",
+                                                key: "{tab_id}",
                                                 class: "dropdown dropdown-end",
-                                                // Clickable chip
+                                                // Clickable chip, switches to the tab and shows its details
                                                 div {
                                                     tabindex: "0",
-                                                    role: "button",
-                                                    class: "file-indicator flex items-center gap-1.5 px-2 py-1 rounded-md text-xs cursor-pointer",
+                                                    role: "tab",
+                                                    aria_selected: active_tab() == Some(tab_id),
+                                                    class: if active_tab() == Some(tab_id) { "file-indicator active flex items-center gap-1.5 px-2 py-1 rounded-md text-xs cursor-pointer" } else { "file-indicator flex items-center gap-1.5 px-2 py-1 rounded-md text-xs cursor-pointer" },
+                                                    onclick: move |_| on_switch_tab(tab_id),
                                                     // File icon
                                                     svg {
                                                         xmlns: "http://www.w3.org/2000/svg",
@@ -326,6 +521,15 @@ pub(crate) fn MainLayout() -> Element {
                                                     span { class: "font-medium truncate max-w-[150px]",
                                                         "{file.table_name()}"
                                                     }
+                                                    button {
+                                                        class: "opacity-60 hover:opacity-100 leading-none",
+                                                        title: "Close {file.table_name()}",
+                                                        onclick: move |e| {
+                                                            e.stop_propagation();
+                                                            on_close_tab(tab_id);
+                                                        },
+                                                        "×"
+                                                    }
                                                 }
                                                 // Dropdown content
                                                 div {
@@ -356,6 +560,25 @@ pub(crate) fn MainLayout() -> Element {
                                                                 "{file.metadata().columns}"
                                                             }
                                                         }
+                                                        if !file.is_local() {
+                                                            {
+                                                                let file = file.clone();
+                                                                let too_large = file.metadata().file_size > MAX_IN_MEMORY_FILE_SIZE;
+                                                                rsx! {
+                                                                    button {
+                                                                        class: "btn btn-xs btn-ghost w-full mt-1",
+                                                                        disabled: too_large || loading_into_memory(),
+                                                                        title: if too_large { "File is too large to load into memory" } else { "Download the whole file once so queries stop fetching ranges" },
+                                                                        onclick: move |_| on_load_into_memory(file.clone()),
+                                                                        if loading_into_memory() {
+                                                                            "Loading into memory..."
+                                                                        } else {
+                                                                            "Load into memory"
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
                                                     }
                                                 }
                                             }
@@ -368,47 +591,115 @@ pub(crate) fn MainLayout() -> Element {
                                 ParquetReader {
                                     read_call_back: on_parquet_read,
                                     initial_url: url_param,
+                                    fetch_progress: fetch_progress(),
                                 }
                             }
 
-                            if let Some(msg) = error_message() {
-                                div { class: "panel-soft p-4 border-l-2 border-red-400",
-                                    pre { class: "text-sm text-red-600 dark:text-red-400 whitespace-pre-wrap break-words",
-                                        "{msg}"
+                            if let Some(error) = error_message() {
+                                div { class: "panel-soft p-4 border-l-2 border-red-400 space-y-1 text-sm",
+                                    div { class: "font-semibold text-red-600 dark:text-red-400",
+                                        "{error.kind.title()}"
+                                    }
+                                    div { class: "break-words", "{error.summary}" }
+                                    if !error.kind.hint().is_empty() {
+                                        div { class: "opacity-75", "{error.kind.hint()}" }
+                                    }
+                                    details { class: "text-xs",
+                                        summary { class: "cursor-pointer opacity-75 select-none", "Show details" }
+                                        pre { class: "mt-1 whitespace-pre-wrap break-words opacity-75",
+                                            "{error.details}"
+                                        }
                                     }
                                 }
                             }
 
-                            if let Some(table) = loaded_files().last() {
+                            if let Some(table) = active_table() {
                                 if table.metadata().row_group_count > 0 {
                                     QueryInput {
-                                        value: query_input(),
+                                        key: "{table.registered_table_name()}",
+                                        value: query_input,
+                                        parquet_table: table.clone(),
+                                        sql_mode,
                                         on_value_change: move |v| {
                                             let mut query_input = query_input;
                                             query_input.set(v);
                                         },
-                                        on_user_submit_query: on_submit_query,
+                                        on_user_submit_query: move |query| on_submit_query(query, sql_mode()),
+                                    }
+                                    QueryHistory {
+                                        entries: {
+                                            history_version();
+                                            load_query_history(table.table_name())
+                                        },
+                                        on_select: move |query| {
+                                            let mut query_input = query_input;
+                                            query_input.set(query);
+                                        },
+                                        on_clear: {
+                                            let table = table.clone();
+                                            move |_| {
+                                                let mut history_version = history_version;
+                                                clear_query_history(table.table_name());
+                                                history_version += 1;
+                                            }
+                                        },
+                                    }
+                                    if let Some((last_query, last_raw_sql, last_table)) = last_user_query()
+                                        && last_table.registered_table_name() != table.registered_table_name()
+                                    {
+                                        {
+                                            let retargeted = retarget_query(&last_query, &last_table, &table);
+                                            rsx! {
+                                                div { class: "flex items-center gap-2 text-xs mt-2",
+                                                    span { class: "opacity-75 whitespace-nowrap", "Last query on {last_table.table_name()}:" }
+                                                    code { class: "truncate font-mono", title: "{last_query}", "{last_query}" }
+                                                    button {
+                                                        class: "btn btn-xs btn-ghost whitespace-nowrap",
+                                                        title: "Run it against {table.table_name()}",
+                                                        onclick: move |_| on_submit_query(retargeted.clone(), last_raw_sql),
+                                                        "Re-run on this file"
+                                                    }
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                             }
 
                             div { class: "space-y-3",
-                                for entry in query_results().iter().rev().filter(|r| r.display) {
+                                for entry in query_results().iter().rev().filter(|r| r.display && Some(r.tab) == active_tab()) {
                                     div { key: "{entry.id}",
                                         QueryResultView {
                                             id: entry.id,
                                             query: entry.query.clone(),
+                                            raw_sql: entry.raw_sql,
                                             parquet_table: entry.table.clone(),
                                             on_hide,
+                                            on_sql_generated: {
+                                                let query = entry.query.clone();
+                                                let table = entry.table.clone();
+                                                move |sql: String| {
+                                                    // The query every file opens with is not worth keeping.
+                                                    if query != DEFAULT_QUERY {
+                                                        let mut history_version = history_version;
+                                                        record_query(table.table_name(), &query, &sql);
+                                                        history_version += 1;
+                                                    }
+                                                }
+                                            },
                                         }
                                     }
                                 }
                             }
 
-                            if let Some(table) = loaded_files().last() {
+                            if let Some(table) = active_table() {
                                 div { class: "space-y-4 mt-6",
                                     MetadataView { parquet_reader: table.clone() }
                                     SchemaSection { parquet_reader: table.clone() }
+                                    PivotSection {
+                                        key: "{table.registered_table_name()}",
+                                        parquet_reader: table.clone(),
+                                    }
                                 }
                             } else if !is_in_vscode {
                                 div { class: "text-center text-tertiary py-12",
@@ -1,17 +1,23 @@
 use crate::{
-    ParquetResolved,
+    ParquetResolved, SESSION_CTX,
     components::{
-        FileLevelInfo, PageInfo, StatisticsDisplay,
-        ui::{Panel, SectionHeader},
+        FileLevelInfo, FooterMetadata, PageInfo, SchemaTemplateExport, StatisticsDisplay,
+        ui::{LoadError, Panel, SectionHeader, Skeleton},
     },
-    utils::count_column_chunk_pages,
+    utils::{count_column_chunk_pages, probe_bloom_filter},
 };
-use byte_unit::{Byte, UnitType};
 use dioxus::prelude::*;
-use parquet::{basic::Compression, file::metadata::ParquetMetaData};
+use dioxus_primitives::toast::{ToastOptions, use_toast};
+use parquet::{
+    basic::{Compression, LogicalType},
+    file::metadata::{ColumnChunkMetaData, ParquetMetaData},
+};
 use std::sync::Arc;
 
-use crate::utils::format_rows;
+use crate::utils::{
+    CsvExportOptions, download_data, format_bytes, format_rows, row_group_statistics_batch,
+    write_csv, write_parquet,
+};
 
 /// Mirror `Compression::codec_to_string` from `arrow-rs` so we can keep parity with the
 /// formatting used by upstream metadata printing helpers.
@@ -34,12 +40,139 @@ impl CompressionExt for Compression {
     }
 }
 
+/// Row group and column selected by a URL fragment such as `#rg=3&col=5`.
+#[derive(Debug, Default, PartialEq)]
+struct FragmentSelection {
+    row_group: Option<usize>,
+    column: Option<usize>,
+}
+
+fn parse_metadata_fragment(fragment: &str) -> FragmentSelection {
+    let mut selection = FragmentSelection::default();
+    for pair in fragment.trim_start_matches('#').split('&') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let value = value.parse::<usize>().ok();
+        match key {
+            "rg" => selection.row_group = value,
+            "col" => selection.column = value,
+            _ => {}
+        }
+    }
+    selection
+}
+
+fn fragment_selection() -> FragmentSelection {
+    web_sys::window()
+        .and_then(|window| window.location().hash().ok())
+        .map(|hash| parse_metadata_fragment(&hash))
+        .unwrap_or_default()
+}
+
+/// Order of the row group selector's options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum RowGroupOrder {
+    #[default]
+    File,
+    /// Largest compressed size first.
+    CompressedSize,
+    /// Most rows first.
+    Rows,
+}
+
+impl RowGroupOrder {
+    const ALL: [RowGroupOrder; 3] = [
+        RowGroupOrder::File,
+        RowGroupOrder::CompressedSize,
+        RowGroupOrder::Rows,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            RowGroupOrder::File => "file",
+            RowGroupOrder::CompressedSize => "size",
+            RowGroupOrder::Rows => "rows",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            RowGroupOrder::File => "File order",
+            RowGroupOrder::CompressedSize => "Largest first",
+            RowGroupOrder::Rows => "Most rows first",
+        }
+    }
+}
+
+/// Row group indexes in `order`; ties keep file order.
+pub(crate) fn sorted_row_groups(metadata: &ParquetMetaData, order: RowGroupOrder) -> Vec<usize> {
+    let mut row_groups = (0..metadata.num_row_groups()).collect::<Vec<_>>();
+    match order {
+        RowGroupOrder::File => {}
+        RowGroupOrder::CompressedSize => {
+            row_groups.sort_by_key(|&i| std::cmp::Reverse(metadata.row_group(i).compressed_size()))
+        }
+        RowGroupOrder::Rows => {
+            row_groups.sort_by_key(|&i| std::cmp::Reverse(metadata.row_group(i).num_rows()))
+        }
+    }
+    row_groups
+}
+
+fn row_group_option_label(
+    metadata: &ParquetMetaData,
+    row_group: usize,
+    order: RowGroupOrder,
+) -> String {
+    let rg = metadata.row_group(row_group);
+    match order {
+        RowGroupOrder::File => row_group.to_string(),
+        RowGroupOrder::CompressedSize => format!(
+            "RG {row_group} ({})",
+            format_bytes(rg.compressed_size() as u64)
+        ),
+        RowGroupOrder::Rows => format!(
+            "RG {row_group} ({} rows)",
+            format_rows(rg.num_rows() as u64)
+        ),
+    }
+}
+
 #[component]
 pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
     let metadata_display = parquet_reader.metadata().clone();
     let row_group_count = metadata_display.row_group_count;
-    let mut selected_row_group = use_signal(|| 0usize);
-    let mut selected_column = use_signal(|| 0usize);
+    let column_count = metadata_display
+        .metadata
+        .file_metadata()
+        .schema_descr()
+        .num_columns();
+    // Deep links like `#rg=3&col=5` preselect a row group and column; out-of-range values are ignored.
+    let initial_selection = use_hook(fragment_selection);
+    let mut selected_row_group = use_signal(|| {
+        initial_selection
+            .row_group
+            .filter(|rg| (*rg as u64) < row_group_count)
+            .unwrap_or(0)
+    });
+    let mut selected_column = use_signal(|| {
+        initial_selection
+            .column
+            .filter(|col| *col < column_count)
+            .unwrap_or(0)
+    });
+
+    let mut row_group_order = use_signal(RowGroupOrder::default);
+    let row_group_options = sorted_row_groups(&metadata_display.metadata, row_group_order())
+        .into_iter()
+        .map(|i| {
+            (
+                i,
+                row_group_option_label(&metadata_display.metadata, i, row_group_order()),
+            )
+        })
+        .collect::<Vec<_>>();
 
     let sorted_fields = {
         let mut fields = metadata_display
@@ -55,11 +188,6 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
     };
 
     let metadata_for_col = metadata_display.metadata.clone();
-    let column_stats = move || {
-        let rg = metadata_for_col.row_group(selected_row_group());
-        let col = rg.column(selected_column());
-        col.statistics().cloned()
-    };
 
     let reader_for_column_info = parquet_reader.clone();
     let reader_for_page_info = parquet_reader.clone();
@@ -78,6 +206,55 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                         title: "Parquet Metadata Documentation",
                         "(doc)"
                     }
+                    if row_group_count > 0 {
+                        div { class: "dropdown dropdown-end",
+                            div {
+                                tabindex: "0",
+                                role: "button",
+                                class: "btn btn-xs btn-ghost",
+                                title: "Min/max/null/distinct counts of every column chunk, one row each",
+                                "Export row group stats"
+                            }
+                            ul {
+                                tabindex: "0",
+                                class: "dropdown-content menu menu-xs bg-base-100 rounded-box z-20 w-32 p-1 shadow",
+                                li {
+                                    button {
+                                        onclick: {
+                                            let metadata = metadata_display.metadata.clone();
+                                            let table_name = parquet_reader.table_name().to_string();
+                                            move |_| {
+                                                let data = row_group_statistics_batch(&metadata)
+                                                    .and_then(|batch| write_csv(&[batch], &CsvExportOptions::default()));
+                                                match data {
+                                                    Ok(data) => download_data(&format!("{table_name}.row_group_stats.csv"), data),
+                                                    Err(e) => tracing::warn!("Failed to export row group stats: {e}"),
+                                                }
+                                            }
+                                        },
+                                        "CSV"
+                                    }
+                                }
+                                li {
+                                    button {
+                                        onclick: {
+                                            let metadata = metadata_display.metadata.clone();
+                                            let table_name = parquet_reader.table_name().to_string();
+                                            move |_| {
+                                                let data = row_group_statistics_batch(&metadata)
+                                                    .and_then(|batch| write_parquet(&[batch]));
+                                                match data {
+                                                    Ok(data) => download_data(&format!("{table_name}.row_group_stats.parquet"), data),
+                                                    Err(e) => tracing::warn!("Failed to export row group stats: {e}"),
+                                                }
+                                            }
+                                        },
+                                        "Parquet"
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }),
             }
             div { class: "grid gap-6 lg:grid-cols-2",
@@ -95,9 +272,26 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                                     select {
                                         id: "row-group-select",
                                         class: "select select-bordered w-full",
+                                        value: "{selected_row_group()}",
                                         onchange: move |ev| selected_row_group.set(ev.value().parse::<usize>().unwrap_or(0)),
-                                        for i in 0..row_group_count {
-                                            option { value: "{i}", class: "py-2", "{i}" }
+                                        for (i , label) in row_group_options.iter() {
+                                            option { value: "{i}", class: "py-2", "{label}" }
+                                        }
+                                    }
+                                    select {
+                                        class: "select select-bordered w-40 ml-2",
+                                        title: "Order of the row groups in the list",
+                                        value: "{row_group_order().key()}",
+                                        onchange: move |ev| {
+                                            if let Some(order) = RowGroupOrder::ALL
+                                                .into_iter()
+                                                .find(|order| order.key() == ev.value())
+                                            {
+                                                row_group_order.set(order);
+                                            }
+                                        },
+                                        for order in RowGroupOrder::ALL {
+                                            option { value: "{order.key()}", "{order.label()}" }
                                         }
                                     }
                                 }
@@ -105,6 +299,9 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                                     metadata: metadata_display.metadata.clone(),
                                     row_group_id: selected_row_group(),
                                 }
+                                if row_group_count > 1 {
+                                    RowGroupRangeTables { parquet_reader: parquet_reader.clone() }
+                                }
                             }
                             div {
                                 div { class: "flex items-center mb-2",
@@ -116,6 +313,7 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                                     select {
                                         id: "column-select",
                                         class: "select select-bordered w-full",
+                                        value: "{selected_column()}",
                                         onchange: move |ev| selected_column.set(ev.value().parse::<usize>().unwrap_or(0)),
                                         for (i , field) in sorted_fields.iter() {
                                             option { value: "{i}", class: "py-2", "{field}" }
@@ -135,7 +333,11 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                     div { class: "flex flex-col space-y-2",
                         div {
                             div { class: "font-semibold mb-1", "Row Group stats" }
-                            StatisticsDisplay { statistics: column_stats() }
+                            StatisticsDisplay {
+                                metadata: metadata_for_col.clone(),
+                                row_group_id: selected_row_group(),
+                                column_id: selected_column(),
+                            }
                         }
                         PageInfo {
                             parquet_reader: reader_for_page_info.clone(),
@@ -145,6 +347,8 @@ pub fn MetadataView(parquet_reader: Arc<ParquetResolved>) -> Element {
                     }
                 }
             }
+            SchemaTemplateExport { parquet_reader: parquet_reader.clone() }
+            FooterMetadata { metadata: metadata_display.metadata.clone() }
         }
     }
 }
@@ -156,19 +360,39 @@ fn RowGroupInfo(metadata: Arc<ParquetMetaData>, row_group_id: usize) -> Element
         let compressed_size = rg.compressed_size() as u64;
         let uncompressed_size = rg.total_byte_size() as u64;
         let num_rows = rg.num_rows() as u64;
-        (compressed_size, uncompressed_size, num_rows)
+        let sorted_by = rg.sorting_columns().map(|sorting_columns| {
+            sorting_columns
+                .iter()
+                .map(|sc| {
+                    let name = usize::try_from(sc.column_idx)
+                        .ok()
+                        .filter(|idx| *idx < rg.num_columns())
+                        .map(|idx| rg.column(idx).column_path().string())
+                        .unwrap_or_else(|| format!("#{}", sc.column_idx));
+                    let direction = if sc.descending { "DESC" } else { "ASC" };
+                    let nulls = if sc.nulls_first {
+                        "NULLS FIRST"
+                    } else {
+                        "NULLS LAST"
+                    };
+                    format!("{name} {direction} {nulls}")
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        (compressed_size, uncompressed_size, num_rows, sorted_by)
     };
 
-    let (compressed_size, uncompressed_size, num_rows) = row_group_info();
+    let (compressed_size, uncompressed_size, num_rows, sorted_by) = row_group_info();
     rsx! {
         div { class: "grid grid-cols-2 gap-2 bg-base-200 p-2 rounded-md",
             div { class: "space-y-1",
                 div { class: "text-base-content opacity-60 text-xs", "Compressed" }
-                div { "{Byte::from_u64(compressed_size).get_appropriate_unit(UnitType::Binary):.2}" }
+                div { "{format_bytes(compressed_size)}" }
             }
             div { class: "space-y-1",
                 div { class: "text-base-content opacity-60 text-xs", "Uncompressed" }
-                div { "{Byte::from_u64(uncompressed_size).get_appropriate_unit(UnitType::Binary):.2}" }
+                div { "{format_bytes(uncompressed_size)}" }
             }
             div { class: "space-y-1",
                 div { class: "text-base-content opacity-60 text-xs", "Compression%" }
@@ -178,15 +402,179 @@ fn RowGroupInfo(metadata: Arc<ParquetMetaData>, row_group_id: usize) -> Element
                 div { class: "text-base-content opacity-60 text-xs", "Rows" }
                 div { "{format_rows(num_rows)}" }
             }
+            div { class: "space-y-1 col-span-2",
+                div { class: "text-base-content opacity-60 text-xs", "Sorted by" }
+                match sorted_by {
+                    Some(sorted_by) if !sorted_by.is_empty() => rsx! {
+                        div { class: "font-mono break-all", "{sorted_by}" }
+                    },
+                    _ => rsx! {
+                        div { class: "opacity-60", "Not declared" }
+                    },
+                }
+            }
         }
     }
 }
 
+/// Registers the row groups before and from a split point as two tables, so the start and
+/// end of a file can be compared in SQL.
+#[component]
+fn RowGroupRangeTables(parquet_reader: Arc<ParquetResolved>) -> Element {
+    let row_group_count = parquet_reader.metadata().metadata.num_row_groups();
+    let toast_api = use_toast();
+    let mut split_at = use_signal(|| row_group_count / 2);
+    let mut registering = use_signal(|| false);
+    let mut registered = use_signal(Vec::<String>::new);
+
+    let register = move |_| {
+        let parquet_reader = parquet_reader.clone();
+        let split_at = split_at();
+        registering.set(true);
+        spawn(async move {
+            let mut names = Vec::new();
+            for range in [0..split_at, split_at..row_group_count] {
+                match parquet_reader
+                    .register_row_group_range(SESSION_CTX.as_ref(), range)
+                    .await
+                {
+                    Ok(name) => names.push(name),
+                    Err(e) => {
+                        toast_api.error(
+                            "Failed to register row groups".to_string(),
+                            ToastOptions::new().description(format!("{e:#}")),
+                        );
+                        break;
+                    }
+                }
+            }
+            registered.set(names);
+            registering.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "mt-2 bg-base-200 p-2 rounded-md space-y-2",
+            div { class: "flex items-center gap-2",
+                label {
+                    r#for: "row-group-split",
+                    class: "text-base-content opacity-60 text-xs",
+                    title: "Register row groups before this one and from this one on as two tables",
+                    "Split at row group"
+                }
+                input {
+                    id: "row-group-split",
+                    r#type: "number",
+                    class: "input input-bordered input-xs w-20",
+                    min: "1",
+                    max: "{row_group_count - 1}",
+                    value: "{split_at()}",
+                    oninput: move |ev| {
+                        if let Ok(value) = ev.value().parse::<usize>() {
+                            split_at.set(value.clamp(1, row_group_count - 1));
+                        }
+                    },
+                }
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    disabled: registering(),
+                    onclick: register,
+                    if registering() {
+                        "Registering..."
+                    } else {
+                        "Register as two tables"
+                    }
+                }
+            }
+            if !registered().is_empty() {
+                div { class: "text-xs",
+                    "Query as "
+                    for (i , name) in registered().iter().enumerate() {
+                        if i > 0 {
+                            " and "
+                        }
+                        code { class: "font-mono break-all", "\"{name}\"" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Geospatial statistics of a geometry/geography column chunk, formatted for display.
+#[derive(Clone, PartialEq)]
+struct GeoStatsData {
+    bbox: Option<String>,
+    geometry_types: Option<String>,
+}
+
+/// Names a WKB geometry type code, e.g. `1001` is `Point Z`.
+fn geometry_type_name(code: i32) -> String {
+    let base = match code % 1000 {
+        1 => "Point",
+        2 => "LineString",
+        3 => "Polygon",
+        4 => "MultiPoint",
+        5 => "MultiLineString",
+        6 => "MultiPolygon",
+        7 => "GeometryCollection",
+        _ => return format!("Unknown ({code})"),
+    };
+    match code / 1000 {
+        1 => format!("{base} Z"),
+        2 => format!("{base} M"),
+        3 => format!("{base} ZM"),
+        _ => base.to_string(),
+    }
+}
+
+fn geo_stats_data(col: &ColumnChunkMetaData) -> Option<GeoStatsData> {
+    let is_geo = matches!(
+        col.column_descr().logical_type(),
+        Some(LogicalType::Geometry { .. } | LogicalType::Geography { .. })
+    );
+    if !is_geo {
+        return None;
+    }
+    let Some(stats) = col.geo_statistics() else {
+        return Some(GeoStatsData {
+            bbox: None,
+            geometry_types: None,
+        });
+    };
+    let bbox = stats.bounding_box().map(|bbox| {
+        let mut ranges = vec![
+            format!("x: [{}, {}]", bbox.get_xmin(), bbox.get_xmax()),
+            format!("y: [{}, {}]", bbox.get_ymin(), bbox.get_ymax()),
+        ];
+        if let (Some(min), Some(max)) = (bbox.get_zmin(), bbox.get_zmax()) {
+            ranges.push(format!("z: [{min}, {max}]"));
+        }
+        if let (Some(min), Some(max)) = (bbox.get_mmin(), bbox.get_mmax()) {
+            ranges.push(format!("m: [{min}, {max}]"));
+        }
+        ranges.join(", ")
+    });
+    let geometry_types = stats.geospatial_types().map(|types| {
+        types
+            .iter()
+            .map(|code| geometry_type_name(*code))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+    Some(GeoStatsData {
+        bbox,
+        geometry_types,
+    })
+}
+
 #[derive(Clone)]
 struct ColumnInfoData {
     compressed_size: u64,
     uncompressed_size: u64,
     compression: Compression,
+    geo_stats: Option<GeoStatsData>,
+    has_bloom_filter: bool,
 }
 
 #[component]
@@ -208,24 +596,22 @@ pub fn ColumnInfo(
             compressed_size,
             uncompressed_size,
             compression,
+            geo_stats: geo_stats_data(col),
+            has_bloom_filter: col.bloom_filter_offset().is_some(),
         }
     };
+    let probe_reader = parquet_reader.clone();
 
-    let page_count = use_resource(move || {
+    let mut page_count = use_resource(move || {
         let mut column_reader = parquet_reader.reader().clone();
         let metadata = metadata.clone();
         async move {
             count_column_chunk_pages(&mut column_reader, &metadata, row_group_id(), column_id())
                 .await
-                .unwrap_or_default()
+                .map_err(|e| e.to_string())
         }
     });
 
-    let page_count_text = match (page_count.value())() {
-        Some(value) => value.to_string(),
-        None => "...".to_string(),
-    };
-
     rsx! {
         div { class: "space-y-8",
             div { class: "flex flex-col space-y-2",
@@ -233,13 +619,13 @@ pub fn ColumnInfo(
                     div { class: "space-y-1",
                         div { class: "text-base-content opacity-60 text-xs", "Compressed" }
                         div {
-                            "{Byte::from_u64(column_info.compressed_size).get_appropriate_unit(UnitType::Binary):.2}"
+                            "{format_bytes(column_info.compressed_size)}"
                         }
                     }
                     div { class: "space-y-1",
                         div { class: "text-base-content opacity-60 text-xs", "Uncompressed" }
                         div {
-                            "{Byte::from_u64(column_info.uncompressed_size).get_appropriate_unit(UnitType::Binary):.2}"
+                            "{format_bytes(column_info.uncompressed_size)}"
                         }
                     }
                     div { class: "space-y-1",
@@ -254,10 +640,160 @@ pub fn ColumnInfo(
                     }
                     div { class: "space-y-1",
                         div { class: "text-base-content opacity-60 text-xs", "Pages" }
-                        div { "{page_count_text}" }
+                        match (page_count.value())() {
+                            Some(Ok(count)) => rsx! {
+                                div { "{count}" }
+                            },
+                            Some(Err(e)) => rsx! {
+                                LoadError {
+                                    message: e,
+                                    on_retry: move |_| page_count.restart(),
+                                }
+                            },
+                            None => rsx! {
+                                Skeleton { class: Some("h-4 w-8".to_string()) }
+                            },
+                        }
+                    }
+                }
+                if let Some(geo_stats) = column_info.geo_stats {
+                    div { class: "grid grid-cols-1 gap-2 bg-base-200 p-2 rounded-md",
+                        div { class: "space-y-1",
+                            div { class: "text-base-content opacity-60 text-xs", "Bounding box" }
+                            div { class: "font-mono break-all",
+                                {geo_stats.bbox.unwrap_or_else(|| "Not written".to_string())}
+                            }
+                        }
+                        div { class: "space-y-1",
+                            div { class: "text-base-content opacity-60 text-xs", "Geometry types" }
+                            div {
+                                {geo_stats.geometry_types.unwrap_or_else(|| "Not written".to_string())}
+                            }
+                        }
+                    }
+                }
+                if column_info.has_bloom_filter {
+                    BloomFilterProbe {
+                        key: "{row_group_id()}-{column_id()}",
+                        parquet_reader: probe_reader,
+                        row_group_id: row_group_id(),
+                        column_id: column_id(),
                     }
                 }
             }
         }
     }
 }
+
+/// Checks a typed value against a column chunk's bloom filter, to see whether a
+/// `column = value` lookup could skip this row group.
+#[component]
+fn BloomFilterProbe(
+    parquet_reader: Arc<ParquetResolved>,
+    row_group_id: usize,
+    column_id: usize,
+) -> Element {
+    let mut value = use_signal(String::new);
+    let mut probing = use_signal(|| false);
+    // The probed value and the outcome, kept until the next probe.
+    let mut result = use_signal(|| None::<(String, Result<Option<bool>, String>)>);
+
+    let probe = use_callback(move |_: ()| {
+        let parquet_reader = parquet_reader.clone();
+        let probed = value();
+        probing.set(true);
+        spawn(async move {
+            let outcome = probe_bloom_filter(
+                parquet_reader.reader().clone(),
+                parquet_reader.metadata().metadata.clone(),
+                row_group_id,
+                column_id,
+                &probed,
+            )
+            .await
+            .map_err(|e| format!("{e:#}"));
+            result.set(Some((probed, outcome)));
+            probing.set(false);
+        });
+    });
+
+    rsx! {
+        div { class: "flex flex-col gap-2 bg-base-200 p-2 rounded-md",
+            div { class: "text-base-content opacity-60 text-xs", "Bloom filter probe" }
+            div { class: "flex items-center gap-2",
+                input {
+                    r#type: "text",
+                    class: "input input-bordered input-xs flex-1 font-mono",
+                    placeholder: "Value as stored, e.g. 42 or abc",
+                    value: "{value}",
+                    oninput: move |ev| value.set(ev.value()),
+                    onkeydown: move |ev| {
+                        if ev.key() == Key::Enter {
+                            probe.call(());
+                        }
+                    },
+                }
+                button {
+                    class: "btn btn-xs",
+                    disabled: probing(),
+                    onclick: move |_| probe.call(()),
+                    if probing() {
+                        "Checking..."
+                    } else {
+                        "Check"
+                    }
+                }
+            }
+            match result() {
+                Some((probed, Ok(Some(true)))) => rsx! {
+                    div { class: "text-xs",
+                        "`{probed}` is possibly present in row group {row_group_id}; it cannot be skipped."
+                    }
+                },
+                Some((probed, Ok(Some(false)))) => rsx! {
+                    div { class: "text-xs",
+                        "`{probed}` is definitely absent from row group {row_group_id}; a lookup would skip it."
+                    }
+                },
+                Some((_, Ok(None))) => rsx! {
+                    div { class: "text-xs opacity-75", "This column chunk has no bloom filter." }
+                },
+                Some((_, Err(e))) => rsx! {
+                    div { class: "text-xs text-error break-words", "{e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test_parse_metadata_fragment() {
+        assert_eq!(
+            parse_metadata_fragment("#rg=3&col=5"),
+            FragmentSelection {
+                row_group: Some(3),
+                column: Some(5),
+            }
+        );
+        assert_eq!(
+            parse_metadata_fragment("col=2&other=x"),
+            FragmentSelection {
+                row_group: None,
+                column: Some(2),
+            }
+        );
+        assert_eq!(parse_metadata_fragment(""), FragmentSelection::default());
+        assert_eq!(
+            parse_metadata_fragment("#rg=abc"),
+            FragmentSelection::default()
+        );
+    }
+}
@@ -1,8 +1,10 @@
+pub(crate) mod load_error;
 pub mod main_layout;
 pub mod metadata;
 pub mod parquet_reader;
 pub mod parquet_rewriter;
-mod plan_visualizer;
+pub mod pivot;
+pub(crate) mod plan_visualizer;
 pub mod query_results;
 pub mod schema;
 pub mod settings;
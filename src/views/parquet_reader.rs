@@ -10,19 +10,111 @@ use parquet::arrow::async_reader::{AsyncFileReader, ParquetObjectReader};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use url::Url;
 
+use crate::SESSION_CTX;
+use crate::components::FetchProgressBar;
 use crate::components::ui::{BUTTON_GHOST, BUTTON_OUTLINE, INPUT_BASE, Panel};
 use crate::parquet_ctx::{MetadataSummary, ParquetResolved};
-use crate::storage::WebFileObjectStore;
-use crate::storage::readers;
-use crate::utils::{get_stored_value, save_to_storage};
+use crate::storage::readers::ListingEntry;
+use crate::storage::text_files::{CsvOptions, is_csv_file_name, read_csv_file};
+use crate::storage::{
+    FetchProgress, IoStats, WebFileObjectStore, pick_file_handle, supports_file_system_access,
+};
+use crate::storage::{delta, is_signed_url, readers, redact_signed_url};
+use crate::utils::{format_bytes, get_stored_value, save_to_storage};
+use crate::views::settings::preload_page_index_enabled;
 
 const S3_BUCKET_KEY: &str = "s3_bucket";
 const S3_REGION_KEY: &str = "s3_region";
 const S3_FILE_PATH_KEY: &str = "s3_file_path";
+const GCS_BUCKET_KEY: &str = "gcs_bucket";
+const GCS_FILE_PATH_KEY: &str = "gcs_file_path";
+const HF_DATASET_KEY: &str = "hf_dataset";
+const HF_REVISION_KEY: &str = "hf_revision";
+const HF_FILE_PATH_KEY: &str = "hf_file_path";
+const HF_TOKEN_KEY: &str = "hf_token";
+const HF_CONFIG_KEY: &str = "hf_config";
+const HF_SPLIT_KEY: &str = "hf_split";
 
 const DEFAULT_URL: &str = "https://huggingface.co/datasets/open-r1/OpenR1-Math-220k/resolve/main/data/train-00003-of-00010.parquet";
 
+/// File extensions recognized as Parquet, matched case-insensitively.
+pub(crate) const PARQUET_EXTENSIONS: &[&str] = &[".parquet", ".parq", ".pq"];
+
+/// Whether a file name looks like a Parquet file: either a known Parquet extension,
+/// or no extension at all (e.g. Spark/Hive outputs like `part-00000`).
+/// The content is verified against the magic bytes once the footer is fetched.
+pub(crate) fn is_parquet_file_name(file_name: &str) -> bool {
+    TableNameWithoutExtension::from_parquet_file(file_name.to_string()).is_ok()
+}
+
+/// Bytes read from the start of a file that failed the Parquet magic check, to guess its format.
+const FORMAT_SNIFF_LEN: u64 = 512;
+
+/// A file that is not Parquet, with a guess of what it is instead.
+#[derive(Debug)]
+pub(crate) struct NotParquetError {
+    pub file_name: String,
+    pub detected_format: Option<&'static str>,
+}
+
+impl std::fmt::Display for NotParquetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a valid Parquet file", self.file_name)?;
+        match self.detected_format {
+            // The viewer only reads Parquet; ORC is common enough to point at a conversion.
+            Some("ORC") => write!(
+                f,
+                "; it looks like ORC, which the viewer does not read. Convert it to Parquet \
+                 first, e.g. with `pyarrow.orc` or DuckDB's `COPY ... TO 'file.parquet'`"
+            ),
+            Some(format) => write!(f, "; it looks like {format}"),
+            None => write!(f, ": the PAR1 magic bytes are missing"),
+        }
+    }
+}
+
+impl std::error::Error for NotParquetError {}
+
+/// Whether `head` and `tail`, the first and last bytes of a file, carry the Parquet magic.
+pub(crate) fn has_parquet_magic(head: &[u8], tail: &[u8]) -> bool {
+    head.starts_with(b"PAR1") && tail.ends_with(b"PAR1")
+}
+
+/// Guesses the format of a non-Parquet file from its first bytes.
+pub(crate) fn guess_file_format(head: &[u8]) -> Option<&'static str> {
+    const MAGIC: &[(&[u8], &str)] = &[
+        (b"ORC", "ORC"),
+        (b"ARROW1", "Arrow IPC"),
+        (b"Obj\x01", "Avro"),
+        (b"\x1f\x8b", "a gzip archive"),
+        (b"PK\x03\x04", "a ZIP archive (or an Excel file)"),
+    ];
+    if let Some((_, format)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return Some(format);
+    }
+
+    // The sniffed prefix may end in the middle of a character.
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&head[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    if text.starts_with('{') || text.starts_with('[') {
+        return Some("JSON");
+    }
+    let first_line = text.lines().next().unwrap_or_default();
+    if [',', '\t', ';', '|']
+        .iter()
+        .any(|d| first_line.contains(*d))
+    {
+        return Some("CSV");
+    }
+    None
+}
+
 #[derive(Clone)]
 pub struct TableNameWithoutExtension {
     table_name: String,
@@ -30,12 +122,27 @@ pub struct TableNameWithoutExtension {
 
 impl TableNameWithoutExtension {
     fn from_parquet_file(file_name_with_extension: String) -> Result<Self> {
-        if !file_name_with_extension.ends_with(".parquet") {
-            return Err(anyhow::anyhow!("File name must end with .parquet"));
+        if file_name_with_extension.is_empty() {
+            return Err(anyhow::anyhow!("File name must not be empty"));
+        }
+        let lowercase = file_name_with_extension.to_ascii_lowercase();
+        if let Some(extension) = PARQUET_EXTENSIONS
+            .iter()
+            .find(|extension| lowercase.ends_with(*extension))
+        {
+            let file_name =
+                &file_name_with_extension[..file_name_with_extension.len() - extension.len()];
+            return Ok(Self {
+                table_name: file_name.to_string(),
+            });
+        }
+        if file_name_with_extension.contains('.') {
+            return Err(anyhow::anyhow!(
+                "File name must end with .parquet, .parq or .pq, or have no extension"
+            ));
         }
-        let file_name = file_name_with_extension.strip_suffix(".parquet").unwrap();
         Ok(Self {
-            table_name: file_name.to_string(),
+            table_name: file_name_with_extension,
         })
     }
 
@@ -50,6 +157,10 @@ pub struct ParquetUnresolved {
     pub path_relative_to_object_store: Path,
     pub object_store_url: ObjectStoreUrl,
     pub object_store: Arc<dyn ObjectStore>,
+    /// Byte counters of the object store, when it tracks them.
+    pub io_stats: Option<Arc<IoStats>>,
+    /// The Delta or Iceberg snapshot table this file was opened from, if any.
+    pub snapshot_table: Option<String>,
 }
 
 impl ParquetUnresolved {
@@ -71,8 +182,21 @@ impl ParquetUnresolved {
             path_relative_to_object_store,
             object_store_url,
             object_store,
+            io_stats: None,
+            snapshot_table: None,
         })
     }
+
+    pub(crate) fn with_io_stats(mut self, io_stats: Arc<IoStats>) -> Self {
+        self.io_stats = Some(io_stats);
+        self
+    }
+
+    pub(crate) fn with_snapshot_table(mut self, snapshot_table: String) -> Self {
+        self.snapshot_table = Some(snapshot_table);
+        self
+    }
+
     /// The table path used to register_parquet in DataFusion
     pub fn table_path(&self) -> String {
         format!(
@@ -93,6 +217,13 @@ impl ParquetUnresolved {
         let footer_size = {
             use parquet::file::FOOTER_SIZE;
 
+            if actual_file_size < FOOTER_SIZE as u64 {
+                return Err(anyhow::anyhow!(
+                    "{} is not a Parquet file: too small to contain a footer",
+                    self.path_relative_to_object_store
+                ));
+            }
+
             let footer_bytes = self
                 .object_store
                 .get_range(
@@ -103,6 +234,21 @@ impl ParquetUnresolved {
 
             // Decode the footer to get the metadata length
             let footer_tail = &footer_bytes[footer_bytes.len() - FOOTER_SIZE..];
+            if !footer_tail.ends_with(b"PAR1") {
+                // Only a file that fails the magic check pays for reading its head.
+                let head = self
+                    .object_store
+                    .get_range(
+                        &self.path_relative_to_object_store,
+                        0..FORMAT_SNIFF_LEN.min(actual_file_size),
+                    )
+                    .await?;
+                return Err(NotParquetError {
+                    file_name: self.table_name.as_str().to_string(),
+                    detected_format: guess_file_format(&head),
+                }
+                .into());
+            }
             let metadata_len = u32::from_le_bytes([
                 footer_tail[0],
                 footer_tail[1],
@@ -113,26 +259,26 @@ impl ParquetUnresolved {
             metadata_len + FOOTER_SIZE as u64
         };
 
+        let preload_page_index = preload_page_index_enabled();
         let mut reader = ParquetObjectReader::new(
             self.object_store.clone(),
             self.path_relative_to_object_store.clone(),
         )
-        .with_preload_column_index(true)
-        .with_preload_offset_index(true);
+        .with_preload_column_index(preload_page_index)
+        .with_preload_offset_index(preload_page_index);
 
         let metadata = reader.get_metadata(None).await?;
 
         let table_path = self.table_path();
 
-        if ctx
-            .runtime_env()
-            .object_store(&self.object_store_url)
-            .is_err()
+        // A store with I/O counters replaces any existing one so the counters see every query.
+        if self.io_stats.is_some()
+            || ctx
+                .runtime_env()
+                .object_store(&self.object_store_url)
+                .is_err()
         {
-            tracing::info!(
-                "Object store {} not found, registering",
-                self.object_store_url
-            );
+            tracing::info!("Registering object store {}", self.object_store_url);
             ctx.register_object_store(self.object_store_url.as_ref(), self.object_store.clone());
         } else {
             tracing::info!(
@@ -168,8 +314,11 @@ impl ParquetUnresolved {
                 metadata_memory_size as u64,
                 actual_file_size,
                 footer_size,
+                preload_page_index,
             )?,
-        ))
+            self.io_stats,
+        )
+        .with_snapshot_table(self.snapshot_table))
     }
 }
 
@@ -190,6 +339,8 @@ fn short_object_store_tag(object_store_url: &ObjectStoreUrl) -> String {
 pub fn ParquetReader(
     read_call_back: EventHandler<Result<ParquetUnresolved>>,
     initial_url: Option<String>,
+    /// Progress of the remote file being opened, if any.
+    fetch_progress: Option<FetchProgress>,
 ) -> Element {
     let mut active_tab = use_signal(|| {
         if initial_url.is_some() {
@@ -202,8 +353,10 @@ pub fn ParquetReader(
     let mut loaded_url = use_signal(|| false);
     if !loaded_url() {
         loaded_url.set(true);
-        if let Some(ref url) = initial_url {
-            read_call_back.call(readers::read_from_url(url));
+        if let Some(url) = initial_url.clone() {
+            spawn(async move {
+                read_call_back.call(readers::open_url(&url).await);
+            });
         }
     }
 
@@ -235,6 +388,16 @@ pub fn ParquetReader(
                             onclick: move |_| active_tab.set("s3".to_string()),
                             "From S3"
                         }
+                        button {
+                            class: "{tab_button_class(\"gcs\")}",
+                            onclick: move |_| active_tab.set("gcs".to_string()),
+                            "From GCS"
+                        }
+                        button {
+                            class: "{tab_button_class(\"hf\")}",
+                            onclick: move |_| active_tab.set("hf".to_string()),
+                            "From HuggingFace"
+                        }
                     }
                 }
             }
@@ -249,39 +412,70 @@ pub fn ParquetReader(
                     "s3" => rsx! {
                         S3Reader { read_call_back }
                     },
+                    "gcs" => rsx! {
+                        GcsReader { read_call_back }
+                    },
+                    "hf" => rsx! {
+                        HuggingFaceReader { read_call_back }
+                    },
                     _ => rsx! {
                         FileReader { read_call_back }
                     },
                 }
             }
+            if let Some(progress) = fetch_progress {
+                div { class: "mt-2 px-1",
+                    FetchProgressBar { progress }
+                }
+            }
         }
     }
 }
 
+/// A picked local file, with its File System Access handle when one was granted.
+type PickedFile = (web_sys::File, Option<web_sys::FileSystemFileHandle>);
+
 #[component]
 fn FileReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Element {
     let file_input_id = use_signal(|| format!("file-input-{}", uuid::Uuid::new_v4()));
     let toast_api = use_toast();
     let mut drag_depth = use_signal(|| 0i32);
     let is_dragging = move || drag_depth() > 0;
-    let mut selected_file_name = use_signal(|| None::<String>);
+    let mut selected_file_names = use_signal(Vec::<String>::new);
+    let mut csv_options = use_signal(CsvOptions::default);
+
+    let supports_file_handles = supports_file_system_access();
 
-    let read_web_file = use_callback(move |file: web_sys::File| {
+    let read_web_file = use_callback(move |(file, handle): PickedFile| {
         let table_name = file.name();
-        if !table_name.to_ascii_lowercase().ends_with(".parquet") {
+        if is_csv_file_name(&table_name) {
+            selected_file_names.write().push(table_name);
+            let options = csv_options();
+            spawn(async move {
+                read_call_back.call(read_csv_file(file, options).await);
+            });
+            return;
+        }
+        if !is_parquet_file_name(&table_name) {
             toast_api.error(
                 "Unsupported file type".to_string(),
-                ToastOptions::new().description("Please select a `.parquet` file.".to_string()),
+                ToastOptions::new().description(
+                    "Please select a `.parquet`, `.parq`, `.pq` or `.csv` file, or one without an extension."
+                        .to_string(),
+                ),
             );
             return;
         }
 
-        selected_file_name.set(Some(table_name.clone()));
+        selected_file_names.write().push(table_name.clone());
 
         let result = (|| {
             let path_relative_to_object_store = Path::parse(&table_name)?;
             let uuid = uuid::Uuid::new_v4();
-            let object_store = Arc::new(WebFileObjectStore::new(file));
+            let object_store = Arc::new(match handle {
+                Some(handle) => WebFileObjectStore::from_handle(handle, file),
+                None => WebFileObjectStore::new(file),
+            });
             let object_store_url = ObjectStoreUrl::parse(format!("webfile://{uuid}"))?;
             ParquetUnresolved::try_new(
                 table_name.clone(),
@@ -303,9 +497,27 @@ fn FileReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Elemen
             );
             return;
         };
-        read_web_file.call(file);
+        read_web_file.call((file, None));
     });
 
+    let open_file_handle = move |_| {
+        spawn(async move {
+            match pick_file_handle().await {
+                Ok(Some((handle, file))) => {
+                    selected_file_names.set(Vec::new());
+                    read_web_file.call((file, Some(handle)));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    toast_api.error(
+                        "Failed to open file".to_string(),
+                        ToastOptions::new().description(e),
+                    );
+                }
+            }
+        });
+    };
+
     rsx! {
         div {
             class: format!(
@@ -332,19 +544,13 @@ fn FileReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Elemen
                 ev.prevent_default();
                 drag_depth.set(0);
 
+                // Every dropped file opens in its own tab, registered as its own table.
                 let files = ev.files();
-                let file_count = files.len();
-                if let Some(file_data) = files.into_iter().next() {
-                    if file_count > 1 {
-                        toast_api
-
-                            .warning(
-                                "Multiple files dropped".to_string(),
-                                ToastOptions::new()
-                                    .description("Using the first file only.".to_string()),
-                            );
+                if !files.is_empty() {
+                    selected_file_names.set(Vec::new());
+                    for file_data in files {
+                        handle_file_data.call(file_data);
                     }
-                    handle_file_data.call(file_data);
                     return;
                 }
                 if let Some(text) = ev
@@ -357,16 +563,25 @@ fn FileReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Elemen
                         .map(str::trim)
                         .find(|line| !line.is_empty() && !line.starts_with('#'));
                     if let Some(url) = candidate {
-                        let looks_like_parquet_url = url.contains(".parquet");
-                        if looks_like_parquet_url {
-                            read_call_back.call(readers::read_from_url(url));
+                        let looks_like_table_url = url::Url::parse(url)
+                            .ok()
+                            .and_then(|u| {
+                                u.path_segments()
+                                    .and_then(|mut segments| segments.next_back().map(str::to_string))
+                            })
+                            .is_some_and(|name| is_parquet_file_name(&name) || is_csv_file_name(&name));
+                        if looks_like_table_url {
+                            let url = url.to_string();
+                            spawn(async move {
+                                read_call_back.call(readers::open_url(&url).await);
+                            });
                         } else {
                             toast_api
                                 .error(
-                                    "Dropped text is not a Parquet URL".to_string(),
+                                    "Dropped text is not a Parquet or CSV URL".to_string(),
                                     ToastOptions::new()
                                         .description(
-                                            "Drop a `.parquet` file, or a URL ending in `.parquet`."
+                                            "Drop a Parquet or CSV file, or a URL pointing to one."
                                                 .to_string(),
                                         ),
                                 );
@@ -378,39 +593,84 @@ fn FileReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Elemen
                     .error(
                         "Nothing to import".to_string(),
                         ToastOptions::new()
-                            .description("Drop a `.parquet` file here.".to_string()),
+                            .description("Drop a Parquet file here.".to_string()),
                     );
             },
 
             input {
                 id: "{file_input_id()}",
                 r#type: "file",
-                accept: ".parquet",
                 class: "hidden",
+                multiple: true,
                 onchange: move |ev| {
-                    let files = ev.files();
-                    let Some(file_data) = files.into_iter().next() else {
-                        return;
-                    };
-                    handle_file_data.call(file_data);
+                    selected_file_names.set(Vec::new());
+                    for file_data in ev.files() {
+                        handle_file_data.call(file_data);
+                    }
                 },
             }
 
             div { class: "flex flex-col items-center gap-1 text-center",
                 div { class: "space-y-0.5",
-                    p { class: "text-sm font-medium", "Drop a Parquet file here" }
+                    p { class: "text-sm font-medium", "Drop Parquet or CSV files here" }
+                    p { class: "text-xs opacity-60", "Each file becomes a table you can join with the others" }
                 }
 
-                label {
-                    r#for: "{file_input_id()}",
-                    class: "btn btn-outline btn-sm",
-                    "Choose file"
+                div { class: "flex items-center gap-2",
+                    label {
+                        r#for: "{file_input_id()}",
+                        class: "btn btn-outline btn-sm",
+                        "Choose files"
+                    }
+                    if supports_file_handles {
+                        button {
+                            r#type: "button",
+                            class: "btn btn-ghost btn-sm",
+                            title: "Read through a File System Access handle; suited to very large files",
+                            onclick: open_file_handle,
+                            "Open large file"
+                        }
+                    }
                 }
 
-                if let Some(name) = selected_file_name() {
+                div {
+                    class: "flex items-center gap-3 text-xs opacity-75",
+                    title: "How .csv and .tsv files are parsed; the schema is inferred from the data",
+                    span { "CSV:" }
+                    label { class: "flex items-center gap-1 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox checkbox-xs",
+                            checked: csv_options().has_header,
+                            onchange: move |ev| csv_options.write().has_header = ev.checked(),
+                        }
+                        "Header row"
+                    }
+                    label { class: "flex items-center gap-1",
+                        "Delimiter"
+                        select {
+                            class: "select select-bordered select-xs",
+                            value: "{csv_options().delimiter}",
+                            onchange: move |ev| {
+                                if let Ok(delimiter) = ev.value().parse::<u8>() {
+                                    csv_options.write().delimiter = delimiter;
+                                }
+                            },
+                            for (label , delimiter) in [("Comma", b','), ("Semicolon", b';'), ("Tab", b'\t'), ("Pipe", b'|')] {
+                                option {
+                                    value: "{delimiter}",
+                                    selected: csv_options().delimiter == delimiter,
+                                    "{label}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !selected_file_names().is_empty() {
                     p { class: "text-xs opacity-60 mt-1",
                         "Selected: "
-                        span { class: "font-mono", "{name}" }
+                        span { class: "font-mono", "{selected_file_names().join(\", \")}" }
                     }
                 }
             }
@@ -423,7 +683,20 @@ pub fn UrlReader(
     read_call_back: EventHandler<Result<ParquetUnresolved>>,
     initial_url: Option<String>,
 ) -> Element {
-    let mut url = use_signal(|| initial_url.unwrap_or_else(|| DEFAULT_URL.to_string()));
+    let initial_url = initial_url.unwrap_or_else(|| DEFAULT_URL.to_string());
+    // A pre-signed URL is kept in memory only; the input shows it with the signature elided.
+    let mut signed_url = use_signal(|| {
+        Url::parse(&initial_url)
+            .is_ok_and(|url| is_signed_url(&url))
+            .then(|| initial_url.clone())
+    });
+    let mut url = use_signal(|| redact_signed_url(&initial_url));
+    let mut is_table = use_signal(|| false);
+    let toast_api = use_toast();
+    let full_url = move || match signed_url() {
+        Some(signed) if redact_signed_url(&signed) == url() => signed,
+        _ => url(),
+    };
 
     rsx! {
         div { class: "h-full flex items-center",
@@ -431,15 +704,57 @@ pub fn UrlReader(
                 class: "w-full",
                 onsubmit: move |ev| {
                     ev.prevent_default();
-                    read_call_back.call(readers::read_from_url(&url()));
+                    if !is_table() {
+                        spawn(async move {
+                            read_call_back.call(readers::open_url(&full_url()).await);
+                        });
+                        return;
+                    }
+                    spawn(async move {
+                        match delta::read_table_from_url(SESSION_CTX.as_ref(), &full_url()).await {
+                            Ok((first_file, table_name, summary)) => {
+                                toast_api
+                                    .success(
+                                        "Table registered".to_string(),
+                                        ToastOptions::new()
+                                            .description(
+                                                format!(
+                                                    "Query {summary} as \"{table_name}\". Showing the first file.",
+                                                ),
+                                            ),
+                                    );
+                                read_call_back.call(Ok(first_file));
+                            }
+                            Err(e) => read_call_back.call(Err(e)),
+                        }
+                    });
                 },
                 div { class: "flex flex-col gap-2 sm:flex-row sm:items-center",
                     input {
                         r#type: "url",
-                        placeholder: "Enter Parquet file URL",
+                        placeholder: if is_table() { "Enter Delta or Iceberg table URL" } else { "Enter Parquet or CSV file URL" },
                         value: "{url()}",
                         class: "flex-1 {INPUT_BASE}",
-                        oninput: move |ev| url.set(ev.value()),
+                        oninput: move |ev| {
+                            let value = ev.value();
+                            if Url::parse(&value).is_ok_and(|url| is_signed_url(&url)) {
+                                url.set(redact_signed_url(&value));
+                                signed_url.set(Some(value));
+                            } else {
+                                url.set(value);
+                            }
+                        },
+                    }
+                    label {
+                        class: "flex items-center gap-1.5 text-xs cursor-pointer whitespace-nowrap",
+                        title: "Read the table's _delta_log or Iceberg metadata and register its latest snapshot as one table",
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox checkbox-xs",
+                            checked: is_table(),
+                            onchange: move |ev| is_table.set(ev.checked()),
+                        }
+                        "Delta / Iceberg table"
                     }
                     button { r#type: "submit", class: "{BUTTON_GHOST}", "Read URL" }
                 }
@@ -454,6 +769,23 @@ fn S3Reader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Element
     let mut s3_region =
         use_signal(|| get_stored_value(S3_REGION_KEY).unwrap_or("us-east-1".to_string()));
     let mut s3_file_path = use_signal(|| get_stored_value(S3_FILE_PATH_KEY).unwrap_or_default());
+    // Prefix shown in the browser and its listing, `None` while the browser is closed.
+    let mut browse_prefix = use_signal(|| None::<String>);
+    let mut listing = use_signal(|| None::<Result<Vec<ListingEntry>, String>>);
+
+    let mut browse = move |prefix: String| {
+        browse_prefix.set(Some(prefix.clone()));
+        listing.set(None);
+        spawn(async move {
+            let result = readers::list_s3_prefix(&s3_bucket(), &s3_region(), &prefix)
+                .await
+                .map_err(|e| e.to_string());
+            // A slow listing may come back after the user moved on to another folder.
+            if browse_prefix.peek().as_deref() == Some(prefix.as_str()) {
+                listing.set(Some(result));
+            }
+        });
+    };
 
     rsx! {
         div {
@@ -492,24 +824,388 @@ fn S3Reader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Element
                         }
                     }
                     div { class: "sm:col-span-2",
+                        label { class: "label text-sm font-medium", "File Path" }
+                        div { class: "flex gap-2",
+                            input {
+                                r#type: "text",
+                                class: "flex-1 {INPUT_BASE}",
+                                value: "{s3_file_path()}",
+                                oninput: move |ev| {
+                                    let value = ev.value();
+                                    save_to_storage(S3_FILE_PATH_KEY, &value);
+                                    s3_file_path.set(value);
+                                },
+                            }
+                            button {
+                                r#type: "button",
+                                class: "{BUTTON_GHOST}",
+                                title: "List the folders and Parquet files around this path",
+                                onclick: move |_| {
+                                    if browse_prefix().is_some() {
+                                        browse_prefix.set(None);
+                                    } else {
+                                        browse(browse_start(&s3_file_path()));
+                                    }
+                                },
+                                if browse_prefix().is_some() {
+                                    "Close"
+                                } else {
+                                    "Browse"
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(prefix) = browse_prefix() {
+                    div { class: "border border-base-300 rounded p-2 text-sm space-y-1",
+                        div { class: "flex items-center gap-2 text-xs opacity-75",
+                            button {
+                                r#type: "button",
+                                class: "btn btn-xs btn-ghost",
+                                disabled: prefix.is_empty(),
+                                onclick: {
+                                    let parent = parent_prefix(&prefix);
+                                    move |_| browse(parent.clone())
+                                },
+                                ".."
+                            }
+                            span { class: "font-mono truncate", "s3://{s3_bucket()}/{prefix}" }
+                        }
+                        match listing() {
+                            None => rsx! {
+                                span { class: "loading loading-spinner loading-xs" }
+                            },
+                            Some(Err(e)) => rsx! {
+                                div { class: "text-error text-xs", "Failed to list {prefix}: {e}" }
+                            },
+                            Some(Ok(entries)) if entries.is_empty() => rsx! {
+                                div { class: "text-xs opacity-60", "No folders or Parquet files here" }
+                            },
+                            Some(Ok(entries)) => rsx! {
+                                ul { class: "max-h-64 overflow-auto",
+                                    for entry in entries {
+                                        li { key: "{entry.path()}",
+                                            match &entry {
+                                                ListingEntry::Folder(path) => rsx! {
+                                                    button {
+                                                        r#type: "button",
+                                                        class: "w-full text-left font-mono px-2 py-0.5 rounded hover:bg-base-200 cursor-pointer",
+                                                        onclick: {
+                                                            let path = path.clone();
+                                                            move |_| browse(path.clone())
+                                                        },
+                                                        "{entry_name(path)}/"
+                                                    }
+                                                },
+                                                ListingEntry::File { path, size } => rsx! {
+                                                    button {
+                                                        r#type: "button",
+                                                        class: "w-full flex justify-between gap-2 text-left font-mono px-2 py-0.5 rounded hover:bg-base-200 cursor-pointer",
+                                                        onclick: {
+                                                            let path = path.clone();
+                                                            move |_| {
+                                                                save_to_storage(S3_FILE_PATH_KEY, &path);
+                                                                s3_file_path.set(path.clone());
+                                                                browse_prefix.set(None);
+                                                                read_call_back
+                                                                    .call(readers::read_from_s3(&s3_bucket(), &s3_region(), &path));
+                                                            }
+                                                        },
+                                                        span { class: "truncate", "{entry_name(path)}" }
+                                                        span { class: "opacity-60 whitespace-nowrap", "{format_bytes(*size)}" }
+                                                    }
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+                div { class: "flex justify-end",
+                    button {
+                        r#type: "submit",
+                        class: "{BUTTON_OUTLINE} w-full sm:w-auto text-center",
+                        "Read S3"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Folder to start browsing from: the path itself when it ends in `/`, else its parent.
+fn browse_start(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    match path.strip_suffix('/') {
+        Some(folder) => folder.to_string(),
+        None => parent_prefix(path),
+    }
+}
+
+fn parent_prefix(path: &str) -> String {
+    path.trim_end_matches('/')
+        .rsplit_once('/')
+        .map(|(parent, _)| parent.to_string())
+        .unwrap_or_default()
+}
+
+/// Last segment of a listed path.
+fn entry_name(path: &str) -> &str {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
+
+#[component]
+fn GcsReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Element {
+    let mut gcs_bucket = use_signal(|| get_stored_value(GCS_BUCKET_KEY).unwrap_or_default());
+    let mut gcs_file_path = use_signal(|| get_stored_value(GCS_FILE_PATH_KEY).unwrap_or_default());
+
+    rsx! {
+        div {
+            form {
+                class: "space-y-3 w-full",
+                onsubmit: move |ev| {
+                    ev.prevent_default();
+                    read_call_back.call(readers::read_from_gcs(&gcs_bucket(), &gcs_file_path()));
+                },
+                div { class: "grid grid-cols-1 gap-4 sm:grid-cols-2",
+                    div {
+                        label { class: "label text-sm font-medium", "Bucket" }
+                        input {
+                            r#type: "text",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{gcs_bucket()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(GCS_BUCKET_KEY, &value);
+                                gcs_bucket.set(value);
+                            },
+                        }
+                    }
+                    div {
                         label { class: "label text-sm font-medium", "File Path" }
                         input {
                             r#type: "text",
                             class: "w-full {INPUT_BASE}",
-                            value: "{s3_file_path()}",
+                            value: "{gcs_file_path()}",
                             oninput: move |ev| {
                                 let value = ev.value();
-                                save_to_storage(S3_FILE_PATH_KEY, &value);
-                                s3_file_path.set(value);
+                                save_to_storage(GCS_FILE_PATH_KEY, &value);
+                                gcs_file_path.set(value);
                             },
                         }
                     }
                 }
+                p { class: "text-xs opacity-75",
+                    "Private buckets need a service account in Settings, and a CORS policy that allows this site."
+                }
                 div { class: "flex justify-end",
                     button {
                         r#type: "submit",
                         class: "{BUTTON_OUTLINE} w-full sm:w-auto text-center",
-                        "Read S3"
+                        "Read GCS"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn HuggingFaceReader(read_call_back: EventHandler<Result<ParquetUnresolved>>) -> Element {
+    let mut dataset = use_signal(|| get_stored_value(HF_DATASET_KEY).unwrap_or_default());
+    let mut revision =
+        use_signal(|| get_stored_value(HF_REVISION_KEY).unwrap_or("main".to_string()));
+    let mut file_path = use_signal(|| get_stored_value(HF_FILE_PATH_KEY).unwrap_or_default());
+    let mut token = use_signal(|| get_stored_value(HF_TOKEN_KEY).unwrap_or_default());
+    let mut config = use_signal(|| get_stored_value(HF_CONFIG_KEY).unwrap_or_default());
+    let mut split = use_signal(|| get_stored_value(HF_SPLIT_KEY).unwrap_or("train".to_string()));
+    // Parquet parts of the split, `None` until listed; `Some(None)` while listing.
+    let mut parts = use_signal(|| None::<Option<Result<Vec<ListingEntry>, String>>>);
+
+    let list_parts = move |_| {
+        parts.set(Some(None));
+        spawn(async move {
+            let result = readers::list_huggingface_parts(
+                &dataset(),
+                &revision(),
+                &config(),
+                &split(),
+                &token(),
+            )
+            .await
+            .map_err(|e| e.to_string());
+            parts.set(Some(Some(result)));
+        });
+    };
+
+    rsx! {
+        div {
+            form {
+                class: "space-y-3 w-full",
+                onsubmit: move |ev| {
+                    ev.prevent_default();
+                    read_call_back
+                        .call(
+                            readers::read_from_huggingface(
+                                &dataset(),
+                                &revision(),
+                                &file_path(),
+                                &token(),
+                            ),
+                        );
+                },
+                div { class: "grid grid-cols-1 gap-4 sm:grid-cols-2",
+                    div {
+                        label { class: "label text-sm font-medium", "Dataset" }
+                        input {
+                            r#type: "text",
+                            placeholder: "org/dataset",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{dataset()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(HF_DATASET_KEY, &value);
+                                dataset.set(value);
+                            },
+                        }
+                    }
+                    div {
+                        label { class: "label text-sm font-medium", "Revision" }
+                        input {
+                            r#type: "text",
+                            placeholder: "main",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{revision()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(HF_REVISION_KEY, &value);
+                                revision.set(value);
+                            },
+                        }
+                    }
+                    div {
+                        label { class: "label text-sm font-medium", "Config (optional)" }
+                        input {
+                            r#type: "text",
+                            placeholder: "default",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{config()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(HF_CONFIG_KEY, &value);
+                                config.set(value);
+                            },
+                        }
+                    }
+                    div {
+                        label { class: "label text-sm font-medium", "Split" }
+                        div { class: "flex gap-2",
+                            input {
+                                r#type: "text",
+                                placeholder: "train",
+                                class: "flex-1 {INPUT_BASE}",
+                                value: "{split()}",
+                                oninput: move |ev| {
+                                    let value = ev.value();
+                                    save_to_storage(HF_SPLIT_KEY, &value);
+                                    split.set(value);
+                                },
+                            }
+                            button {
+                                r#type: "button",
+                                class: "{BUTTON_GHOST}",
+                                title: "List the Parquet files of this split in the dataset",
+                                onclick: list_parts,
+                                "List parts"
+                            }
+                        }
+                    }
+                    div {
+                        label { class: "label text-sm font-medium", "File Path" }
+                        input {
+                            r#type: "text",
+                            placeholder: "data/train-00000-of-00001.parquet",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{file_path()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(HF_FILE_PATH_KEY, &value);
+                                file_path.set(value);
+                            },
+                        }
+                    }
+                    div {
+                        label { class: "label text-sm font-medium", "Token (gated datasets)" }
+                        input {
+                            r#type: "password",
+                            placeholder: "hf_...",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{token()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                save_to_storage(HF_TOKEN_KEY, &value);
+                                token.set(value);
+                            },
+                        }
+                    }
+                }
+                if let Some(listed) = parts() {
+                    div { class: "border border-base-300 rounded p-2 text-sm",
+                        match listed {
+                            None => rsx! {
+                                span { class: "loading loading-spinner loading-xs" }
+                            },
+                            Some(Err(e)) => rsx! {
+                                div { class: "text-error text-xs", "Failed to list parts: {e}" }
+                            },
+                            Some(Ok(entries)) if entries.is_empty() => rsx! {
+                                div { class: "text-xs opacity-60", "No Parquet files found for split \"{split()}\"" }
+                            },
+                            Some(Ok(entries)) => rsx! {
+                                ul { class: "max-h-64 overflow-auto",
+                                    for entry in entries {
+                                        li { key: "{entry.path()}",
+                                            if let ListingEntry::File { path, size } = &entry {
+                                                button {
+                                                    r#type: "button",
+                                                    class: "w-full flex justify-between gap-2 text-left font-mono px-2 py-0.5 rounded hover:bg-base-200 cursor-pointer",
+                                                    onclick: {
+                                                        let path = path.clone();
+                                                        move |_| {
+                                                            save_to_storage(HF_FILE_PATH_KEY, &path);
+                                                            file_path.set(path.clone());
+                                                            read_call_back
+                                                                .call(
+                                                                    readers::read_from_huggingface(
+                                                                        &dataset(),
+                                                                        &revision(),
+                                                                        &path,
+                                                                        &token(),
+                                                                    ),
+                                                                );
+                                                        }
+                                                    },
+                                                    span { class: "truncate", "{path}" }
+                                                    span { class: "opacity-60 whitespace-nowrap", "{format_bytes(*size)}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+                div { class: "flex justify-end",
+                    button {
+                        r#type: "submit",
+                        class: "{BUTTON_OUTLINE} w-full sm:w-auto text-center",
+                        "Read HuggingFace"
                     }
                 }
             }
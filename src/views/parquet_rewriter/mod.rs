@@ -2,6 +2,7 @@ use dioxus::prelude::*;
 
 mod tool;
 
+pub(crate) use tool::CompressionChoice;
 use tool::ParquetRewriterTool;
 
 #[component]
@@ -1,20 +1,35 @@
-use arrow_schema::SchemaRef;
+use arrow::array::{ArrayRef, AsArray, make_array, new_null_array};
+use arrow::buffer::NullBuffer;
+use arrow::compute::{CastOptions, can_cast_types, cast_with_options};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow_schema::{DataType, Field, FieldRef, Schema, SchemaRef, TimeUnit};
 use bytes::Bytes;
+use datafusion::prelude::{SessionConfig, SessionContext};
 use dioxus::html::HasFileData;
 use dioxus::prelude::*;
 use dioxus_primitives::toast::{ToastOptions, use_toast};
-use parquet::arrow::ArrowWriter;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::{ARROW_SCHEMA_META_KEY, ArrowWriter, ProjectionMask};
 use parquet::basic::Compression;
+use parquet::file::metadata::KeyValue;
 use parquet::file::properties::{
     DEFAULT_DICTIONARY_PAGE_SIZE_LIMIT, DEFAULT_PAGE_SIZE, EnabledStatistics, WriterProperties,
+    WriterVersion,
 };
 use parquet::schema::types::ColumnPath;
-use std::collections::HashMap;
-use wasm_bindgen::JsCast;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::js_sys;
 
+use crate::storage::text_files::{
+    CsvOptions, csv_to_parquet, is_csv_file_name, is_json_file_name, json_to_parquet,
+};
+use crate::utils::download_data;
+use crate::views::parquet_reader::{
+    NotParquetError, guess_file_format, has_parquet_magic, is_parquet_file_name,
+};
+
 const DEFAULT_ROW_GROUP_SIZE: usize = 256 * 1024;
 
 /// Information about a loaded parquet file for rewriting
@@ -26,10 +41,16 @@ struct ParquetFileInfo {
     row_count: usize,
     compression: Compression,
     size_bytes: u64,
+    /// Uncompressed size of all row groups, as recorded in the footer.
+    uncompressed_bytes: u64,
+    /// Rows in the largest source row group.
+    max_row_group_rows: usize,
+    /// File-level key-value metadata, without the embedded Arrow schema.
+    key_value_metadata: Vec<(String, String)>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
-enum CompressionChoice {
+pub(crate) enum CompressionChoice {
     Zstd,
     Snappy,
     Gzip,
@@ -41,7 +62,7 @@ enum CompressionChoice {
 }
 
 impl CompressionChoice {
-    fn all() -> &'static [CompressionChoice] {
+    pub(crate) fn all() -> &'static [CompressionChoice] {
         &[
             CompressionChoice::Lz4Raw,
             CompressionChoice::Zstd,
@@ -53,7 +74,7 @@ impl CompressionChoice {
         ]
     }
 
-    fn value(&self) -> &'static str {
+    pub(crate) fn value(&self) -> &'static str {
         match self {
             CompressionChoice::Zstd => "zstd",
             CompressionChoice::Snappy => "snappy",
@@ -65,7 +86,7 @@ impl CompressionChoice {
         }
     }
 
-    fn label(&self) -> &'static str {
+    pub(crate) fn label(&self) -> &'static str {
         match self {
             CompressionChoice::Zstd => "ZSTD",
             CompressionChoice::Snappy => "Snappy",
@@ -77,7 +98,7 @@ impl CompressionChoice {
         }
     }
 
-    fn from_value(value: &str) -> Option<Self> {
+    pub(crate) fn from_value(value: &str) -> Option<Self> {
         match value {
             "zstd" => Some(CompressionChoice::Zstd),
             "snappy" => Some(CompressionChoice::Snappy),
@@ -90,7 +111,7 @@ impl CompressionChoice {
         }
     }
 
-    fn to_parquet(self) -> Compression {
+    pub(crate) fn to_parquet(self) -> Compression {
         match self {
             CompressionChoice::Zstd => Compression::ZSTD(Default::default()),
             CompressionChoice::Snappy => Compression::SNAPPY,
@@ -103,6 +124,196 @@ impl CompressionChoice {
     }
 }
 
+/// Target type for a per-column cast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TypeChoice {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Utf8,
+    LargeUtf8,
+    Utf8View,
+    DictionaryUtf8,
+    TimestampSecond,
+    TimestampMillisecond,
+    TimestampMicrosecond,
+    TimestampNanosecond,
+    Date32,
+}
+
+impl TypeChoice {
+    fn all() -> &'static [TypeChoice] {
+        &[
+            TypeChoice::Int8,
+            TypeChoice::Int16,
+            TypeChoice::Int32,
+            TypeChoice::Int64,
+            TypeChoice::UInt8,
+            TypeChoice::UInt16,
+            TypeChoice::UInt32,
+            TypeChoice::UInt64,
+            TypeChoice::Float32,
+            TypeChoice::Float64,
+            TypeChoice::Utf8,
+            TypeChoice::LargeUtf8,
+            TypeChoice::Utf8View,
+            TypeChoice::DictionaryUtf8,
+            TypeChoice::TimestampSecond,
+            TypeChoice::TimestampMillisecond,
+            TypeChoice::TimestampMicrosecond,
+            TypeChoice::TimestampNanosecond,
+            TypeChoice::Date32,
+        ]
+    }
+
+    fn value(&self) -> &'static str {
+        match self {
+            TypeChoice::Int8 => "int8",
+            TypeChoice::Int16 => "int16",
+            TypeChoice::Int32 => "int32",
+            TypeChoice::Int64 => "int64",
+            TypeChoice::UInt8 => "uint8",
+            TypeChoice::UInt16 => "uint16",
+            TypeChoice::UInt32 => "uint32",
+            TypeChoice::UInt64 => "uint64",
+            TypeChoice::Float32 => "float32",
+            TypeChoice::Float64 => "float64",
+            TypeChoice::Utf8 => "utf8",
+            TypeChoice::LargeUtf8 => "large_utf8",
+            TypeChoice::Utf8View => "utf8_view",
+            TypeChoice::DictionaryUtf8 => "dictionary_utf8",
+            TypeChoice::TimestampSecond => "timestamp_s",
+            TypeChoice::TimestampMillisecond => "timestamp_ms",
+            TypeChoice::TimestampMicrosecond => "timestamp_us",
+            TypeChoice::TimestampNanosecond => "timestamp_ns",
+            TypeChoice::Date32 => "date32",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            TypeChoice::Int8 => "Int8",
+            TypeChoice::Int16 => "Int16",
+            TypeChoice::Int32 => "Int32",
+            TypeChoice::Int64 => "Int64",
+            TypeChoice::UInt8 => "UInt8",
+            TypeChoice::UInt16 => "UInt16",
+            TypeChoice::UInt32 => "UInt32",
+            TypeChoice::UInt64 => "UInt64",
+            TypeChoice::Float32 => "Float32",
+            TypeChoice::Float64 => "Float64",
+            TypeChoice::Utf8 => "String",
+            TypeChoice::LargeUtf8 => "LargeString",
+            TypeChoice::Utf8View => "StringView",
+            TypeChoice::DictionaryUtf8 => "Dictionary<Int32, String>",
+            TypeChoice::TimestampSecond => "Timestamp (s)",
+            TypeChoice::TimestampMillisecond => "Timestamp (ms)",
+            TypeChoice::TimestampMicrosecond => "Timestamp (us)",
+            TypeChoice::TimestampNanosecond => "Timestamp (ns)",
+            TypeChoice::Date32 => "Date32",
+        }
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|choice| choice.value() == value)
+    }
+
+    /// The arrow type to cast to. Timestamp unit changes keep the source timezone.
+    fn to_arrow(self, source: &DataType) -> DataType {
+        let timezone = match source {
+            DataType::Timestamp(_, tz) => tz.clone(),
+            _ => None,
+        };
+        match self {
+            TypeChoice::Int8 => DataType::Int8,
+            TypeChoice::Int16 => DataType::Int16,
+            TypeChoice::Int32 => DataType::Int32,
+            TypeChoice::Int64 => DataType::Int64,
+            TypeChoice::UInt8 => DataType::UInt8,
+            TypeChoice::UInt16 => DataType::UInt16,
+            TypeChoice::UInt32 => DataType::UInt32,
+            TypeChoice::UInt64 => DataType::UInt64,
+            TypeChoice::Float32 => DataType::Float32,
+            TypeChoice::Float64 => DataType::Float64,
+            TypeChoice::Utf8 => DataType::Utf8,
+            TypeChoice::LargeUtf8 => DataType::LargeUtf8,
+            TypeChoice::Utf8View => DataType::Utf8View,
+            TypeChoice::DictionaryUtf8 => {
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+            }
+            TypeChoice::TimestampSecond => DataType::Timestamp(TimeUnit::Second, timezone),
+            TypeChoice::TimestampMillisecond => {
+                DataType::Timestamp(TimeUnit::Millisecond, timezone)
+            }
+            TypeChoice::TimestampMicrosecond => {
+                DataType::Timestamp(TimeUnit::Microsecond, timezone)
+            }
+            TypeChoice::TimestampNanosecond => DataType::Timestamp(TimeUnit::Nanosecond, timezone),
+            TypeChoice::Date32 => DataType::Date32,
+        }
+    }
+}
+
+/// Quick choices for `row_group_size`, for users who don't know the right raw number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RowGroupPreset {
+    /// Aim for row groups of about this many uncompressed bytes.
+    TargetBytes(u64),
+    Rows(usize),
+    /// Keep the row group size of the source files.
+    MatchSource,
+}
+
+impl RowGroupPreset {
+    fn all() -> &'static [RowGroupPreset] {
+        &[
+            RowGroupPreset::TargetBytes(128 * 1024 * 1024),
+            RowGroupPreset::Rows(1_000_000),
+            RowGroupPreset::MatchSource,
+        ]
+    }
+
+    fn label(&self) -> String {
+        match self {
+            RowGroupPreset::TargetBytes(bytes) => format!("{} MB target", bytes / (1024 * 1024)),
+            RowGroupPreset::Rows(rows) if rows % 1_000_000 == 0 => {
+                format!("{}M rows", rows / 1_000_000)
+            }
+            RowGroupPreset::Rows(rows) => format!("{} rows", format_rows(*rows)),
+            RowGroupPreset::MatchSource => "Match source".to_string(),
+        }
+    }
+
+    /// The row group size this preset picks for `files`, or `None` if it needs files to
+    /// estimate from and there are none.
+    fn row_group_size(&self, files: &[ParquetFileInfo]) -> Option<usize> {
+        match self {
+            RowGroupPreset::Rows(rows) => Some(*rows),
+            RowGroupPreset::TargetBytes(target) => {
+                let rows: usize = files.iter().map(|f| f.row_count).sum();
+                let bytes: u64 = files.iter().map(|f| f.uncompressed_bytes).sum();
+                if rows == 0 || bytes == 0 {
+                    return None;
+                }
+                let average_row_bytes = (bytes as f64 / rows as f64).max(1.0);
+                Some(((*target as f64 / average_row_bytes) as usize).max(1))
+            }
+            RowGroupPreset::MatchSource => files
+                .iter()
+                .map(|f| f.max_row_group_rows)
+                .max()
+                .filter(|rows| *rows > 0),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct RewriteSettings {
     compression: CompressionChoice,
@@ -111,8 +322,34 @@ struct RewriteSettings {
     row_group_size: usize,
     page_index_enabled: bool,
     bloom_filter_enabled: bool,
+    writer_version: WriterVersion,
+    dictionary_enabled: bool,
+    /// Drop statistics, page indexes and bloom filters for the smallest footer.
+    /// Overrides `page_index_enabled` and `bloom_filter_enabled`.
+    minimal_footer: bool,
+    flatten_nested: bool,
+    /// Top-level columns left out of the output.
+    dropped_columns: HashSet<String>,
+    /// Output order of the top-level columns by source name. Columns not listed follow in
+    /// source order, so an empty list keeps the source order.
+    column_order: Vec<String>,
+    /// New names of top-level columns, keyed by source name. Other settings keep using the
+    /// source names.
+    column_renames: HashMap<String, String>,
     per_column_compression: bool,
     column_compressions: HashMap<String, CompressionChoice>,
+    per_column_dictionary: bool,
+    /// Dictionary encoding on or off per column, overriding `dictionary_enabled`.
+    column_dictionaries: HashMap<String, bool>,
+    cast_columns: bool,
+    column_types: HashMap<String, TypeChoice>,
+    /// Replace the file-level key-value metadata with `key_value_metadata`.
+    edit_key_value_metadata: bool,
+    key_value_metadata: Vec<(String, String)>,
+    /// SQL predicate rows must satisfy to be written, e.g. `year >= 2020`. Empty keeps every row.
+    row_filter: String,
+    /// Split the output into files of at most this many rows, downloaded as a ZIP. 0 writes one file.
+    rows_per_file: usize,
 }
 
 impl Default for RewriteSettings {
@@ -124,8 +361,23 @@ impl Default for RewriteSettings {
             row_group_size: DEFAULT_ROW_GROUP_SIZE,
             page_index_enabled: true,
             bloom_filter_enabled: false,
+            writer_version: WriterVersion::PARQUET_1_0,
+            dictionary_enabled: true,
+            minimal_footer: false,
+            flatten_nested: false,
+            dropped_columns: HashSet::new(),
+            column_order: Vec::new(),
+            column_renames: HashMap::new(),
             per_column_compression: false,
             column_compressions: HashMap::new(),
+            per_column_dictionary: false,
+            column_dictionaries: HashMap::new(),
+            cast_columns: false,
+            column_types: HashMap::new(),
+            edit_key_value_metadata: false,
+            key_value_metadata: Vec::new(),
+            row_filter: String::new(),
+            rows_per_file: 0,
         }
     }
 }
@@ -136,6 +388,35 @@ struct RewriteState {
     files: Vec<ParquetFileInfo>,
     is_rewriting: bool,
     error: Option<String>,
+    /// Field differences behind a schema mismatch `error`.
+    schema_differences: Vec<String>,
+    /// Accept files with differing columns and write them under the union of their schemas.
+    union_schema: bool,
+    /// Sizes before and after the last successful rewrite.
+    summary: Option<RewriteSummary>,
+}
+
+/// Compressed bytes of a kept top-level column, by source name, before and after a rewrite.
+#[derive(Clone, Debug, PartialEq)]
+struct ColumnSizeChange {
+    name: String,
+    /// Output name, when the column was renamed.
+    renamed: Option<String>,
+    input_bytes: u64,
+    output_bytes: u64,
+}
+
+/// Input and output sizes of a rewrite, to judge whether the chosen settings helped.
+#[derive(Clone, Debug, PartialEq)]
+struct RewriteSummary {
+    input_bytes: u64,
+    output_bytes: u64,
+    columns: Vec<ColumnSizeChange>,
+}
+
+/// Change from `before` to `after` in percent, `None` when there was nothing before.
+fn size_change_pct(before: u64, after: u64) -> Option<f64> {
+    (before > 0).then(|| (after as f64 - before as f64) / before as f64 * 100.0)
 }
 
 impl RewriteState {
@@ -150,6 +431,163 @@ impl RewriteState {
     fn total_rows(&self) -> usize {
         self.files.iter().map(|f| f.row_count).sum()
     }
+
+    /// The first file whose schema differs from the first file's.
+    fn first_mismatch(&self) -> Option<&ParquetFileInfo> {
+        let first_schema = &self.files.first()?.schema;
+        self.files
+            .iter()
+            .skip(1)
+            .find(|f| f.schema != *first_schema)
+    }
+
+    /// Recomputes the mismatch error after files are removed or the union toggle changes.
+    fn refresh_schema_error(&mut self) {
+        self.error = None;
+        self.schema_differences.clear();
+        if self.union_schema {
+            return;
+        }
+        if let Some(mismatch) = self.first_mismatch() {
+            let error = format!(
+                "Schema mismatch: '{}' has a different schema than the first file",
+                mismatch.name
+            );
+            self.schema_differences = schema_differences(&self.files[0].schema, &mismatch.schema);
+            self.error = Some(error);
+        }
+    }
+
+    /// The schema the rewrite reads from: the union of all files when unioning is on and the
+    /// schemas are compatible, otherwise the first file's.
+    fn input_schema(&self) -> Option<SchemaRef> {
+        let first_schema = self.files.first()?.schema.clone();
+        if !self.union_schema {
+            return Some(first_schema);
+        }
+        Some(union_schemas(&self.files).unwrap_or(first_schema))
+    }
+}
+
+/// All fields of `files` in order of first appearance. Fields missing from some file become
+/// nullable; the same name with different types is an error.
+fn union_schemas(files: &[ParquetFileInfo]) -> anyhow::Result<SchemaRef> {
+    let Some(first) = files.first() else {
+        return Err(anyhow::anyhow!("No files to rewrite"));
+    };
+    if files.iter().all(|file| file.schema == first.schema) {
+        return Ok(first.schema.clone());
+    }
+
+    let mut fields: Vec<(Field, &str)> = Vec::new();
+    for file in files {
+        for field in file.schema.fields() {
+            match fields
+                .iter_mut()
+                .find(|(seen, _)| seen.name() == field.name())
+            {
+                Some((seen, seen_in)) if seen.data_type() != field.data_type() => {
+                    return Err(anyhow::anyhow!(
+                        "Column `{}` is {} in '{}' but {} in '{}'",
+                        field.name(),
+                        seen.data_type(),
+                        seen_in,
+                        field.data_type(),
+                        file.name
+                    ));
+                }
+                Some((seen, _)) => {
+                    if field.is_nullable() {
+                        seen.set_nullable(true);
+                    }
+                }
+                None => fields.push((field.as_ref().clone(), file.name.as_str())),
+            }
+        }
+    }
+    let fields = fields
+        .into_iter()
+        .map(|(field, _)| {
+            let in_every_file = files
+                .iter()
+                .all(|file| file.schema.field_with_name(field.name()).is_ok());
+            let nullable = field.is_nullable() || !in_every_file;
+            field.with_nullable(nullable)
+        })
+        .collect::<Vec<_>>();
+    Ok(Arc::new(Schema::new_with_metadata(
+        fields,
+        first.schema.metadata().clone(),
+    )))
+}
+
+/// Reorders the columns of `batch` to `schema` by name, filling columns it lacks with nulls.
+fn align_batch(batch: RecordBatch, schema: &SchemaRef) -> anyhow::Result<RecordBatch> {
+    if batch.schema() == *schema {
+        return Ok(batch);
+    }
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) => column.clone(),
+            None => new_null_array(field.data_type(), batch.num_rows()),
+        })
+        .collect();
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    Ok(RecordBatch::try_new_with_options(
+        schema.clone(),
+        columns,
+        &options,
+    )?)
+}
+
+/// Field-by-field differences of `actual` from `expected`, empty when the schemas are equal.
+fn schema_differences(expected: &Schema, actual: &Schema) -> Vec<String> {
+    let mut differences = Vec::new();
+    for (position, field) in expected.fields().iter().enumerate() {
+        let name = field.name();
+        let Some((actual_position, actual_field)) = actual.fields().find(name) else {
+            differences.push(format!("Missing field `{name}`"));
+            continue;
+        };
+        if field.data_type() != actual_field.data_type() {
+            differences.push(format!(
+                "`{name}`: type {} instead of {}",
+                actual_field.data_type(),
+                field.data_type()
+            ));
+        }
+        if field.is_nullable() != actual_field.is_nullable() {
+            let nullability = |nullable: bool| if nullable { "nullable" } else { "required" };
+            differences.push(format!(
+                "`{name}`: {} instead of {}",
+                nullability(actual_field.is_nullable()),
+                nullability(field.is_nullable())
+            ));
+        }
+        if field.metadata() != actual_field.metadata() {
+            differences.push(format!("`{name}`: field metadata differs"));
+        }
+        if position != actual_position {
+            differences.push(format!(
+                "`{name}`: at position {actual_position} instead of {position}"
+            ));
+        }
+    }
+    for field in actual.fields() {
+        if expected.fields().find(field.name()).is_none() {
+            differences.push(format!(
+                "Extra field `{}` ({})",
+                field.name(),
+                field.data_type()
+            ));
+        }
+    }
+    if differences.is_empty() && expected.metadata() != actual.metadata() {
+        differences.push("Schema metadata differs".to_string());
+    }
+    differences
 }
 
 #[component]
@@ -157,33 +595,46 @@ pub fn ParquetRewriterTool() -> Element {
     let toast_api = use_toast();
     let mut state = use_signal(RewriteState::default);
     let mut settings = use_signal(RewriteSettings::default);
+    // Column being dragged in the column list, by source name.
+    let mut dragged_column = use_signal(|| None::<String>);
     let mut drag_depth = use_signal(|| 0i32);
     let is_dragging = move || drag_depth() > 0;
     let file_input_id = use_signal(|| format!("rewrite-file-input-{}", uuid::Uuid::new_v4()));
 
     let add_file = use_callback(move |file_info: ParquetFileInfo| {
         let mut current = state();
-        if !current.files.is_empty() && current.files[0].schema != file_info.schema {
+        if !current.union_schema
+            && !current.files.is_empty()
+            && current.files[0].schema != file_info.schema
+        {
             state.set(RewriteState {
                 error: Some(format!(
                     "Schema mismatch: '{}' has a different schema than the first file",
                     file_info.name
                 )),
+                schema_differences: schema_differences(&current.files[0].schema, &file_info.schema),
                 ..current
             });
             return;
         }
         current.files.push(file_info);
         current.error = None;
+        current.schema_differences.clear();
         state.set(current);
     });
 
     let read_web_file = use_callback(move |file: web_sys::File| {
         let file_name = file.name();
-        if !file_name.to_ascii_lowercase().ends_with(".parquet") {
+        if !is_parquet_file_name(&file_name)
+            && !is_csv_file_name(&file_name)
+            && !is_json_file_name(&file_name)
+        {
             toast_api.error(
                 "Unsupported file type".to_string(),
-                ToastOptions::new().description("Please select `.parquet` files only.".to_string()),
+                ToastOptions::new().description(
+                    "Please select `.parquet`, `.parq`, `.pq`, `.csv`, `.tsv` or newline-delimited `.json` files, or files without an extension."
+                        .to_string(),
+                ),
             );
             return;
         }
@@ -218,13 +669,18 @@ pub fn ParquetRewriterTool() -> Element {
     let mut remove_file = move |index: usize| {
         let mut current = state();
         current.files.remove(index);
-        current.error = None;
-        if !current.schemas_match() {
-            current.error = Some("Schema mismatch between remaining files".to_string());
-        }
+        current.refresh_schema_error();
         state.set(current);
     };
 
+    let toggle_union_schema = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        state.with_mut(|current| {
+            current.union_schema = enabled;
+            current.refresh_schema_error();
+        });
+    };
+
     let clear_all = move |_| {
         state.set(RewriteState::default());
     };
@@ -249,6 +705,22 @@ pub fn ParquetRewriterTool() -> Element {
         }
     };
 
+    // Empty input goes back to writing a single file.
+    let update_rows_per_file = move |ev: Event<FormData>| {
+        let value = ev.value();
+        if value.trim().is_empty() {
+            settings.with_mut(|current| current.rows_per_file = 0);
+        } else if let Ok(value) = value.parse::<usize>() {
+            settings.with_mut(|current| current.rows_per_file = value);
+        }
+    };
+
+    let apply_row_group_preset = move |preset: RowGroupPreset| {
+        if let Some(size) = preset.row_group_size(&state.read().files) {
+            settings.with_mut(|current| current.row_group_size = size);
+        }
+    };
+
     let update_dictionary_page_size = move |ev: Event<FormData>| {
         if let Ok(value) = ev.value().parse::<usize>()
             && value > 0
@@ -273,11 +745,66 @@ pub fn ParquetRewriterTool() -> Element {
         settings.with_mut(|current| current.bloom_filter_enabled = enabled);
     };
 
+    let toggle_minimal_footer = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        settings.with_mut(|current| current.minimal_footer = enabled);
+    };
+
+    let toggle_flatten_nested = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        settings.with_mut(|current| current.flatten_nested = enabled);
+    };
+
+    let update_row_filter = move |ev: Event<FormData>| {
+        let value = ev.value();
+        settings.with_mut(|current| current.row_filter = value);
+    };
+
+    let update_writer_version = move |ev: Event<FormData>| {
+        let version = match ev.value().as_str() {
+            "2.0" => WriterVersion::PARQUET_2_0,
+            _ => WriterVersion::PARQUET_1_0,
+        };
+        settings.with_mut(|current| current.writer_version = version);
+    };
+
+    let toggle_dictionary = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        settings.with_mut(|current| current.dictionary_enabled = enabled);
+    };
+
+    let toggle_per_column_dictionary = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        settings.with_mut(|current| current.per_column_dictionary = enabled);
+    };
+
     let toggle_per_column_compression = move |ev: Event<FormData>| {
         let enabled = ev.checked();
         settings.with_mut(|current| current.per_column_compression = enabled);
     };
 
+    let toggle_cast_columns = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        settings.with_mut(|current| current.cast_columns = enabled);
+    };
+
+    // Starts from the first file's entries the first time editing is turned on.
+    let toggle_edit_key_value_metadata = move |ev: Event<FormData>| {
+        let enabled = ev.checked();
+        let first_file_entries = state
+            .read()
+            .files
+            .first()
+            .map(|file| file.key_value_metadata.clone())
+            .unwrap_or_default();
+        settings.with_mut(|current| {
+            current.edit_key_value_metadata = enabled;
+            if enabled && current.key_value_metadata.is_empty() {
+                current.key_value_metadata = first_file_entries;
+            }
+        });
+    };
+
     let do_rewrite = move |_| {
         let current = state();
         if current.files.is_empty() {
@@ -288,7 +815,7 @@ pub fn ParquetRewriterTool() -> Element {
             return;
         }
 
-        if !current.schemas_match() {
+        if !current.union_schema && !current.schemas_match() {
             toast_api.error(
                 "Schema mismatch".to_string(),
                 ToastOptions::new().description(
@@ -299,17 +826,41 @@ pub fn ParquetRewriterTool() -> Element {
             return;
         }
 
+        let input_schema = match union_schemas(&current.files) {
+            Ok(schema) => schema,
+            Err(e) => {
+                toast_api.error(
+                    "Schema conflict".to_string(),
+                    ToastOptions::new().description(format!("{}", e)),
+                );
+                return;
+            }
+        };
+
+        let active_settings = settings();
+        if let Err(e) = validate_row_filter(&input_schema, &active_settings) {
+            toast_api.error(
+                "Invalid row filter".to_string(),
+                ToastOptions::new().description(format!("{}", e)),
+            );
+            return;
+        }
+
         state.set(RewriteState {
             is_rewriting: true,
             ..current.clone()
         });
 
-        let active_settings = settings();
-
         spawn(async move {
-            match rewrite_parquet_files(&current.files, &active_settings).await {
-                Ok(rewritten_data) => {
-                    download_data("rewritten.parquet", rewritten_data);
+            let result = rewrite_parquet_files(&current.files, &active_settings).await;
+            let summary = result.as_ref().ok().and_then(|parts| {
+                rewrite_summary(&current.files, parts, &active_settings)
+                    .inspect_err(|e| tracing::warn!("Failed to summarize the rewrite: {e}"))
+                    .ok()
+            });
+            match result {
+                Ok(mut parts) if parts.len() == 1 => {
+                    download_data("rewritten.parquet", parts.remove(0));
                     toast_api.success(
                         "Rewrite complete".to_string(),
                         ToastOptions::new()
@@ -317,9 +868,40 @@ pub fn ParquetRewriterTool() -> Element {
                     );
                     state.set(RewriteState {
                         is_rewriting: false,
+                        summary,
                         ..state()
                     });
                 }
+                Ok(parts) => {
+                    let part_count = parts.len();
+                    match zip_parts(parts) {
+                        Ok(archive) => {
+                            download_data("rewritten.zip", archive);
+                            toast_api.success(
+                                "Rewrite complete".to_string(),
+                                ToastOptions::new().description(format!(
+                                    "A ZIP of {part_count} files is downloading."
+                                )),
+                            );
+                            state.set(RewriteState {
+                                is_rewriting: false,
+                                summary,
+                                ..state()
+                            });
+                        }
+                        Err(e) => {
+                            toast_api.error(
+                                "Rewrite failed".to_string(),
+                                ToastOptions::new().description(format!("{}", e)),
+                            );
+                            state.set(RewriteState {
+                                is_rewriting: false,
+                                error: Some(format!("{}", e)),
+                                ..state()
+                            });
+                        }
+                    }
+                }
                 Err(e) => {
                     toast_api.error(
                         "Rewrite failed".to_string(),
@@ -338,20 +920,34 @@ pub fn ParquetRewriterTool() -> Element {
     let current_state = state();
     let current_settings = settings();
     let has_files = !current_state.files.is_empty();
-    let can_rewrite = has_files && current_state.schemas_match();
-    let column_names: Vec<String> = current_state
-        .files
-        .first()
-        .map(|file| {
-            file.schema
+    let can_rewrite = has_files && (current_state.union_schema || current_state.schemas_match());
+    let input_schema = current_state.input_schema();
+    let column_names: Vec<String> = input_schema
+        .as_ref()
+        .map(|schema| {
+            schema
                 .fields()
                 .iter()
                 .map(|field| field.name().to_string())
                 .collect()
         })
         .unwrap_or_default();
+    let row_filter_error = input_schema
+        .as_ref()
+        .and_then(|schema| validate_row_filter(schema, &current_settings).err())
+        .map(|e| e.to_string());
+    let rename_error = input_schema
+        .as_ref()
+        .and_then(|schema| rename_schema(schema, &current_settings.column_renames).err())
+        .map(|e| e.to_string());
+    let ordered_column_names = ordered_columns(&column_names, &current_settings.column_order);
+    let kept_column_count = column_names
+        .iter()
+        .filter(|name| !current_settings.dropped_columns.contains(*name))
+        .count();
     let column_rows: Vec<(String, String)> = column_names
         .iter()
+        .filter(|name| !current_settings.dropped_columns.contains(*name))
         .map(|name| {
             let override_value = current_settings
                 .column_compressions
@@ -361,6 +957,40 @@ pub fn ParquetRewriterTool() -> Element {
             (name.clone(), override_value)
         })
         .collect();
+    let column_dictionary_rows: Vec<(String, String)> = column_names
+        .iter()
+        .filter(|name| !current_settings.dropped_columns.contains(*name))
+        .map(|name| {
+            let override_value = match current_settings.column_dictionaries.get(name) {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "default",
+            };
+            (name.clone(), override_value.to_string())
+        })
+        .collect();
+    let column_type_rows: Vec<(String, String, String)> = input_schema
+        .as_ref()
+        .map(|schema| {
+            schema
+                .fields()
+                .iter()
+                .filter(|field| !current_settings.dropped_columns.contains(field.name()))
+                .map(|field| {
+                    let override_value = current_settings
+                        .column_types
+                        .get(field.name())
+                        .map(|choice| choice.value().to_string())
+                        .unwrap_or_else(|| "keep".to_string());
+                    (
+                        field.name().to_string(),
+                        field.data_type().to_string(),
+                        override_value,
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
     rsx! {
         div { class: "space-y-6 select-text",
@@ -388,8 +1018,17 @@ pub fn ParquetRewriterTool() -> Element {
                             d: "M12 9v3.75m9-.75a9 9 0 11-18 0 9 9 0 0118 0zm-9 3.75h.008v.008H12v-.008z",
                         }
                     }
-                    span { class: "text-sm text-red-600 dark:text-red-400 select-text",
-                        "{error}"
+                    div { class: "space-y-1 min-w-0",
+                        span { class: "text-sm text-red-600 dark:text-red-400 select-text",
+                            "{error}"
+                        }
+                        if !current_state.schema_differences.is_empty() {
+                            ul { class: "list-disc pl-4 text-xs text-red-600 dark:text-red-400 font-mono break-all select-text",
+                                for difference in current_state.schema_differences.iter() {
+                                    li { "{difference}" }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -441,7 +1080,6 @@ pub fn ParquetRewriterTool() -> Element {
                         input {
                             id: "{file_input_id()}",
                             r#type: "file",
-                            accept: ".parquet",
                             multiple: true,
                             class: "hidden",
                             onchange: move |ev| {
@@ -468,7 +1106,7 @@ pub fn ParquetRewriterTool() -> Element {
                             }
                             div {
                                 p { class: "text-primary text-sm font-medium select-text",
-                                    "Drop Parquet files here"
+                                    "Drop Parquet, CSV or JSON files here"
                                 }
                                 p { class: "text-tertiary text-xs mt-0.5 select-text",
                                     "or click to browse"
@@ -483,6 +1121,21 @@ pub fn ParquetRewriterTool() -> Element {
                         }
                     }
 
+                    div { class: "flex items-start justify-between gap-3",
+                        div { class: "space-y-0.5",
+                            label { class: "text-xs text-tertiary select-text", "Union schema" }
+                            p { class: "text-[11px] text-tertiary select-text",
+                                "Accept files with different columns. Columns a file lacks are filled with nulls."
+                            }
+                        }
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm",
+                            checked: current_state.union_schema,
+                            onchange: toggle_union_schema,
+                        }
+                    }
+
                     if has_files {
                         div { class: "space-y-2",
                             div { class: "flex items-center justify-between",
@@ -490,8 +1143,14 @@ pub fn ParquetRewriterTool() -> Element {
                                     "Files ({current_state.files.len()})"
                                 }
                                 if !current_state.schemas_match() {
-                                    span { class: "text-red-500 text-xs select-text",
-                                        "Schema mismatch"
+                                    if current_state.union_schema {
+                                        span { class: "text-tertiary text-xs select-text",
+                                            "Schemas unioned"
+                                        }
+                                    } else {
+                                        span { class: "text-red-500 text-xs select-text",
+                                            "Schema mismatch"
+                                        }
                                     }
                                 }
                             }
@@ -581,6 +1240,17 @@ pub fn ParquetRewriterTool() -> Element {
                                 }
                             }
 
+                            div { class: "space-y-1",
+                                label { class: "text-xs text-tertiary select-text", "Writer version" }
+                                select {
+                                    class: "select select-bordered select-sm w-full select-text",
+                                    value: if current_settings.writer_version == WriterVersion::PARQUET_2_0 { "2.0" } else { "1.0" },
+                                    onchange: update_writer_version,
+                                    option { value: "1.0", "1.0 (widest compatibility)" }
+                                    option { value: "2.0", "2.0 (data page v2, newer encodings)" }
+                                }
+                            }
+
                             div { class: "space-y-1",
                                 label { class: "text-xs text-tertiary select-text",
                                     "Row group size (rows)"
@@ -595,6 +1265,25 @@ pub fn ParquetRewriterTool() -> Element {
                                 p { class: "text-[11px] text-tertiary select-text",
                                     "{format_rows(current_settings.row_group_size)} rows per group"
                                 }
+                                div { class: "flex flex-wrap gap-1",
+                                    for preset in RowGroupPreset::all().iter().copied() {
+                                        {
+                                            let size = preset.row_group_size(&current_state.files);
+                                            rsx! {
+                                                button {
+                                                    class: "btn-soft text-[11px] px-2 py-0.5 select-text",
+                                                    disabled: size.is_none(),
+                                                    title: match size {
+                                                        Some(size) => format!("{} rows per group", format_rows(size)),
+                                                        None => "Add a file to estimate from".to_string(),
+                                                    },
+                                                    onclick: move |_| apply_row_group_preset(preset),
+                                                    "{preset.label()}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
 
                             div { class: "space-y-1",
@@ -628,6 +1317,27 @@ pub fn ParquetRewriterTool() -> Element {
                                     "{format_bytes_short(current_settings.dictionary_page_size as u64)} per dictionary page"
                                 }
                             }
+
+                            div { class: "space-y-1",
+                                label { class: "text-xs text-tertiary select-text",
+                                    "Rows per output file"
+                                }
+                                input {
+                                    class: "input input-bordered input-sm w-full select-text",
+                                    r#type: "number",
+                                    min: "0",
+                                    placeholder: "Single file",
+                                    value: if current_settings.rows_per_file > 0 { "{current_settings.rows_per_file}" } else { "" },
+                                    oninput: update_rows_per_file,
+                                }
+                                p { class: "text-[11px] text-tertiary select-text",
+                                    if current_settings.rows_per_file > 0 {
+                                        "Split into files of {format_rows(current_settings.rows_per_file)} rows, downloaded as a ZIP"
+                                    } else {
+                                        "Write everything into one file"
+                                    }
+                                }
+                            }
                         }
 
                         div { class: "flex items-start justify-between gap-3",
@@ -640,11 +1350,27 @@ pub fn ParquetRewriterTool() -> Element {
                             input {
                                 r#type: "checkbox",
                                 class: "toggle toggle-sm",
-                                checked: current_settings.page_index_enabled,
+                                checked: current_settings.page_index_enabled && !current_settings.minimal_footer,
+                                disabled: current_settings.minimal_footer,
                                 onchange: toggle_page_index,
                             }
                         }
 
+                        div { class: "flex items-start justify-between gap-3",
+                            div { class: "space-y-0.5",
+                                label { class: "text-xs text-tertiary select-text", "Dictionary encoding" }
+                                p { class: "text-[11px] text-tertiary select-text",
+                                    "On by default. Columns fall back to plain encoding once the dictionary page fills up."
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: current_settings.dictionary_enabled,
+                                onchange: toggle_dictionary,
+                            }
+                        }
+
                         div { class: "flex items-start justify-between gap-3",
                             div { class: "space-y-0.5",
                                 label { class: "text-xs text-tertiary select-text", "Bloom filter" }
@@ -655,32 +1381,184 @@ pub fn ParquetRewriterTool() -> Element {
                             input {
                                 r#type: "checkbox",
                                 class: "toggle toggle-sm",
-                                checked: current_settings.bloom_filter_enabled,
+                                checked: current_settings.bloom_filter_enabled && !current_settings.minimal_footer,
+                                disabled: current_settings.minimal_footer,
                                 onchange: toggle_bloom_filter,
                             }
                         }
 
-                        div { class: "divider-soft" }
+                        div { class: "flex items-start justify-between gap-3",
+                            div { class: "space-y-0.5",
+                                label { class: "text-xs text-tertiary select-text", "Minimal footer" }
+                                p { class: "text-[11px] text-tertiary select-text",
+                                    "Off by default. Drops statistics, page indexes and bloom filters for the smallest footer; readers can no longer skip data."
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: current_settings.minimal_footer,
+                                onchange: toggle_minimal_footer,
+                            }
+                        }
 
-                        div { class: "space-y-2",
-                            div { class: "flex items-start justify-between gap-3",
-                                div { class: "space-y-0.5",
-                                    label { class: "text-xs text-tertiary select-text",
-                                        "Per-column compression"
-                                    }
-                                    p { class: "text-[11px] text-tertiary select-text",
-                                        "Off by default. When on, overrides can be set per column."
-                                    }
+                        div { class: "flex items-start justify-between gap-3",
+                            div { class: "space-y-0.5",
+                                label { class: "text-xs text-tertiary select-text",
+                                    "Flatten nested columns"
                                 }
-                                input {
-                                    r#type: "checkbox",
-                                    class: "toggle toggle-sm",
-                                    checked: current_settings.per_column_compression,
-                                    onchange: toggle_per_column_compression,
+                                p { class: "text-[11px] text-tertiary select-text",
+                                    "Off by default. Expands struct columns into dot-named leaf columns, e.g. `a.b`."
                                 }
                             }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: current_settings.flatten_nested,
+                                onchange: toggle_flatten_nested,
+                            }
+                        }
 
-                            if current_settings.per_column_compression {
+                        div { class: "divider-soft" }
+
+                        div { class: "space-y-2",
+                            div { class: "space-y-0.5",
+                                label { class: "text-xs text-tertiary select-text",
+                                    "Columns ({kept_column_count} of {column_names.len()} kept)"
+                                }
+                                p { class: "text-[11px] text-tertiary select-text",
+                                    "Uncheck columns to leave them out of the output. Drag to reorder, type to rename."
+                                }
+                            }
+                            if column_names.is_empty() {
+                                div { class: "text-[11px] text-tertiary select-text",
+                                    "Add at least one file to choose columns."
+                                }
+                            } else {
+                                div { class: "space-y-1 max-h-56 overflow-auto pr-1",
+                                    for column_name in ordered_column_names.iter().cloned() {
+                                        div {
+                                            key: "{column_name}",
+                                            class: if dragged_column().as_deref() == Some(column_name.as_str()) { "flex items-center gap-2 text-xs text-primary select-text opacity-50" } else { "flex items-center gap-2 text-xs text-primary select-text" },
+                                            draggable: "true",
+                                            ondragstart: {
+                                                let column_for_drag = column_name.clone();
+                                                move |_| dragged_column.set(Some(column_for_drag.clone()))
+                                            },
+                                            ondragover: move |ev: Event<DragData>| ev.prevent_default(),
+                                            ondrop: {
+                                                let target = column_name.clone();
+                                                let ordered = ordered_column_names.clone();
+                                                move |ev: Event<DragData>| {
+                                                    ev.prevent_default();
+                                                    let dragged = dragged_column();
+                                                    dragged_column.set(None);
+                                                    if let Some(column) = dragged {
+                                                        let order = move_column(ordered.clone(), &column, &target);
+                                                        settings.with_mut(|current| current.column_order = order);
+                                                    }
+                                                }
+                                            },
+                                            ondragend: move |_| dragged_column.set(None),
+                                            span {
+                                                class: "cursor-grab opacity-50 select-none",
+                                                title: "Drag to reorder",
+                                                "⠿"
+                                            }
+                                            input {
+                                                r#type: "checkbox",
+                                                class: "checkbox checkbox-xs",
+                                                checked: !current_settings.dropped_columns.contains(&column_name),
+                                                // The last kept column cannot be dropped.
+                                                disabled: kept_column_count == 1
+                                                    && !current_settings.dropped_columns.contains(&column_name),
+                                                onchange: {
+                                                    let column_for_update = column_name.clone();
+                                                    move |ev: Event<FormData>| {
+                                                        let keep = ev.checked();
+                                                        settings
+                                                            .with_mut(|current| {
+                                                                if keep {
+                                                                    current.dropped_columns.remove(&column_for_update);
+                                                                } else {
+                                                                    current.dropped_columns.insert(column_for_update.clone());
+                                                                }
+                                                            });
+                                                    }
+                                                },
+                                            }
+                                            span { class: "truncate flex-1", "{column_name}" }
+                                            input {
+                                                r#type: "text",
+                                                class: "input input-bordered input-xs w-32 select-text",
+                                                placeholder: "Rename",
+                                                value: current_settings.column_renames.get(&column_name).cloned().unwrap_or_default(),
+                                                oninput: {
+                                                    let column_for_update = column_name.clone();
+                                                    move |ev: Event<FormData>| {
+                                                        let value = ev.value();
+                                                        settings
+                                                            .with_mut(|current| {
+                                                                if value.trim().is_empty() {
+                                                                    current.column_renames.remove(&column_for_update);
+                                                                } else {
+                                                                    current.column_renames.insert(column_for_update.clone(), value);
+                                                                }
+                                                            });
+                                                    }
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                                if let Some(error) = rename_error.as_ref() {
+                                    p { class: "text-[11px] text-red-600 dark:text-red-400 break-all select-text",
+                                        "{error}"
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "space-y-1",
+                            label { class: "text-xs text-tertiary select-text", "Row filter" }
+                            p { class: "text-[11px] text-tertiary select-text",
+                                "Optional SQL predicate; only matching rows are written, e.g. `year >= 2020 AND country = 'US'`."
+                            }
+                            input {
+                                r#type: "text",
+                                class: "input input-bordered input-sm w-full font-mono select-text",
+                                placeholder: "Keep all rows",
+                                value: "{current_settings.row_filter}",
+                                oninput: update_row_filter,
+                            }
+                            if let Some(error) = row_filter_error.as_ref() {
+                                p { class: "text-[11px] text-red-600 dark:text-red-400 break-all select-text",
+                                    "{error}"
+                                }
+                            }
+                        }
+
+                        div { class: "divider-soft" }
+
+                        div { class: "space-y-2",
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "text-xs text-tertiary select-text",
+                                        "Per-column compression"
+                                    }
+                                    p { class: "text-[11px] text-tertiary select-text",
+                                        "Off by default. When on, overrides can be set per column."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: current_settings.per_column_compression,
+                                    onchange: toggle_per_column_compression,
+                                }
+                            }
+
+                            if current_settings.per_column_compression {
                                 if column_names.is_empty() {
                                     div { class: "text-[11px] text-tertiary select-text",
                                         "Add at least one file to configure per-column compression."
@@ -726,6 +1604,225 @@ pub fn ParquetRewriterTool() -> Element {
                                 }
                             }
                         }
+
+                        div { class: "divider-soft" }
+
+                        div { class: "space-y-2",
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "text-xs text-tertiary select-text",
+                                        "Per-column dictionary"
+                                    }
+                                    p { class: "text-[11px] text-tertiary select-text",
+                                        "Off by default. When on, dictionary encoding can be turned on or off per column."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: current_settings.per_column_dictionary,
+                                    onchange: toggle_per_column_dictionary,
+                                }
+                            }
+
+                            if current_settings.per_column_dictionary {
+                                if column_names.is_empty() {
+                                    div { class: "text-[11px] text-tertiary select-text",
+                                        "Add at least one file to configure per-column dictionary encoding."
+                                    }
+                                } else {
+                                    div { class: "space-y-2 max-h-56 overflow-auto pr-1",
+                                        for (column_name , override_value) in column_dictionary_rows {
+                                            div {
+                                                key: "{column_name}",
+                                                class: "flex items-center justify-between gap-3",
+                                                span { class: "text-xs text-primary truncate select-text",
+                                                    "{column_name}"
+                                                }
+                                                select {
+                                                    class: "select select-bordered select-xs w-40 select-text",
+                                                    value: "{override_value}",
+                                                    onchange: {
+                                                        let column_for_update = column_name.clone();
+                                                        move |ev| {
+                                                            let value = ev.value();
+                                                            settings
+                                                                .with_mut(|current| {
+                                                                    match value.as_str() {
+                                                                        "on" => {
+                                                                            current.column_dictionaries.insert(column_for_update.clone(), true);
+                                                                        }
+                                                                        "off" => {
+                                                                            current.column_dictionaries.insert(column_for_update.clone(), false);
+                                                                        }
+                                                                        _ => {
+                                                                            current.column_dictionaries.remove(&column_for_update);
+                                                                        }
+                                                                    }
+                                                                });
+                                                        }
+                                                    },
+                                                    option { value: "default", "Use default" }
+                                                    option { value: "on", "Dictionary" }
+                                                    option { value: "off", "No dictionary" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "divider-soft" }
+
+                        div { class: "space-y-2",
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "text-xs text-tertiary select-text",
+                                        "Cast column types"
+                                    }
+                                    p { class: "text-[11px] text-tertiary select-text",
+                                        "Off by default. When on, top-level columns can be cast to a different type, e.g. to downcast oversized types."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: current_settings.cast_columns,
+                                    onchange: toggle_cast_columns,
+                                }
+                            }
+
+                            if current_settings.cast_columns {
+                                if column_type_rows.is_empty() {
+                                    div { class: "text-[11px] text-tertiary select-text",
+                                        "Add at least one file to configure column types."
+                                    }
+                                } else {
+                                    div { class: "space-y-2 max-h-56 overflow-auto pr-1",
+                                        for (column_name , current_type , override_value) in column_type_rows {
+                                            div {
+                                                key: "{column_name}",
+                                                class: "flex items-center justify-between gap-3",
+                                                div { class: "min-w-0",
+                                                    p { class: "text-xs text-primary truncate select-text",
+                                                        "{column_name}"
+                                                    }
+                                                    p { class: "text-[11px] text-tertiary font-mono truncate select-text",
+                                                        "{current_type}"
+                                                    }
+                                                }
+                                                select {
+                                                    class: "select select-bordered select-xs w-40 select-text",
+                                                    value: "{override_value}",
+                                                    onchange: {
+                                                        let column_for_update = column_name.clone();
+                                                        move |ev| {
+                                                            let value = ev.value();
+                                                            settings
+                                                                .with_mut(|current| {
+                                                                    if value == "keep" {
+                                                                        current.column_types.remove(&column_for_update);
+                                                                    } else if let Some(choice) = TypeChoice::from_value(&value) {
+                                                                        current.column_types.insert(column_for_update.clone(), choice);
+                                                                    }
+                                                                });
+                                                        }
+                                                    },
+                                                    option { value: "keep", "Keep type" }
+                                                    for option in TypeChoice::all() {
+                                                        option { value: "{option.value()}",
+                                                            "{option.label()}"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "divider-soft" }
+
+                        div { class: "space-y-2",
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "text-xs text-tertiary select-text",
+                                        "Edit key-value metadata"
+                                    }
+                                    p { class: "text-[11px] text-tertiary select-text",
+                                        "Off by default. When on, the file-level key-value metadata is replaced with the entries below, starting from the first file's."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: current_settings.edit_key_value_metadata,
+                                    onchange: toggle_edit_key_value_metadata,
+                                }
+                            }
+
+                            if current_settings.edit_key_value_metadata {
+                                div { class: "space-y-2 max-h-56 overflow-auto pr-1",
+                                    for (index , (key , value)) in current_settings.key_value_metadata.iter().cloned().enumerate() {
+                                        div { class: "flex items-center gap-2",
+                                            input {
+                                                r#type: "text",
+                                                class: "input input-bordered input-xs w-40 font-mono",
+                                                placeholder: "key",
+                                                value: "{key}",
+                                                oninput: move |ev| {
+                                                    settings
+                                                        .with_mut(|current| {
+                                                            if let Some(entry) = current.key_value_metadata.get_mut(index) {
+                                                                entry.0 = ev.value();
+                                                            }
+                                                        });
+                                                },
+                                            }
+                                            input {
+                                                r#type: "text",
+                                                class: "input input-bordered input-xs flex-1 min-w-0 font-mono",
+                                                placeholder: "value",
+                                                value: "{value}",
+                                                oninput: move |ev| {
+                                                    settings
+                                                        .with_mut(|current| {
+                                                            if let Some(entry) = current.key_value_metadata.get_mut(index) {
+                                                                entry.1 = ev.value();
+                                                            }
+                                                        });
+                                                },
+                                            }
+                                            button {
+                                                class: "btn btn-ghost btn-xs",
+                                                title: "Remove entry",
+                                                onclick: move |_| {
+                                                    settings
+                                                        .with_mut(|current| {
+                                                            if index < current.key_value_metadata.len() {
+                                                                current.key_value_metadata.remove(index);
+                                                            }
+                                                        });
+                                                },
+                                                "✕"
+                                            }
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-ghost btn-xs",
+                                    onclick: move |_| {
+                                        settings
+                                            .with_mut(|current| {
+                                                current.key_value_metadata.push((String::new(), String::new()))
+                                            });
+                                    },
+                                    "+ Add entry"
+                                }
+                            }
+                        }
                     }
 
                     div { class: "divider-soft" }
@@ -749,6 +1846,47 @@ pub fn ParquetRewriterTool() -> Element {
                                 }
                             }
                         }
+                        if let Some(summary) = current_state.summary.as_ref() {
+                            div { class: "space-y-1 pt-1",
+                                div { class: "flex items-center justify-between text-xs",
+                                    span { class: "text-tertiary select-text", "Last rewrite" }
+                                    span { class: "text-primary select-text",
+                                        "{format_bytes_short(summary.input_bytes)} → {format_bytes_short(summary.output_bytes)} ({format_size_change(summary.input_bytes, summary.output_bytes)})"
+                                    }
+                                }
+                                div { class: "max-h-56 overflow-auto",
+                                    table { class: "w-full text-[11px] select-text",
+                                        thead {
+                                            tr { class: "text-tertiary",
+                                                th { class: "text-left font-normal", "Column" }
+                                                th { class: "text-right font-normal", "Before" }
+                                                th { class: "text-right font-normal", "After" }
+                                                th { class: "text-right font-normal", "Change" }
+                                            }
+                                        }
+                                        tbody {
+                                            for column in summary.columns.iter() {
+                                                tr { key: "{column.name}", class: "text-primary",
+                                                    td { class: "truncate max-w-32",
+                                                        if let Some(renamed) = column.renamed.as_ref() {
+                                                            "{column.name} → {renamed}"
+                                                        } else {
+                                                            "{column.name}"
+                                                        }
+                                                    }
+                                                    td { class: "text-right font-mono", "{format_bytes_short(column.input_bytes)}" }
+                                                    td { class: "text-right font-mono", "{format_bytes_short(column.output_bytes)}" }
+                                                    td {
+                                                        class: if column.output_bytes <= column.input_bytes { "text-right font-mono text-green-600 dark:text-green-400" } else { "text-right font-mono text-red-600 dark:text-red-400" },
+                                                        "{format_size_change(column.input_bytes, column.output_bytes)}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
 
                     button {
@@ -788,14 +1926,86 @@ pub fn ParquetRewriterTool() -> Element {
     }
 }
 
-fn format_rows(count: usize) -> String {
-    let mut result = count.to_string();
-    let mut i = result.len();
-    while i > 3 {
-        i -= 3;
-        result.insert(i, ',');
+/// Compressed bytes of every top-level column in a Parquet file, keyed by its Arrow name.
+fn compressed_column_sizes(data: Bytes) -> anyhow::Result<HashMap<String, u64>> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(data)?;
+    let mut sizes = HashMap::new();
+    for row_group in builder.metadata().row_groups() {
+        for column in row_group.columns() {
+            let Some(top_level) = column.column_path().parts().first() else {
+                continue;
+            };
+            *sizes.entry(top_level.clone()).or_default() += column.compressed_size() as u64;
+        }
+    }
+    Ok(sizes)
+}
+
+/// Compares the inputs of a rewrite with the files it wrote, column by column. Flattened
+/// leaves count towards the struct column they came from.
+fn rewrite_summary(
+    files: &[ParquetFileInfo],
+    parts: &[Vec<u8>],
+    settings: &RewriteSettings,
+) -> anyhow::Result<RewriteSummary> {
+    let mut input_sizes = HashMap::<String, u64>::new();
+    for file in files {
+        for (name, size) in compressed_column_sizes(file.data.clone())? {
+            *input_sizes.entry(name).or_default() += size;
+        }
+    }
+    let mut output_sizes = HashMap::<String, u64>::new();
+    for part in parts {
+        for (name, size) in compressed_column_sizes(Bytes::from(part.clone()))? {
+            *output_sizes.entry(name).or_default() += size;
+        }
     }
-    result
+
+    let input_schema = union_schemas(files)?;
+    let columns = output_column_indices(&input_schema, settings)
+        .into_iter()
+        .map(|index| {
+            let name = input_schema.field(index).name().clone();
+            let renamed = settings
+                .column_renames
+                .get(&name)
+                .map(|renamed| renamed.trim().to_string())
+                .filter(|renamed| !renamed.is_empty() && *renamed != name);
+            let output_name = renamed.clone().unwrap_or_else(|| name.clone());
+            let output_bytes = output_sizes
+                .iter()
+                .filter(|(column, _)| {
+                    **column == output_name
+                        || (settings.flatten_nested
+                            && column.starts_with(&format!("{output_name}.")))
+                })
+                .map(|(_, size)| size)
+                .sum();
+            ColumnSizeChange {
+                input_bytes: input_sizes.get(&name).copied().unwrap_or(0),
+                name,
+                renamed,
+                output_bytes,
+            }
+        })
+        .collect();
+
+    Ok(RewriteSummary {
+        input_bytes: files.iter().map(|file| file.data.len() as u64).sum(),
+        output_bytes: parts.iter().map(|part| part.len() as u64).sum(),
+        columns,
+    })
+}
+
+fn format_size_change(before: u64, after: u64) -> String {
+    match size_change_pct(before, after) {
+        Some(pct) => format!("{pct:+.1}%"),
+        None => "-".to_string(),
+    }
+}
+
+fn format_rows(count: usize) -> String {
+    crate::utils::format_rows(count as u64)
 }
 
 fn format_bytes_short(bytes: u64) -> String {
@@ -838,6 +2048,23 @@ async fn read_parquet_file_info(file: web_sys::File) -> anyhow::Result<ParquetFi
     let uint8_array = js_sys::Uint8Array::new(&array_buffer);
     let data = Bytes::from(uint8_array.to_vec());
 
+    // CSV and JSON sources are converted with inferred types first; the type overrides and
+    // writer settings then apply to them like to any Parquet input.
+    let data = if is_csv_file_name(&name) {
+        Bytes::from(csv_to_parquet(data, CsvOptions::for_file_name(&name)).await?)
+    } else if is_json_file_name(&name) {
+        Bytes::from(json_to_parquet(data).await?)
+    } else {
+        data
+    };
+    if !has_parquet_magic(&data, &data) {
+        return Err(NotParquetError {
+            file_name: name,
+            detected_format: guess_file_format(&data),
+        }
+        .into());
+    }
+
     let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone())?;
     let metadata = builder.metadata();
 
@@ -848,6 +2075,27 @@ async fn read_parquet_file_info(file: web_sys::File) -> anyhow::Result<ParquetFi
         .map(|rg| rg.num_rows() as usize)
         .sum();
 
+    let uncompressed_bytes = metadata
+        .row_groups()
+        .iter()
+        .map(|rg| rg.total_byte_size() as u64)
+        .sum();
+    let max_row_group_rows = metadata
+        .row_groups()
+        .iter()
+        .map(|rg| rg.num_rows() as usize)
+        .max()
+        .unwrap_or(0);
+
+    let key_value_metadata = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .into_iter()
+        .flatten()
+        .filter(|kv| kv.key != ARROW_SCHEMA_META_KEY)
+        .map(|kv| (kv.key.clone(), kv.value.clone().unwrap_or_default()))
+        .collect();
+
     let compression = metadata
         .row_groups()
         .first()
@@ -862,29 +2110,74 @@ async fn read_parquet_file_info(file: web_sys::File) -> anyhow::Result<ParquetFi
         row_count,
         compression,
         size_bytes,
+        uncompressed_bytes,
+        max_row_group_rows,
+        key_value_metadata,
     })
 }
 
+/// Rewrites `files` into one output file, or into several when `rows_per_file` is set.
 async fn rewrite_parquet_files(
     files: &[ParquetFileInfo],
     settings: &RewriteSettings,
-) -> anyhow::Result<Vec<u8>> {
+) -> anyhow::Result<Vec<Vec<u8>>> {
     if files.is_empty() {
         return Err(anyhow::anyhow!("No files to rewrite"));
     }
 
-    let schema = files[0].schema.clone();
+    let input_schema = union_schemas(files)?;
+    let kept_columns = output_column_indices(&input_schema, settings);
+    if kept_columns.is_empty() {
+        return Err(anyhow::anyhow!("Keep at least one column"));
+    }
+    let projected = Arc::new(input_schema.project(&kept_columns)?);
+    let cast = if settings.cast_columns {
+        cast_schema(&projected, &settings.column_types)?
+    } else {
+        projected.clone()
+    };
+    let schema = rename_schema(&cast, &settings.column_renames)?;
+    let output_schema = if settings.flatten_nested {
+        flatten_schema(&schema)
+    } else {
+        schema.clone()
+    };
+    // Readers merge the embedded Arrow schema's metadata back into the key-value metadata,
+    // so it has to match for removed entries to stay removed.
+    let key_value_metadata = settings.edit_key_value_metadata.then(|| {
+        settings
+            .key_value_metadata
+            .iter()
+            .filter(|(key, _)| !key.trim().is_empty())
+            .map(|(key, value)| (key.trim().to_string(), value.clone()))
+            .collect::<Vec<_>>()
+    });
+    let output_schema = match &key_value_metadata {
+        Some(entries) => Arc::new(
+            output_schema
+                .as_ref()
+                .clone()
+                .with_metadata(entries.iter().cloned().collect()),
+        ),
+        None => output_schema,
+    };
 
-    let mut buf = Vec::new();
     let mut builder = WriterProperties::builder()
         .set_compression(settings.compression.to_parquet())
         .set_data_page_size_limit(settings.data_page_size)
         .set_dictionary_page_size_limit(settings.dictionary_page_size)
-        .set_max_row_group_size(settings.row_group_size);
+        .set_max_row_group_size(settings.row_group_size)
+        .set_writer_version(settings.writer_version)
+        .set_dictionary_enabled(settings.dictionary_enabled);
 
-    builder = builder.set_bloom_filter_enabled(settings.bloom_filter_enabled);
+    builder =
+        builder.set_bloom_filter_enabled(settings.bloom_filter_enabled && !settings.minimal_footer);
 
-    if settings.page_index_enabled {
+    if settings.minimal_footer {
+        builder = builder
+            .set_statistics_enabled(EnabledStatistics::None)
+            .set_offset_index_disabled(true);
+    } else if settings.page_index_enabled {
         builder = builder
             .set_statistics_enabled(EnabledStatistics::Page)
             .set_offset_index_disabled(false);
@@ -895,44 +2188,877 @@ async fn rewrite_parquet_files(
     }
 
     if settings.per_column_compression {
-        for (column, compression) in settings.column_compressions.iter() {
+        for (column, compression) in settings
+            .column_compressions
+            .iter()
+            .filter(|(column, _)| !settings.dropped_columns.contains(*column))
+        {
+            let name = settings.column_renames.get(column).unwrap_or(column);
             builder = builder.set_column_compression(
-                ColumnPath::from(column.as_str()),
+                ColumnPath::from(name.as_str()),
                 compression.to_parquet(),
             );
         }
     }
 
+    if settings.per_column_dictionary {
+        for (column, enabled) in settings
+            .column_dictionaries
+            .iter()
+            .filter(|(column, _)| !settings.dropped_columns.contains(*column))
+        {
+            let name = settings.column_renames.get(column).unwrap_or(column);
+            builder =
+                builder.set_column_dictionary_enabled(ColumnPath::from(name.as_str()), *enabled);
+        }
+    }
+
+    if let Some(entries) = key_value_metadata.filter(|entries| !entries.is_empty()) {
+        builder = builder.set_key_value_metadata(Some(
+            entries
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect(),
+        ));
+    }
+
     let props = builder.build();
-    let mut writer = ArrowWriter::try_new(&mut buf, schema, Some(props))?;
+    let mut parts = PartWriter::new(output_schema.clone(), props, settings.rows_per_file);
+
+    let row_filter = match settings.row_filter.trim() {
+        "" => None,
+        predicate => Some((filter_context(), predicate)),
+    };
 
     for file in files {
         let builder = ParquetRecordBatchReaderBuilder::try_new(file.data.clone())?;
-        let reader = builder.build()?;
+        let file_columns = projected
+            .fields()
+            .iter()
+            .filter_map(|field| file.schema.index_of(field.name()).ok())
+            .collect::<Vec<_>>();
+        let projection = ProjectionMask::roots(builder.parquet_schema(), file_columns);
+        let reader = builder.with_projection(projection).build()?;
 
         for batch_result in reader {
-            let batch = batch_result?;
-            writer.write(&batch)?;
+            let batch = align_batch(batch_result?, &projected)?;
+            let batches = match &row_filter {
+                Some((ctx, predicate)) => filter_batch(ctx, batch, predicate).await?,
+                None => vec![batch],
+            };
+            for batch in batches {
+                let batch = cast_batch(batch, &cast)?;
+                let batch = RecordBatch::try_new(schema.clone(), batch.columns().to_vec())?;
+                let batch = if settings.flatten_nested {
+                    flatten_batch(batch, &output_schema)?
+                } else {
+                    batch
+                };
+                parts.write(&batch)?;
+            }
+        }
+    }
+
+    parts.finish()
+}
+
+/// Writes batches into consecutive parquet files of at most `rows_per_file` rows each.
+struct PartWriter {
+    schema: SchemaRef,
+    props: WriterProperties,
+    /// 0 keeps everything in a single file.
+    rows_per_file: usize,
+    current: Option<(ArrowWriter<Vec<u8>>, usize)>,
+    finished: Vec<Vec<u8>>,
+}
+
+impl PartWriter {
+    fn new(schema: SchemaRef, props: WriterProperties, rows_per_file: usize) -> Self {
+        Self {
+            schema,
+            props,
+            rows_per_file,
+            current: None,
+            finished: Vec::new(),
+        }
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<()> {
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let (writer, rows) = match &mut self.current {
+                Some(current) => current,
+                None => self.current.insert((
+                    ArrowWriter::try_new(
+                        Vec::new(),
+                        self.schema.clone(),
+                        Some(self.props.clone()),
+                    )?,
+                    0,
+                )),
+            };
+            let remaining = batch.num_rows() - offset;
+            let take = match self.rows_per_file {
+                0 => remaining,
+                limit => remaining.min(limit - *rows),
+            };
+            writer.write(&batch.slice(offset, take))?;
+            *rows += take;
+            offset += take;
+
+            if self.rows_per_file > 0 && *rows >= self.rows_per_file {
+                self.close_current()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn close_current(&mut self) -> anyhow::Result<()> {
+        if let Some((writer, _)) = self.current.take() {
+            self.finished.push(writer.into_inner()?);
+        }
+        Ok(())
+    }
+
+    /// Closes the last part. Input without rows still produces one empty file.
+    fn finish(mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        self.close_current()?;
+        if self.finished.is_empty() {
+            let writer = ArrowWriter::try_new(Vec::new(), self.schema, Some(self.props))?;
+            self.finished.push(writer.into_inner()?);
         }
+        Ok(self.finished)
+    }
+}
+
+/// Bundles the split output into a ZIP. Parts are stored as-is since parquet is already compressed.
+fn zip_parts(parts: Vec<Vec<u8>>) -> anyhow::Result<Vec<u8>> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+    for (i, part) in parts.iter().enumerate() {
+        archive.start_file(format!("rewritten-{:05}.parquet", i + 1), options)?;
+        archive.write_all(part)?;
+    }
+    Ok(archive.finish()?.into_inner())
+}
+
+/// A throwaway single-partition context for evaluating the row filter, kept apart from the
+/// shared session so nothing gets registered there.
+fn filter_context() -> SessionContext {
+    SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1))
+}
+
+/// Checks that the row filter parses and only references kept columns, before any work starts.
+fn validate_row_filter(schema: &SchemaRef, settings: &RewriteSettings) -> anyhow::Result<()> {
+    let predicate = settings.row_filter.trim();
+    if predicate.is_empty() {
+        return Ok(());
     }
+    let kept_columns = kept_column_indices(schema, &settings.dropped_columns);
+    let projected = Arc::new(schema.project(&kept_columns)?);
+    let df = filter_context().read_batch(RecordBatch::new_empty(projected))?;
+    let expr = df.parse_sql_expr(predicate)?;
+    df.filter(expr)?;
+    Ok(())
+}
+
+/// Keeps the rows of `batch` matching the SQL `predicate`.
+async fn filter_batch(
+    ctx: &SessionContext,
+    batch: RecordBatch,
+    predicate: &str,
+) -> anyhow::Result<Vec<RecordBatch>> {
+    let df = ctx.read_batch(batch)?;
+    let expr = df.parse_sql_expr(predicate)?;
+    Ok(df.filter(expr)?.collect().await?)
+}
+
+/// Indices of the top-level fields of `schema` that are not in `dropped`.
+fn kept_column_indices(schema: &Schema, dropped: &HashSet<String>) -> Vec<usize> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !dropped.contains(field.name()))
+        .map(|(i, _)| i)
+        .collect()
+}
 
-    writer.close()?;
+/// `names` in the order of `order`, followed by the names it does not list in their own order.
+fn ordered_columns(names: &[String], order: &[String]) -> Vec<String> {
+    let mut ordered = order
+        .iter()
+        .filter(|name| names.contains(name))
+        .cloned()
+        .collect::<Vec<_>>();
+    ordered.extend(names.iter().filter(|name| !order.contains(name)).cloned());
+    ordered
+}
 
-    Ok(buf)
+/// Moves `column` to the position `target` holds in `order`.
+fn move_column(mut order: Vec<String>, column: &str, target: &str) -> Vec<String> {
+    let (Some(from), Some(to)) = (
+        order.iter().position(|name| name == column),
+        order.iter().position(|name| name == target),
+    ) else {
+        return order;
+    };
+    let moved = order.remove(from);
+    order.insert(to, moved);
+    order
 }
 
-fn download_data(file_name: &str, data: Vec<u8>) {
-    let blob =
-        web_sys::Blob::new_with_u8_array_sequence(&js_sys::Array::of1(&data.into())).unwrap();
-    let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
-    let a = web_sys::window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .create_element("a")
+/// Indices of the kept top-level fields of `schema`, in output order.
+fn output_column_indices(schema: &Schema, settings: &RewriteSettings) -> Vec<usize> {
+    let names = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().to_string())
+        .collect::<Vec<_>>();
+    ordered_columns(&names, &settings.column_order)
+        .iter()
+        .filter(|name| !settings.dropped_columns.contains(*name))
+        .filter_map(|name| schema.index_of(name).ok())
+        .collect()
+}
+
+/// Renames top-level fields, keeping their types. Blank renames are ignored; names that end up
+/// duplicated are rejected.
+fn rename_schema(
+    schema: &SchemaRef,
+    renames: &HashMap<String, String>,
+) -> anyhow::Result<SchemaRef> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| match renames.get(field.name()).map(|name| name.trim()) {
+            Some(name) if !name.is_empty() && name != field.name() => {
+                Arc::new(field.as_ref().clone().with_name(name))
+            }
+            _ => field.clone(),
+        })
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = fields.iter().find(|field| !seen.insert(field.name())) {
+        return Err(anyhow::anyhow!(
+            "More than one column would be named '{}'",
+            duplicate.name()
+        ));
+    }
+    Ok(Arc::new(Schema::new_with_metadata(
+        fields,
+        schema.metadata().clone(),
+    )))
+}
+
+/// Applies the per-column type overrides to the schema, rejecting unsupported casts upfront.
+fn cast_schema(
+    schema: &SchemaRef,
+    column_types: &HashMap<String, TypeChoice>,
+) -> anyhow::Result<SchemaRef> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let Some(choice) = column_types.get(field.name()) else {
+                return Ok(field.clone());
+            };
+            let target = choice.to_arrow(field.data_type());
+            if !can_cast_types(field.data_type(), &target) {
+                return Err(anyhow::anyhow!(
+                    "Cannot cast column '{}' from {} to {}",
+                    field.name(),
+                    field.data_type(),
+                    target
+                ));
+            }
+            Ok(Arc::new(field.as_ref().clone().with_data_type(target)))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(Arc::new(Schema::new_with_metadata(
+        fields,
+        schema.metadata().clone(),
+    )))
+}
+
+/// Casts the batch to the output schema. Casts are not lossy: values that do not fit
+/// the target type (e.g. overflow) fail the rewrite instead of becoming null.
+fn cast_batch(batch: RecordBatch, schema: &SchemaRef) -> anyhow::Result<RecordBatch> {
+    if batch.schema().fields() == schema.fields() {
+        return Ok(batch);
+    }
+
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(column, field)| {
+            if column.data_type() == field.data_type() {
+                return Ok(column.clone());
+            }
+            cast_with_options(column, field.data_type(), &options).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to cast column '{}' from {} to {}: {}",
+                    field.name(),
+                    column.data_type(),
+                    field.data_type(),
+                    e
+                )
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+/// Expands struct fields into dot-named leaf fields (`a.b.c`). A leaf whose name collides
+/// with an existing column is prefixed with its parent field name again until unique.
+fn flatten_schema(schema: &SchemaRef) -> SchemaRef {
+    let mut taken: HashSet<String> = schema
+        .fields()
+        .iter()
+        .filter(|field| !matches!(field.data_type(), DataType::Struct(_)))
+        .map(|field| field.name().to_string())
+        .collect();
+    let mut fields = Vec::new();
+    for field in schema.fields() {
+        flatten_field(field, None, false, &mut taken, &mut fields);
+    }
+    Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+fn flatten_field(
+    field: &FieldRef,
+    parent: Option<&str>,
+    parent_nullable: bool,
+    taken: &mut HashSet<String>,
+    out: &mut Vec<FieldRef>,
+) {
+    let name = match parent {
+        Some(parent) => {
+            let mut name = format!("{parent}.{}", field.name());
+            while taken.contains(&name) {
+                name = format!("{parent}.{name}");
+            }
+            name
+        }
+        None => field.name().to_string(),
+    };
+    // A null parent struct makes all its leaves null.
+    let nullable = field.is_nullable() || parent_nullable;
+
+    match field.data_type() {
+        DataType::Struct(children) => {
+            for child in children {
+                flatten_field(child, Some(&name), nullable, taken, out);
+            }
+        }
+        _ => {
+            taken.insert(name.clone());
+            out.push(Arc::new(
+                field
+                    .as_ref()
+                    .clone()
+                    .with_name(name)
+                    .with_nullable(nullable),
+            ));
+        }
+    }
+}
+
+/// Flattens the batch to match `flatten_schema`, propagating struct nulls into the leaves.
+fn flatten_batch(batch: RecordBatch, schema: &SchemaRef) -> anyhow::Result<RecordBatch> {
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for column in batch.columns() {
+        flatten_array(column.clone(), None, &mut columns)?;
+    }
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+fn flatten_array(
+    array: ArrayRef,
+    parent_nulls: Option<&NullBuffer>,
+    out: &mut Vec<ArrayRef>,
+) -> anyhow::Result<()> {
+    let array = match parent_nulls {
+        Some(parent_nulls) => {
+            let nulls = NullBuffer::union(Some(parent_nulls), array.nulls());
+            make_array(array.to_data().into_builder().nulls(nulls).build()?)
+        }
+        None => array,
+    };
+
+    match array.data_type() {
+        DataType::Struct(_) => {
+            let struct_array = array.as_struct();
+            for child in struct_array.columns() {
+                flatten_array(child.clone(), struct_array.nulls(), out)?;
+            }
+        }
+        _ => out.push(array),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_differences() {
+        let expected = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+        assert!(schema_differences(&expected, &expected).is_empty());
+
+        let actual = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("extra", DataType::Boolean, true),
+        ]);
+        assert_eq!(
+            schema_differences(&expected, &actual),
+            vec![
+                "`id`: type Int32 instead of Int64",
+                "`id`: nullable instead of required",
+                "Missing field `score`",
+                "Extra field `extra` (Boolean)",
+            ]
+        );
+
+        let reordered = Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("id", DataType::Int64, false),
+            Field::new("score", DataType::Float64, true),
+        ]);
+        assert_eq!(
+            schema_differences(&expected, &reordered),
+            vec![
+                "`id`: at position 1 instead of 0",
+                "`name`: at position 0 instead of 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_drops_columns() {
+        use arrow::array::{Int64Array, StringArray};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("blob", DataType::Utf8, true),
+            Field::new("note", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+        )
+        .unwrap();
+        let mut data = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut data, schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let file = ParquetFileInfo {
+            name: "input.parquet".to_string(),
+            schema,
+            data: Bytes::from(data),
+            row_count: 2,
+            compression: Compression::UNCOMPRESSED,
+            size_bytes: 0,
+            uncompressed_bytes: 0,
+            max_row_group_rows: 2,
+            key_value_metadata: Vec::new(),
+        };
+
+        let settings = RewriteSettings {
+            dropped_columns: HashSet::from(["blob".to_string(), "note".to_string()]),
+            per_column_compression: true,
+            column_compressions: HashMap::from([
+                ("id".to_string(), CompressionChoice::Snappy),
+                ("blob".to_string(), CompressionChoice::Zstd),
+            ]),
+            ..RewriteSettings::default()
+        };
+        let mut output = futures::executor::block_on(rewrite_parquet_files(
+            std::slice::from_ref(&file),
+            &settings,
+        ))
+        .unwrap();
+        assert_eq!(output.len(), 1);
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output.remove(0))).unwrap();
+        let metadata = reader.metadata().clone();
+        let batches = reader
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches[0].schema().fields().len(), 1);
+        assert_eq!(batches[0].schema().field(0).name(), "id");
+        assert_eq!(batches[0].num_rows(), 2);
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::SNAPPY
+        );
+
+        let drop_all = RewriteSettings {
+            dropped_columns: HashSet::from(["id", "blob", "note"].map(String::from)),
+            ..RewriteSettings::default()
+        };
+        assert!(futures::executor::block_on(rewrite_parquet_files(&[file], &drop_all)).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_reorders_and_renames_columns() {
+        use arrow::array::{Int64Array, StringArray};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("note", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+        )
+        .unwrap();
+        let mut data = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut data, schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let file = ParquetFileInfo {
+            name: "input.parquet".to_string(),
+            schema,
+            data: Bytes::from(data),
+            row_count: 2,
+            compression: Compression::UNCOMPRESSED,
+            size_bytes: 0,
+            uncompressed_bytes: 0,
+            max_row_group_rows: 2,
+            key_value_metadata: Vec::new(),
+        };
+
+        let names = ["id", "name", "note"].map(String::from);
+        let order = move_column(names.to_vec(), "note", "id");
+        assert_eq!(order, ["note", "id", "name"]);
+        assert_eq!(ordered_columns(&names, &["name".to_string()]), ["name", "id", "note"]);
+
+        let settings = RewriteSettings {
+            column_order: order,
+            column_renames: HashMap::from([("name".to_string(), "label".to_string())]),
+            per_column_compression: true,
+            column_compressions: HashMap::from([("name".to_string(), CompressionChoice::Snappy)]),
+            row_filter: "name = 'b'".to_string(),
+            ..RewriteSettings::default()
+        };
+        let mut output = futures::executor::block_on(rewrite_parquet_files(
+            std::slice::from_ref(&file),
+            &settings,
+        ))
+        .unwrap();
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output.remove(0))).unwrap();
+        let metadata = reader.metadata().clone();
+        let batches = reader
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let output_schema = batches[0].schema();
+        let output_names = output_schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(output_names, ["note", "id", "label"]);
+        assert_eq!(output_schema.field(2).data_type(), &DataType::Utf8);
+        assert_eq!(batches[0].num_rows(), 1);
+        assert_eq!(
+            metadata.row_group(0).column(2).compression(),
+            Compression::SNAPPY
+        );
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::LZ4_RAW
+        );
+
+        let clash = RewriteSettings {
+            column_renames: HashMap::from([("note".to_string(), "id".to_string())]),
+            ..RewriteSettings::default()
+        };
+        assert!(futures::executor::block_on(rewrite_parquet_files(&[file], &clash)).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_writer_version_and_dictionary() {
+        use arrow::array::StringArray;
+        use parquet::basic::Encoding;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8, false),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["x", "x", "y"])),
+                Arc::new(StringArray::from(vec!["x", "x", "y"])),
+            ],
+        )
+        .unwrap();
+        let mut data = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut data, schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let file = ParquetFileInfo {
+            name: "input.parquet".to_string(),
+            schema,
+            data: Bytes::from(data),
+            row_count: 3,
+            compression: Compression::UNCOMPRESSED,
+            size_bytes: 0,
+            uncompressed_bytes: 0,
+            max_row_group_rows: 3,
+            key_value_metadata: Vec::new(),
+        };
+
+        let settings = RewriteSettings {
+            writer_version: WriterVersion::PARQUET_2_0,
+            dictionary_enabled: false,
+            per_column_dictionary: true,
+            column_dictionaries: HashMap::from([("b".to_string(), true)]),
+            ..RewriteSettings::default()
+        };
+        let mut output =
+            futures::executor::block_on(rewrite_parquet_files(&[file], &settings)).unwrap();
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output.remove(0))).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().version(), 2);
+        let has_dictionary = |column: usize| {
+            metadata
+                .row_group(0)
+                .column(column)
+                .encodings()
+                .into_iter()
+                .any(|encoding| matches!(encoding, Encoding::RLE_DICTIONARY))
+        };
+        assert!(!has_dictionary(0));
+        assert!(has_dictionary(1));
+    }
+
+    #[test]
+    fn test_rewrite_summary() {
+        use arrow::array::{Int64Array, StringArray};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("note", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from_iter_values(0..1000)),
+                Arc::new(StringArray::from_iter_values((0..1000).map(|i| format!("n{}", i % 3)))),
+                Arc::new(StringArray::from_iter_values((0..1000).map(|i| format!("x{i}")))),
+            ],
+        )
         .unwrap();
-    a.set_attribute("href", &url).unwrap();
-    a.set_attribute("download", file_name).unwrap();
-    a.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
-    web_sys::Url::revoke_object_url(&url).unwrap();
+        let mut data = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut data, schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let file = ParquetFileInfo {
+            name: "input.parquet".to_string(),
+            schema,
+            data: Bytes::from(data),
+            row_count: 1000,
+            compression: Compression::UNCOMPRESSED,
+            size_bytes: 0,
+            uncompressed_bytes: 0,
+            max_row_group_rows: 1000,
+            key_value_metadata: Vec::new(),
+        };
+
+        let settings = RewriteSettings {
+            dropped_columns: HashSet::from(["note".to_string()]),
+            column_renames: HashMap::from([("name".to_string(), "label".to_string())]),
+            compression: CompressionChoice::Zstd,
+            ..RewriteSettings::default()
+        };
+        let files = std::slice::from_ref(&file);
+        let parts = futures::executor::block_on(rewrite_parquet_files(files, &settings)).unwrap();
+        let summary = rewrite_summary(files, &parts, &settings).unwrap();
+
+        assert_eq!(summary.input_bytes, file.data.len() as u64);
+        assert_eq!(summary.output_bytes, parts[0].len() as u64);
+        assert!(summary.output_bytes < summary.input_bytes);
+        assert_eq!(summary.columns.len(), 2);
+        assert_eq!(summary.columns[0].name, "id");
+        assert_eq!(summary.columns[0].renamed, None);
+        assert_eq!(summary.columns[1].name, "name");
+        assert_eq!(summary.columns[1].renamed.as_deref(), Some("label"));
+        assert!(summary.columns.iter().all(|c| c.input_bytes > 0 && c.output_bytes > 0));
+
+        assert_eq!(size_change_pct(200, 150), Some(-25.0));
+        assert_eq!(size_change_pct(0, 10), None);
+        assert_eq!(format_size_change(200, 250), "+25.0%");
+    }
+
+    #[test]
+    fn test_validate_row_filter() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("country", DataType::Utf8, true),
+        ]));
+        let with_filter = |row_filter: &str| RewriteSettings {
+            row_filter: row_filter.to_string(),
+            ..RewriteSettings::default()
+        };
+
+        assert!(validate_row_filter(&schema, &with_filter("")).is_ok());
+        assert!(validate_row_filter(&schema, &with_filter("id > 1 AND country = 'US'")).is_ok());
+        assert!(validate_row_filter(&schema, &with_filter("missing = 1")).is_err());
+        assert!(validate_row_filter(&schema, &with_filter("id >")).is_err());
+
+        let dropped = RewriteSettings {
+            dropped_columns: HashSet::from(["country".to_string()]),
+            ..with_filter("country = 'US'")
+        };
+        assert!(validate_row_filter(&schema, &dropped).is_err());
+    }
+
+    #[test]
+    fn test_part_writer_splits_rows() {
+        use arrow::array::Int64Array;
+
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batch = |values: Vec<i64>| {
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int64Array::from(values))]).unwrap()
+        };
+        let part_rows = |rows_per_file: usize, batches: &[RecordBatch]| {
+            let mut parts =
+                PartWriter::new(schema.clone(), WriterProperties::default(), rows_per_file);
+            for batch in batches {
+                parts.write(batch).unwrap();
+            }
+            parts
+                .finish()
+                .unwrap()
+                .into_iter()
+                .map(|part| {
+                    ParquetRecordBatchReaderBuilder::try_new(Bytes::from(part))
+                        .unwrap()
+                        .metadata()
+                        .file_metadata()
+                        .num_rows()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let batches = [batch(vec![1, 2, 3]), batch(vec![4, 5, 6, 7])];
+        assert_eq!(part_rows(3, &batches), vec![3, 3, 1]);
+        assert_eq!(part_rows(0, &batches), vec![7]);
+        assert_eq!(part_rows(10, &batches), vec![7]);
+        assert_eq!(part_rows(2, &[]), vec![0]);
+
+        let archive = zip_parts(vec![vec![1, 2], vec![3]]).unwrap();
+        assert_eq!(&archive[..4], b"PK\x03\x04");
+    }
+
+    #[test]
+    fn test_rewrite_unions_schemas() {
+        use arrow::array::{Array, Int64Array, StringArray};
+
+        let file = |name: &str, batch: RecordBatch| {
+            let mut data = Vec::new();
+            let mut writer = ArrowWriter::try_new(&mut data, batch.schema(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            ParquetFileInfo {
+                name: name.to_string(),
+                schema: batch.schema(),
+                data: Bytes::from(data),
+                row_count: batch.num_rows(),
+                compression: Compression::UNCOMPRESSED,
+                size_bytes: 0,
+                uncompressed_bytes: 0,
+                max_row_group_rows: batch.num_rows(),
+                key_value_metadata: Vec::new(),
+            }
+        };
+        let a = file(
+            "a.parquet",
+            RecordBatch::try_from_iter([
+                ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+                (
+                    "name",
+                    Arc::new(StringArray::from(vec!["x", "y"])) as ArrayRef,
+                ),
+            ])
+            .unwrap(),
+        );
+        let b = file(
+            "b.parquet",
+            RecordBatch::try_from_iter([
+                ("score", Arc::new(Int64Array::from(vec![7])) as ArrayRef),
+                ("id", Arc::new(Int64Array::from(vec![3])) as ArrayRef),
+            ])
+            .unwrap(),
+        );
+
+        let files = [a.clone(), b];
+        let schema = union_schemas(&files).unwrap();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "name", "score"]);
+        assert!(schema.fields().iter().all(|f| f.is_nullable()));
+
+        let mut output =
+            futures::executor::block_on(rewrite_parquet_files(&files, &RewriteSettings::default()))
+                .unwrap();
+        let batches = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output.remove(0)))
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let batch = arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.column(1).null_count(), 1);
+        assert_eq!(batch.column(2).null_count(), 2);
+
+        let conflicting = file(
+            "c.parquet",
+            RecordBatch::try_from_iter([(
+                "id",
+                Arc::new(StringArray::from(vec!["3"])) as ArrayRef,
+            )])
+            .unwrap(),
+        );
+        let error = union_schemas(&[a, conflicting]).unwrap_err().to_string();
+        assert!(error.contains("`id` is Int64 in 'a.parquet' but Utf8 in 'c.parquet'"));
+    }
 }
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use arrow::record_batch::RecordBatch;
+use arrow_cast::display::array_value_to_string;
+use dioxus::prelude::*;
+
+use crate::components::ui::{BUTTON_PRIMARY, Panel, SectionHeader};
+use crate::utils::{execute_query_inner, format_rows};
+use crate::{ParquetResolved, SESSION_CTX};
+
+const MAX_DISPLAY_ROWS: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Aggregate {
+    Sum,
+    Avg,
+    Count,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn all() -> &'static [Aggregate] {
+        &[
+            Aggregate::Sum,
+            Aggregate::Avg,
+            Aggregate::Count,
+            Aggregate::Min,
+            Aggregate::Max,
+        ]
+    }
+
+    fn value(self) -> &'static str {
+        match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Avg => "avg",
+            Aggregate::Count => "count",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Aggregate::Sum => "Sum",
+            Aggregate::Avg => "Average",
+            Aggregate::Count => "Count",
+            Aggregate::Min => "Min",
+            Aggregate::Max => "Max",
+        }
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|agg| agg.value() == value)
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn pivot_sql(
+    registered_table_name: &str,
+    group_by: &str,
+    value_column: &str,
+    aggregate: Aggregate,
+) -> String {
+    let group_by = quote_ident(group_by);
+    let alias = quote_ident(&format!("{}_{}", aggregate.value(), value_column));
+    format!(
+        "SELECT {group_by}, {}({}) AS {alias} FROM {} GROUP BY {group_by} ORDER BY {group_by}",
+        aggregate.value().to_uppercase(),
+        quote_ident(value_column),
+        quote_ident(registered_table_name),
+    )
+}
+
+async fn run_pivot(sql: String) -> Result<Vec<RecordBatch>> {
+    let (batches, _) = execute_query_inner(&sql, &SESSION_CTX).await?;
+    Ok(batches)
+}
+
+fn pivot_result_table(batches: &[RecordBatch]) -> Element {
+    let Some(schema) = batches.first().map(|b| b.schema()) else {
+        return rsx! {
+            div { class: "text-xs opacity-60", "No rows" }
+        };
+    };
+    let total_rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+    let rows = batches
+        .iter()
+        .flat_map(|batch| {
+            (0..batch.num_rows()).map(move |row| {
+                batch
+                    .columns()
+                    .iter()
+                    .map(|column| {
+                        array_value_to_string(column.as_ref(), row)
+                            .unwrap_or_else(|_| "NULL".to_string())
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .take(MAX_DISPLAY_ROWS)
+        .collect::<Vec<_>>();
+
+    rsx! {
+        div { class: "rounded-lg border border-base-300 bg-base-100 max-h-[24rem] overflow-auto",
+            table { class: "table table-zebra table-pin-rows table-xs",
+                thead {
+                    tr {
+                        for field in schema.fields().iter() {
+                            th { class: "px-2 py-1 text-left", "{field.name()}" }
+                        }
+                    }
+                }
+                tbody {
+                    for row in rows.into_iter() {
+                        tr { class: "hover",
+                            for value in row.into_iter() {
+                                td { class: "px-2 py-1 font-mono break-words", "{value}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if total_rows > MAX_DISPLAY_ROWS {
+            div { class: "text-xs opacity-60",
+                "Showing {MAX_DISPLAY_ROWS} of {format_rows(total_rows as u64)} groups"
+            }
+        }
+    }
+}
+
+/// Builds and runs a `GROUP BY` query from a group-by column, a value column and an aggregate.
+#[component]
+pub fn PivotSection(parquet_reader: Arc<ParquetResolved>) -> Element {
+    let column_names = parquet_reader
+        .metadata()
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect::<Vec<_>>();
+    let first_column = column_names.first().cloned().unwrap_or_default();
+
+    let mut group_by = use_signal(|| first_column.clone());
+    let mut value_column = use_signal(|| first_column.clone());
+    let mut aggregate = use_signal(|| Aggregate::Count);
+
+    let registered_table_name = parquet_reader.registered_table_name().to_string();
+    let sql = pivot_sql(
+        &registered_table_name,
+        &group_by(),
+        &value_column(),
+        aggregate(),
+    );
+
+    let mut action = use_action(move || {
+        let sql = pivot_sql(
+            &registered_table_name,
+            &group_by(),
+            &value_column(),
+            aggregate(),
+        );
+        async move { run_pivot(sql).await }
+    });
+
+    rsx! {
+        Panel { class: Some("rounded-lg p-3 space-y-3".to_string()),
+            SectionHeader {
+                title: "Pivot".to_string(),
+                subtitle: Some("Aggregate a column grouped by another column".to_string()),
+                class: None,
+                trailing: None,
+            }
+            div { class: "flex flex-wrap items-end gap-3 text-xs",
+                div { class: "space-y-1",
+                    label { class: "text-tertiary", "Group by" }
+                    select {
+                        class: "select select-bordered select-xs w-48",
+                        value: "{group_by()}",
+                        onchange: move |ev| group_by.set(ev.value()),
+                        for name in column_names.iter() {
+                            option { value: "{name}", "{name}" }
+                        }
+                    }
+                }
+                div { class: "space-y-1",
+                    label { class: "text-tertiary", "Aggregate" }
+                    select {
+                        class: "select select-bordered select-xs w-32",
+                        value: "{aggregate().value()}",
+                        onchange: move |ev| {
+                            if let Some(agg) = Aggregate::from_value(&ev.value()) {
+                                aggregate.set(agg);
+                            }
+                        },
+                        for agg in Aggregate::all() {
+                            option { value: "{agg.value()}", "{agg.label()}" }
+                        }
+                    }
+                }
+                div { class: "space-y-1",
+                    label { class: "text-tertiary", "Value" }
+                    select {
+                        class: "select select-bordered select-xs w-48",
+                        value: "{value_column()}",
+                        onchange: move |ev| value_column.set(ev.value()),
+                        for name in column_names.iter() {
+                            option { value: "{name}", "{name}" }
+                        }
+                    }
+                }
+                button {
+                    class: "{BUTTON_PRIMARY} px-3 py-1 text-xs",
+                    disabled: column_names.is_empty() || action.pending(),
+                    onclick: move |_| {
+                        action.call();
+                    },
+                    if action.pending() {
+                        "Running..."
+                    } else {
+                        "Run"
+                    }
+                }
+            }
+            pre { class: "text-xs font-mono opacity-75 whitespace-pre-wrap break-all", "{sql}" }
+            match action.value() {
+                Some(Ok(batches)) => pivot_result_table(&batches.read()),
+                Some(Err(e)) => rsx! {
+                    div { class: "text-xs text-red-600 dark:text-red-400 whitespace-pre-wrap", "{e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pivot_sql() {
+        assert_eq!(
+            pivot_sql("trips_ab12", "city", "fare", Aggregate::Avg),
+            "SELECT \"city\", AVG(\"fare\") AS \"avg_fare\" FROM \"trips_ab12\" GROUP BY \"city\" ORDER BY \"city\"",
+        );
+        assert_eq!(
+            pivot_sql("t", "a\"b", "c", Aggregate::Count),
+            "SELECT \"a\"\"b\", COUNT(\"c\") AS \"count_c\" FROM \"t\" GROUP BY \"a\"\"b\" ORDER BY \"a\"\"b\"",
+        );
+    }
+}
@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
+use datafusion::physical_expr::{expressions::Column, utils::collect_columns};
 use datafusion::physical_plan::{
     DisplayFormatType, ExecutionPlan, ExecutionPlanVisitor, accept,
-    display::DisplayableExecutionPlan,
+    display::DisplayableExecutionPlan, projection::ProjectionExec,
 };
 use dioxus::prelude::*;
+use serde_json::{Value, json};
+
+use crate::utils::{copy_to_clipboard, download_data};
 
 #[derive(Debug, Clone)]
 struct PlanTreeNode {
-    _id: usize,
+    id: usize,
     name: String,
     label: String,
     metrics: Option<String>,
@@ -46,7 +50,7 @@ impl ExecutionPlanVisitor for TreeBuilder {
         });
 
         let node = PlanTreeNode {
-            _id: self.next_id,
+            id: self.next_id,
             name,
             label,
             metrics,
@@ -67,6 +71,32 @@ impl ExecutionPlanVisitor for TreeBuilder {
     }
 }
 
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_dot_node(node: &PlanTreeNode, out: &mut String) {
+    let mut label = format!("{}\\n{}", escape_dot(&node.name), escape_dot(&node.label));
+    if let Some(metrics) = &node.metrics {
+        label.push_str(&format!("\\n{}", escape_dot(metrics)));
+    }
+    out.push_str(&format!("  n{} [label=\"{label}\"];\n", node.id));
+    for child in &node.children {
+        out.push_str(&format!("  n{} -> n{};\n", node.id, child.id));
+        write_dot_node(child, out);
+    }
+}
+
+/// Serializes the plan tree as a Graphviz DOT digraph, edges pointing from parent to child.
+fn plan_to_dot(root: &PlanTreeNode) -> String {
+    let mut out = String::from("digraph plan {\n  node [shape=box, fontname=\"monospace\"];\n");
+    write_dot_node(root, &mut out);
+    out.push_str("}\n");
+    out
+}
+
 fn plan_node_view(node: PlanTreeNode) -> Element {
     let has_children = !node.children.is_empty();
     let multi_children = node.children.len() > 1;
@@ -137,9 +167,43 @@ pub fn physical_plan_view(physical_plan: Arc<dyn ExecutionPlan>) -> Element {
     accept(physical_plan.as_ref(), &mut builder).unwrap();
     let root = builder.current_path.pop().unwrap();
     tracing::info!("{}", displayable_plan.indent(true).to_string());
+    let dot = plan_to_dot(&root);
+    let plan_json = serde_json::to_string_pretty(&plan_to_json(&root)).unwrap_or_default();
 
     rsx! {
         div { class: "relative",
+            div { class: "flex justify-end gap-1",
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    title: "Download the plan as a Graphviz DOT file",
+                    onclick: {
+                        let dot = dot.clone();
+                        move |_| download_data("query_plan.dot", dot.clone().into_bytes())
+                    },
+                    "Export DOT"
+                }
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    title: "Copy the plan as Graphviz DOT",
+                    onclick: move |_| copy_to_clipboard(&dot),
+                    "Copy DOT"
+                }
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    title: "Download the plan tree as JSON",
+                    onclick: {
+                        let plan_json = plan_json.clone();
+                        move |_| download_data("query_plan.json", plan_json.clone().into_bytes())
+                    },
+                    "Export JSON"
+                }
+                button {
+                    class: "btn btn-xs btn-ghost",
+                    title: "Copy the plan tree as JSON",
+                    onclick: move |_| copy_to_clipboard(&plan_json),
+                    "Copy JSON"
+                }
+            }
             svg { class: "absolute", width: "0", height: "0",
                 defs {
                     marker {
@@ -158,3 +222,136 @@ pub fn physical_plan_view(physical_plan: Arc<dyn ExecutionPlan>) -> Element {
         }
     }
 }
+
+/// Where a result column comes from, as far as the physical plan tells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ColumnLineage {
+    /// Passed through, possibly renamed, from this column of the scanned table.
+    Source(String),
+    /// Computed by `expr` from these source columns.
+    Computed { expr: String, inputs: Vec<String> },
+}
+
+/// Traces each output column of `plan` back to the scan, following projections and operators
+/// that pass their input columns through unchanged. Columns produced by aggregates, joins and
+/// other reshaping operators are `None`.
+pub(crate) fn column_lineage(plan: &Arc<dyn ExecutionPlan>) -> Vec<Option<ColumnLineage>> {
+    (0..plan.schema().fields().len())
+        .map(|index| trace_column(plan, index))
+        .collect()
+}
+
+fn trace_column(plan: &Arc<dyn ExecutionPlan>, index: usize) -> Option<ColumnLineage> {
+    if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+        let expr = &projection.expr().get(index)?.expr;
+        if let Some(column) = expr.as_any().downcast_ref::<Column>() {
+            return trace_column(projection.input(), column.index());
+        }
+        let mut columns = collect_columns(expr).into_iter().collect::<Vec<_>>();
+        columns.sort_by_key(|column| column.index());
+        let inputs = columns
+            .iter()
+            .map(
+                |column| match trace_column(projection.input(), column.index()) {
+                    Some(ColumnLineage::Source(name)) => name,
+                    _ => column.name().to_string(),
+                },
+            )
+            .collect();
+        return Some(ColumnLineage::Computed {
+            expr: expr.to_string(),
+            inputs,
+        });
+    }
+
+    let schema = plan.schema();
+    let field = schema.fields().get(index)?;
+    match plan.children().as_slice() {
+        [] => Some(ColumnLineage::Source(field.name().clone())),
+        [child] => {
+            let passes_through = child.schema().fields().len() == schema.fields().len()
+                && child.schema().field(index).name() == field.name();
+            passes_through.then(|| trace_column(child, index)).flatten()
+        }
+        _ => None,
+    }
+}
+
+/// The plan tree as nested JSON objects, each with its children inline.
+fn plan_to_json(node: &PlanTreeNode) -> Value {
+    json!({
+        "id": node.id,
+        "name": node.name,
+        "label": node.label,
+        "metrics": node.metrics,
+        "children": node.children.iter().map(plan_to_json).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_to_dot() {
+        let leaf = |id: usize| PlanTreeNode {
+            id,
+            name: "DataSourceExec".to_string(),
+            label: "file_groups={1 group: [[\"a.parquet\"]]}".to_string(),
+            metrics: None,
+            children: vec![],
+        };
+        let root = PlanTreeNode {
+            id: 0,
+            name: "UnionExec".to_string(),
+            label: "UnionExec".to_string(),
+            metrics: Some("output_rows=2".to_string()),
+            children: vec![leaf(1), leaf(2)],
+        };
+
+        assert_eq!(
+            plan_to_dot(&root),
+            "digraph plan {\n  node [shape=box, fontname=\"monospace\"];\n  \
+             n0 [label=\"UnionExec\\nUnionExec\\noutput_rows=2\"];\n  \
+             n0 -> n1;\n  \
+             n1 [label=\"DataSourceExec\\nfile_groups={1 group: [[\\\"a.parquet\\\"]]}\"];\n  \
+             n0 -> n2;\n  \
+             n2 [label=\"DataSourceExec\\nfile_groups={1 group: [[\\\"a.parquet\\\"]]}\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_to_json() {
+        let root = PlanTreeNode {
+            id: 0,
+            name: "CoalesceBatchesExec".to_string(),
+            label: "target_batch_size=8192".to_string(),
+            metrics: Some("output_rows=2".to_string()),
+            children: vec![PlanTreeNode {
+                id: 1,
+                name: "DataSourceExec".to_string(),
+                label: "file_groups={1 group}".to_string(),
+                metrics: None,
+                children: vec![],
+            }],
+        };
+
+        assert_eq!(
+            plan_to_json(&root),
+            json!({
+                "id": 0,
+                "name": "CoalesceBatchesExec",
+                "label": "target_batch_size=8192",
+                "metrics": "output_rows=2",
+                "children": [{
+                    "id": 1,
+                    "name": "DataSourceExec",
+                    "label": "file_groups={1 group}",
+                    "metrics": null,
+                    "children": [],
+                }],
+            })
+        );
+    }
+}
@@ -1,21 +1,48 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
 
-use arrow::array::AsArray;
-use arrow::compute::concat_batches;
+use arrow::array::{Array, ArrayRef, AsArray, UInt32Array};
+use arrow::compute::{SortOptions, concat_batches, sort_to_indices, take_record_batch};
 use arrow::datatypes::DataType;
 use arrow::record_batch::RecordBatch;
 use arrow_cast::base64::{BASE64_STANDARD, Engine};
 use arrow_cast::display::array_value_to_string;
 use datafusion::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
 use dioxus::prelude::*;
+use dioxus_primitives::toast::{ToastOptions, use_toast};
 use futures::StreamExt;
 use mimetype_detector::detect;
 
 use crate::components::ui::Panel;
-use crate::utils::{export_to_csv_inner, export_to_parquet_inner, format_arrow_type};
-use crate::views::plan_visualizer::physical_plan_view;
+use crate::components::{FetchProgressBar, poll_fetch_progress};
+use crate::storage::FetchProgress;
+use crate::utils::{
+    NumberFormat, copy_to_clipboard, export_to_csv_inner, export_to_json_inner,
+    export_to_json_lines_inner, export_to_parquet_inner, format_arrow_type, format_bytes,
+    format_numeric_value, format_rows, format_temporal_value, write_parquet,
+};
+use crate::views::plan_visualizer::{ColumnLineage, column_lineage, physical_plan_view};
+use crate::views::settings::{
+    CSV_DELIMITERS, csv_export_options, export_max_value_len, number_format,
+};
 use crate::{ParquetResolved, SESSION_CTX, utils::execute_query_first_batch_inner};
 
+/// Wall-clock durations of the two phases of a query, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct QueryTimings {
+    sql_generation_ms: Option<f64>,
+    execution_ms: Option<f64>,
+}
+
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_default()
+}
+
 async fn poll_next_batch(
     mut remaining_stream: Signal<Option<SendableRecordBatchStream>>,
     mut record_batches: Signal<Vec<RecordBatch>>,
@@ -46,27 +73,352 @@ async fn drain_remaining_batches(
     Ok(())
 }
 
+/// Display text of a single result cell, honouring the numeric format setting.
+fn cell_text(column: &dyn Array, row: usize, number_format: NumberFormat) -> String {
+    format_numeric_value(column, row, number_format)
+        .or_else(|| format_temporal_value(column, row))
+        .or_else(|| array_value_to_string(column, row).ok())
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+/// Cells among the first `rows` rows whose displayed text contains `term` (ignoring case),
+/// as (row, column) in row-major order.
+fn find_matching_cells(
+    batch: &RecordBatch,
+    rows: usize,
+    term: &str,
+    number_format: NumberFormat,
+) -> Vec<(usize, usize)> {
+    if term.is_empty() {
+        return vec![];
+    }
+    let term = term.to_lowercase();
+    (0..rows.min(batch.num_rows()))
+        .flat_map(|row| (0..batch.num_columns()).map(move |col| (row, col)))
+        .filter(|&(row, col)| {
+            cell_text(batch.column(col).as_ref(), row, number_format)
+                .to_lowercase()
+                .contains(&term)
+        })
+        .collect()
+}
+
+fn find_highlight(
+    matches: &[(usize, usize)],
+    current: Option<(usize, usize)>,
+    cell: (usize, usize),
+) -> &'static str {
+    if current == Some(cell) {
+        "bg-warning text-warning-content"
+    } else if matches.binary_search(&cell).is_ok() {
+        "bg-warning/30"
+    } else {
+        ""
+    }
+}
+
+/// Text copied from a result cell: the full unformatted value, so IDs and numbers paste back
+/// as they are stored.
+fn cell_copy_text(column: &dyn Array, row: usize) -> String {
+    if column.is_null(row) {
+        return "NULL".to_string();
+    }
+    array_value_to_string(column, row).unwrap_or_default()
+}
+
+/// Copy button shown while hovering a result cell, which must be a `relative group`.
+fn copy_cell_button(column: ArrayRef, row: usize) -> Element {
+    rsx! {
+        button {
+            class: "absolute top-0 right-0 btn btn-ghost btn-xs px-1 min-h-0 h-5 opacity-0 group-hover:opacity-100 bg-base-100/80",
+            title: "Copy value",
+            onclick: move |ev| {
+                ev.stop_propagation();
+                copy_to_clipboard(&cell_copy_text(column.as_ref(), row));
+            },
+            "⧉"
+        }
+    }
+}
+
+fn cell_element_id(result_id: usize, row: usize, col: usize) -> String {
+    format!("query-result-{result_id}-cell-{row}-{col}")
+}
+
+fn rows_container_id(result_id: usize) -> String {
+    format!("query-result-{result_id}-rows")
+}
+
+/// Estimated height of a result row; rows with decoded images are taller.
+fn estimated_row_height(decode_images: bool) -> f64 {
+    if decode_images { 104.0 } else { 28.0 }
+}
+
+/// Rows mounted above and below the viewport, so fast scrolling does not show blank space.
+const OVERSCAN_ROWS: usize = 10;
+
+/// The rows of a `total_rows` table to mount for the given scroll position. Everything else is
+/// replaced by spacers of the estimated height.
+fn row_window(
+    scroll_top: f64,
+    viewport_height: f64,
+    row_height: f64,
+    total_rows: usize,
+) -> Range<usize> {
+    let first_visible = (scroll_top.max(0.0) / row_height) as usize;
+    let visible = (viewport_height / row_height).ceil() as usize;
+    let start = first_visible.saturating_sub(OVERSCAN_ROWS).min(total_rows);
+    let end = (first_visible + visible + OVERSCAN_ROWS).min(total_rows);
+    start..end
+}
+
+/// Moves to the next (or previous) find match and scrolls its cell into view. Rows outside the
+/// rendered window are scrolled to by their estimated position, which mounts them.
+fn step_find_match(
+    mut find_index: Signal<Option<usize>>,
+    matches: &[(usize, usize)],
+    forward: bool,
+    result_id: usize,
+    row_height: f64,
+) {
+    if matches.is_empty() {
+        return;
+    }
+    let next = match (find_index().map(|i| i % matches.len()), forward) {
+        (None, true) => 0,
+        (None, false) => matches.len() - 1,
+        (Some(i), true) => (i + 1) % matches.len(),
+        (Some(i), false) => (i + matches.len() - 1) % matches.len(),
+    };
+    find_index.set(Some(next));
+    let (row, col) = matches[next];
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    if let Some(cell) = document.get_element_by_id(&cell_element_id(result_id, row, col)) {
+        cell.scroll_into_view_with_bool(false);
+    } else if let Some(container) = document.get_element_by_id(&rows_container_id(result_id)) {
+        container.set_scroll_top((row as f64 * row_height) as i32);
+    }
+}
+
+/// Results pivoted so each column is a row (name | value per record), for reading a few
+/// wide records.
+fn transposed_result_table(
+    batch: &RecordBatch,
+    show_rows: usize,
+    number_format: NumberFormat,
+    result_id: usize,
+    matches: &[(usize, usize)],
+    current_match: Option<(usize, usize)>,
+) -> Element {
+    let schema = batch.schema();
+    rsx! {
+        div { class: "max-h-[32rem] overflow-auto relative",
+            table { class: "table table-zebra table-pin-rows table-pin-cols table-xs",
+                if show_rows > 1 {
+                    thead {
+                        tr {
+                            th { class: "px-1 py-1 text-left", "Column" }
+                            for row_idx in 0..show_rows {
+                                th { class: "px-1 py-1 text-left font-normal opacity-60", "Row {row_idx + 1}" }
+                            }
+                        }
+                    }
+                }
+                tbody {
+                    for (col_idx , (field , column)) in schema.fields().iter().zip(batch.columns()).enumerate() {
+                        tr { class: "hover",
+                            th { class: "px-1 py-1 text-left align-top leading-tight w-48",
+                                div { class: "truncate", title: "{field.name()}", "{field.name()}" }
+                                div { class: "text-xs opacity-60 font-normal truncate",
+                                    "{format_arrow_type(field.data_type())}"
+                                }
+                            }
+                            for row_idx in 0..show_rows {
+                                {
+                                    let cell_value = cell_text(column.as_ref(), row_idx, number_format);
+                                    let preview = cell_value.chars().take(200).collect::<String>();
+                                    rsx! {
+                                        td {
+                                            id: "{cell_element_id(result_id, row_idx, col_idx)}",
+                                            class: "relative group px-1 py-1 leading-tight break-words align-top {find_highlight(matches, current_match, (row_idx, col_idx))}",
+                                            {copy_cell_button(column.clone(), row_idx)}
+                                            if cell_value.len() > 200 {
+                                                details {
+                                                    summary { class: "cursor-pointer select-none", "{preview}..." }
+                                                    pre { class: "whitespace-pre-wrap", "{cell_value}" }
+                                                }
+                                            } else {
+                                                "{cell_value}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The rows at `selected` (indices into the concatenated result), as a single batch.
+fn take_selected_rows(
+    batches: &[RecordBatch],
+    selected: &BTreeSet<usize>,
+) -> Result<Vec<RecordBatch>, String> {
+    let Some(first) = batches.first() else {
+        return Ok(vec![]);
+    };
+    let merged = concat_batches(&first.schema(), batches).map_err(|e| e.to_string())?;
+    let indices = UInt32Array::from_iter_values(
+        selected
+            .iter()
+            .filter(|&&row| row < merged.num_rows())
+            .map(|&row| row as u32),
+    );
+    let taken = take_record_batch(&merged, &indices).map_err(|e| e.to_string())?;
+    Ok(vec![taken])
+}
+
+/// The order of `batch`'s rows when sorted by `column`, or `None` if the column's type cannot
+/// be sorted, e.g. a struct.
+fn sorted_row_order(batch: &RecordBatch, column: usize, ascending: bool) -> Option<UInt32Array> {
+    let options = SortOptions {
+        descending: !ascending,
+        nulls_first: false,
+    };
+    sort_to_indices(batch.columns().get(column)?, Some(options), None).ok()
+}
+
+/// The loaded batches merged into one and sorted by the selected column.
+#[derive(Clone, PartialEq)]
+struct SortedResult {
+    batch: RecordBatch,
+    /// Source row of each sorted row, `None` when unsorted or the column cannot be sorted.
+    row_order: Option<UInt32Array>,
+}
+
+fn sorted_result(batches: &[RecordBatch], sort_by: Option<(usize, bool)>) -> Option<SortedResult> {
+    let first = batches.first()?;
+    let merged = concat_batches(&first.schema(), batches).expect("Failed to merge record batches");
+    let row_order =
+        sort_by.and_then(|(column, ascending)| sorted_row_order(&merged, column, ascending));
+    let batch = match &row_order {
+        Some(order) => take_record_batch(&merged, order).expect("Failed to sort record batch"),
+        None => merged,
+    };
+    Some(SortedResult { batch, row_order })
+}
+
+/// The batches to export: the checked rows if any are selected, otherwise the full result.
+async fn batches_for_export(
+    remaining_stream: Signal<Option<SendableRecordBatchStream>>,
+    record_batches: Signal<Vec<RecordBatch>>,
+    selected: BTreeSet<usize>,
+) -> Result<Vec<RecordBatch>, String> {
+    if !selected.is_empty() {
+        return take_selected_rows(&record_batches(), &selected);
+    }
+    drain_remaining_batches(remaining_stream, record_batches).await?;
+    Ok(record_batches())
+}
+
 #[component]
 pub fn QueryResultView(
     id: usize,
     query: String,
+    /// `query` is SQL to run as typed rather than input for natural language translation.
+    raw_sql: bool,
     parquet_table: Arc<ParquetResolved>,
     on_hide: EventHandler<usize>,
+    /// Called with the SQL the query runs as, once it is known.
+    on_sql_generated: EventHandler<String>,
 ) -> Element {
     let show_plan = use_signal(|| false);
+    // Rows shown in the transposed view, which is not virtualized.
     let visible_rows = use_signal(|| 20usize);
+    // Rows of the table view to mount, only updated when scrolling moves past a row boundary.
+    let mut rendered_window =
+        use_signal(|| row_window(0.0, 512.0, estimated_row_height(false), usize::MAX));
     let loading_next_batch = use_signal(|| false);
     let mut initialized = use_signal(|| false);
 
-    let progress = use_signal(|| "Generating SQL...".to_string());
+    let progress = use_signal(move || {
+        if raw_sql {
+            "Executing SQL...".to_string()
+        } else {
+            "Generating SQL...".to_string()
+        }
+    });
     let generated_sql = use_signal(|| None::<String>);
     let execution_error = use_signal(|| None::<String>);
     let physical_plan = use_signal(|| None::<Arc<dyn ExecutionPlan>>);
     let record_batches = use_signal(Vec::<RecordBatch>::new);
     let remaining_stream = use_signal(|| None::<SendableRecordBatchStream>);
+    // Column chunks being fetched for the first batch, `None` once it has arrived.
+    let fetch_progress = use_signal(|| None::<FetchProgress>);
 
     let mut decode_images = use_signal(|| false);
+    let mut transposed = use_signal(|| false);
+    let mut find_term = use_signal(String::new);
+    // Position in the current matches, `None` until the user jumps to one.
+    let mut find_index = use_signal(|| None::<usize>);
+    let mut find_input = use_signal(|| None::<Rc<MountedData>>);
     let mut expanded_image_url = use_signal(|| None::<Arc<str>>);
+    let timings = use_signal(QueryTimings::default);
+    // Counters of the backing store when the query started, as (requested, fetched) bytes.
+    let io_baseline = use_signal(|| None::<(u64, u64)>);
+    let mut selected_rows = use_signal(BTreeSet::<usize>::new);
+    // Column the shown rows are sorted by, and whether ascending; the result itself is unchanged.
+    let mut sort_by = use_signal(|| None::<(usize, bool)>);
+    // CSV options for this result, starting from the ones saved in settings.
+    let mut csv_options = use_signal(csv_export_options);
+    // Destination being edited in the "export to S3" form, if it is open.
+    let mut s3_export_path = use_signal(|| None::<String>);
+    let mut exporting_to_s3 = use_signal(|| false);
+    let toast_api = use_toast();
+
+    // Merging, sorting and searching all loaded rows is too slow to redo on every render, e.g.
+    // for each scroll step, so it only reruns when its inputs change.
+    let sorted = use_memo(move || sorted_result(&record_batches.read(), sort_by()));
+    let shown_rows = use_memo(move || {
+        let sorted = sorted.read();
+        let Some(sorted) = sorted.as_ref() else {
+            return vec![];
+        };
+        let total_rows = sorted.batch.num_rows();
+        // The table view mounts only the rows in its viewport, so every loaded row can be
+        // scrolled to.
+        let show_rows = if transposed() {
+            visible_rows().min(total_rows)
+        } else {
+            total_rows
+        };
+        // Selections refer to rows of the unsorted result, which is what gets exported.
+        (0..show_rows)
+            .map(|row| {
+                sorted
+                    .row_order
+                    .as_ref()
+                    .map_or(row, |order| order.value(row) as usize)
+            })
+            .collect::<Vec<_>>()
+    });
+    // Read from settings once per result, so cells and matches agree on how numbers look.
+    let number_format = use_signal(number_format);
+    let matches = use_memo(move || match sorted.read().as_ref() {
+        Some(sorted) => find_matching_cells(
+            &sorted.batch,
+            shown_rows.read().len(),
+            &find_term.read(),
+            number_format(),
+        ),
+        None => vec![],
+    });
+    let number_format = number_format();
 
     if !initialized() {
         initialized.set(true);
@@ -78,23 +430,44 @@ pub fn QueryResultView(
         let mut physical_plan = physical_plan;
         let mut record_batches = record_batches;
         let mut remaining_stream = remaining_stream;
+        let mut timings = timings;
+        let mut io_baseline = io_baseline;
+        let mut fetch_progress = fetch_progress;
 
         spawn(async move {
-            let sql = match crate::nl_to_sql::user_input_to_sql(&query, &parquet_table)
-                .await
-                .map_err(|e| e.to_string())
-            {
-                Ok(sql) => sql,
-                Err(e) => {
-                    execution_error.set(Some(format!("Error generating SQL: {e}")));
-                    return;
+            let sql = if raw_sql {
+                crate::nl_to_sql::qualify_table_names(&query, &parquet_table)
+            } else {
+                let started_at = now_ms();
+                let sql = crate::nl_to_sql::user_input_to_sql(&query, &parquet_table).await;
+                timings.with_mut(|t| t.sql_generation_ms = Some(now_ms() - started_at));
+                match sql.map_err(|e| e.to_string()) {
+                    Ok(sql) => sql,
+                    Err(e) => {
+                        execution_error.set(Some(format!("Error generating SQL: {e}")));
+                        return;
+                    }
                 }
             };
 
             generated_sql.set(Some(sql.clone()));
+            on_sql_generated.call(sql.clone());
             progress.set(format!("Executing SQL...\n\n{sql}"));
 
-            match execute_query_first_batch_inner(&sql, &SESSION_CTX).await {
+            io_baseline.set(
+                parquet_table
+                    .io_stats()
+                    .map(|stats| (stats.bytes_requested(), stats.bytes_fetched())),
+            );
+            if let Some(stats) = parquet_table.io_stats().cloned() {
+                fetch_progress.set(Some(stats.fetch_progress()));
+                spawn(poll_fetch_progress(stats, fetch_progress));
+            }
+            let started_at = now_ms();
+            let result = execute_query_first_batch_inner(&sql, &SESSION_CTX).await;
+            fetch_progress.set(None);
+            timings.with_mut(|t| t.execution_ms = Some(now_ms() - started_at));
+            match result {
                 Ok((first_batches, stream, plan)) => {
                     physical_plan.set(Some(plan));
                     record_batches.set(first_batches);
@@ -111,6 +484,39 @@ pub fn QueryResultView(
     let plan_for_render = physical_plan();
     let batches = record_batches();
     let has_more_batches = remaining_stream.read().is_some();
+    let total_rows = sorted.read().as_ref().map_or(0, |s| s.batch.num_rows());
+    // Pulls the next batch from the stream; the table view also calls this when scrolled to the end.
+    let load_next_batch = move || {
+        if loading_next_batch() {
+            return;
+        }
+
+        let mut visible_rows = visible_rows;
+        let mut loading_next_batch = loading_next_batch;
+        let mut execution_error = execution_error;
+        loading_next_batch.set(true);
+        spawn(async move {
+            execution_error.set(None);
+            match poll_next_batch(remaining_stream, record_batches).await {
+                Ok(true) => {
+                    visible_rows.set(visible_rows() + 20);
+                }
+                Ok(false) => {}
+                Err(e) => execution_error.set(Some(format!("Error loading next batch: {e}"))),
+            }
+            loading_next_batch.set(false);
+        });
+    };
+    let loaded_rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+    // Batches are pulled lazily, so this grows as more of the result is loaded.
+    let bytes_read = io_baseline().zip(parquet_table.io_stats()).map(
+        |((requested_start, fetched_start), stats)| {
+            (
+                stats.bytes_requested().saturating_sub(requested_start),
+                stats.bytes_fetched().saturating_sub(fetched_start),
+            )
+        },
+    );
 
     rsx! {
         Panel { class: Some("p-3".to_string()),
@@ -125,73 +531,197 @@ pub fn QueryResultView(
                         }
                     }
                     div { class: "flex items-center gap-2",
+                        if !selected_rows.read().is_empty() {
+                            span { class: "text-xs opacity-75 whitespace-nowrap",
+                                "{selected_rows.read().len()} selected"
+                            }
+                            button {
+                                class: "btn btn-xs btn-ghost",
+                                title: "Clear row selection",
+                                onclick: move |_| selected_rows.write().clear(),
+                                "Clear"
+                            }
+                        }
+                        div { class: "dropdown dropdown-end",
+                            div {
+                                tabindex: "0",
+                                role: "button",
+                                class: "btn btn-xs btn-ghost",
+                                title: if selected_rows.read().is_empty() { "Export to CSV" } else { "Export selected rows to CSV" },
+                                "CSV"
+                            }
+                            div {
+                                tabindex: "0",
+                                class: "dropdown-content bg-base-100 rounded-box z-20 w-56 p-3 shadow space-y-2 text-xs",
+                                label { class: "flex items-center justify-between gap-2",
+                                    "Delimiter"
+                                    select {
+                                        class: "select select-bordered select-xs w-32",
+                                        value: CSV_DELIMITERS
+                                            .iter()
+                                            .find(|(_, _, delimiter)| *delimiter == csv_options.read().delimiter)
+                                            .map_or("comma", |(value, _, _)| *value),
+                                        onchange: move |ev| {
+                                            if let Some((_, _, delimiter)) = CSV_DELIMITERS
+                                                .iter()
+                                                .find(|(value, _, _)| *value == ev.value())
+                                            {
+                                                csv_options.write().delimiter = *delimiter;
+                                            }
+                                        },
+                                        for (value , label , _) in CSV_DELIMITERS.iter() {
+                                            option { value: "{value}", "{label}" }
+                                        }
+                                    }
+                                }
+                                label { class: "flex items-center justify-between gap-2",
+                                    "Header row"
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "checkbox checkbox-xs",
+                                        checked: csv_options.read().header,
+                                        onchange: move |ev| csv_options.write().header = ev.checked(),
+                                    }
+                                }
+                                label { class: "flex items-center justify-between gap-2",
+                                    "Quote all fields"
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "checkbox checkbox-xs",
+                                        checked: csv_options.read().quote_all,
+                                        onchange: move |ev| csv_options.write().quote_all = ev.checked(),
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-xs btn-primary w-full",
+                                    onclick: move |_| {
+                                        if physical_plan().is_none() {
+                                            return;
+                                        }
+
+                                        let mut execution_error = execution_error;
+                                        let selected = selected_rows();
+                                        let options = csv_options();
+                                        spawn(async move {
+                                            execution_error.set(None);
+                                            let exported = batches_for_export(remaining_stream, record_batches, selected)
+                                                .await
+                                                .and_then(|batches| {
+                                                    export_to_csv_inner(&batches, &options).map_err(|e| e.to_string())
+                                                });
+                                            if let Err(e) = exported {
+                                                execution_error.set(Some(format!("Error exporting CSV: {e}")));
+                                            }
+                                        });
+                                    },
+                                    "Download"
+                                }
+                            }
+                        }
                         button {
                             class: "btn btn-xs btn-ghost",
-                            title: "Export to CSV",
+                            title: if selected_rows.read().is_empty() { "Export to Parquet" } else { "Export selected rows to Parquet" },
                             onclick: move |_| {
                                 if physical_plan().is_none() {
                                     return;
                                 }
 
                                 let mut execution_error = execution_error;
-                                let remaining_stream = remaining_stream;
-                                let record_batches = record_batches;
+                                let selected = selected_rows();
                                 spawn(async move {
                                     execution_error.set(None);
-                                    if let Err(e) =
-                                        drain_remaining_batches(remaining_stream, record_batches).await
-                                    {
-                                        execution_error.set(Some(format!("Error exporting CSV: {e}")));
+                                    let batches =
+                                        match batches_for_export(remaining_stream, record_batches, selected).await {
+                                            Ok(batches) => batches,
+                                            Err(e) => {
+                                                execution_error
+                                                    .set(Some(format!("Error exporting Parquet: {e}")));
+                                                return;
+                                            }
+                                        };
+                                    if batches.is_empty() {
+                                        execution_error.set(Some(
+                                            "Cannot export Parquet: query returned no rows".to_string(),
+                                        ));
                                         return;
                                     }
-                                    let batches = record_batches();
-                                    export_to_csv_inner(&batches);
+                                    export_to_parquet_inner(&batches);
                                 });
                             },
-                            "CSV"
+                            "Parquet"
                         }
                         button {
                             class: "btn btn-xs btn-ghost",
-                            title: "Export to Parquet",
+                            title: if selected_rows.read().is_empty() { "Export to JSON" } else { "Export selected rows to JSON" },
                             onclick: move |_| {
                                 if physical_plan().is_none() {
                                     return;
                                 }
 
                                 let mut execution_error = execution_error;
-                                let remaining_stream = remaining_stream;
-                                let record_batches = record_batches;
+                                let selected = selected_rows();
                                 spawn(async move {
                                     execution_error.set(None);
-                                    if let Err(e) =
-                                        drain_remaining_batches(remaining_stream, record_batches).await
-                                    {
-                                        execution_error
-                                            .set(Some(format!("Error exporting Parquet: {e}")));
-                                        return;
+                                    let exported = batches_for_export(remaining_stream, record_batches, selected)
+                                        .await
+                                        .and_then(|batches| {
+                                            export_to_json_inner(&batches, export_max_value_len()).map_err(|e| e.to_string())
+                                        });
+                                    if let Err(e) = exported {
+                                        execution_error.set(Some(format!("Error exporting JSON: {e}")));
                                     }
+                                });
+                            },
+                            "JSON"
+                        }
+                        button {
+                            class: "btn btn-xs btn-ghost",
+                            title: if selected_rows.read().is_empty() { "Export to JSON Lines, one object per row" } else { "Export selected rows to JSON Lines" },
+                            onclick: move |_| {
+                                if physical_plan().is_none() {
+                                    return;
+                                }
 
-                                    let batches = record_batches();
-                                    if batches.is_empty() {
-                                        execution_error.set(Some(
-                                            "Cannot export Parquet: query returned no rows".to_string(),
-                                        ));
-                                        return;
+                                let mut execution_error = execution_error;
+                                let selected = selected_rows();
+                                spawn(async move {
+                                    execution_error.set(None);
+                                    let exported = batches_for_export(remaining_stream, record_batches, selected)
+                                        .await
+                                        .and_then(|batches| {
+                                            export_to_json_lines_inner(&batches, export_max_value_len())
+                                                .map_err(|e| e.to_string())
+                                        });
+                                    if let Err(e) = exported {
+                                        execution_error.set(Some(format!("Error exporting JSON Lines: {e}")));
                                     }
-                                    export_to_parquet_inner(&batches);
                                 });
                             },
-                            "Parquet"
+                            "JSONL"
+                        }
+                        if parquet_table.is_s3() {
+                            button {
+                                class: if s3_export_path.read().is_some() { "btn btn-xs btn-primary" } else { "btn btn-xs btn-ghost" },
+                                title: "Write the results as Parquet to the bucket this file was loaded from",
+                                onclick: {
+                                    let default_path = parquet_table.sibling_path("query_results.parquet");
+                                    move |_| {
+                                        let next = match s3_export_path() {
+                                            Some(_) => None,
+                                            None => Some(default_path.clone()),
+                                        };
+                                        s3_export_path.set(next);
+                                    }
+                                },
+                                "S3"
+                            }
                         }
                         button {
                             class: "btn btn-xs btn-ghost",
                             title: "Copy SQL",
                             onclick: move |_| {
-                                if let Some(sql) = generated_sql()
-                                    && let Some(window) = web_sys::window()
-                                {
-                                    let clipboard = window.navigator().clipboard();
-                                    let _ = clipboard.write_text(&sql);
+                                if let Some(sql) = generated_sql() {
+                                    copy_to_clipboard(&sql);
                                 }
                             },
                             "Copy"
@@ -217,6 +747,107 @@ pub fn QueryResultView(
                             onclick: move |_| decode_images.set(!decode_images()),
                             "Decode bytes as images"
                         }
+                        button {
+                            class: if transposed() { "btn btn-xs btn-primary" } else { "btn btn-xs btn-ghost" },
+                            title: "Show columns as rows, one column per record",
+                            onclick: move |_| transposed.set(!transposed()),
+                            "Transpose"
+                        }
+                    }
+                }
+            }
+
+            if let Some(destination) = s3_export_path() {
+                form {
+                    class: "flex items-center gap-2 text-xs mb-2",
+                    onsubmit: {
+                        let parquet_table = parquet_table.clone();
+                        move |ev: FormEvent| {
+                            ev.prevent_default();
+                            if physical_plan().is_none() || exporting_to_s3() {
+                                return;
+                            }
+                            let parquet_table = parquet_table.clone();
+                            let destination = s3_export_path().unwrap_or_default();
+                            let selected = selected_rows();
+                            exporting_to_s3.set(true);
+                            spawn(async move {
+                                let written = async {
+                                    let batches = batches_for_export(remaining_stream, record_batches, selected)
+                                        .await
+                                        .map_err(|e| anyhow::anyhow!(e))?;
+                                    if batches.is_empty() {
+                                        return Err(anyhow::anyhow!("query returned no rows"));
+                                    }
+                                    let data = write_parquet(&batches)?;
+                                    parquet_table.put_object(&SESSION_CTX, &destination, data).await
+                                }
+                                    .await;
+                                match written {
+                                    Ok(url) => {
+                                        s3_export_path.set(None);
+                                        toast_api.success(
+                                            "Exported to S3".to_string(),
+                                            ToastOptions::new().description(format!("Wrote {url}")),
+                                        );
+                                    }
+                                    Err(e) => toast_api.error(
+                                        "Failed to export to S3".to_string(),
+                                        ToastOptions::new().description(format!("{e:#}")),
+                                    ),
+                                }
+                                exporting_to_s3.set(false);
+                            });
+                        }
+                    },
+                    span { class: "opacity-75 whitespace-nowrap", "Destination path" }
+                    input {
+                        r#type: "text",
+                        class: "input input-bordered input-xs flex-1 font-mono",
+                        value: "{destination}",
+                        oninput: move |ev| s3_export_path.set(Some(ev.value())),
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "btn btn-xs btn-primary",
+                        disabled: exporting_to_s3() || destination.trim().is_empty(),
+                        if exporting_to_s3() {
+                            "Uploading..."
+                        } else {
+                            "Upload"
+                        }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "btn btn-xs btn-ghost",
+                        onclick: move |_| s3_export_path.set(None),
+                        "Cancel"
+                    }
+                }
+            }
+
+            if maybe_error.is_none() && plan_for_render.is_some() {
+                div { class: "flex flex-wrap gap-x-4 gap-y-1 text-xs opacity-75 mb-2",
+                    span {
+                        "{format_rows(loaded_rows as u64)} rows"
+                        if has_more_batches {
+                            " loaded so far"
+                        }
+                    }
+                    if let Some(ms) = timings().sql_generation_ms {
+                        span { title: "Time to turn the input into SQL, including any LLM call",
+                            "SQL generation {ms:.0} ms"
+                        }
+                    }
+                    if let Some(ms) = timings().execution_ms {
+                        span { title: "Time until the first batch of results was ready",
+                            "Execution {ms:.0} ms"
+                        }
+                    }
+                    if let Some((requested, fetched)) = bytes_read {
+                        span { title: "Bytes of the file read for this query; cached ranges are not fetched again",
+                            "Read {format_bytes(requested)} ({format_bytes(fetched)} over network)"
+                        }
                     }
                 }
             }
@@ -227,6 +858,11 @@ pub fn QueryResultView(
                 }
             } else if plan_for_render.is_none() {
                 pre { class: "text-base-content opacity-75 text-xs whitespace-pre-wrap", "{progress()}" }
+                if let Some(current) = fetch_progress() {
+                    div { class: "mt-2",
+                        FetchProgressBar { progress: current }
+                    }
+                }
             } else {
                 if show_plan()
                     && let Some(plan) = plan_for_render.clone()
@@ -246,102 +882,289 @@ pub fn QueryResultView(
                     }
                 }
 
-                if batches.is_empty() {
-                    div { class: "text-xs text-base-content opacity-75",
-                        "Query executed successfully, no rows returned."
-                    }
-                } else {
+                if let Some(sorted) = sorted() {
                     {
-                        let merged_record_batch = concat_batches(
-                            &batches[0].schema(),
-                            batches.iter().collect::<Vec<_>>(),
-                        )
-                        .expect("Failed to merge record batches");
+                        let SortedResult { batch: merged_record_batch, row_order } = sorted;
+                        let active_sort = sort_by().filter(|_| row_order.is_some());
+                        let sort_indicator = move |col_idx: usize| match active_sort {
+                            Some((column, true)) if column == col_idx => " ▲",
+                            Some((column, false)) if column == col_idx => " ▼",
+                            _ => "",
+                        };
                         let schema = merged_record_batch.schema();
-                        let total_rows = merged_record_batch.num_rows();
-                        let show_rows = visible_rows().min(total_rows);
+                        let shown_rows = shown_rows();
+                        let show_rows = shown_rows.len();
                         let decode_images = decode_images();
+                        let row_height = estimated_row_height(decode_images);
+                        let rendered_rows = {
+                            let window = rendered_window();
+                            window.start.min(show_rows)..window.end.min(show_rows)
+                        };
+                        let top_spacer = rendered_rows.start as f64 * row_height;
+                        let bottom_spacer = (show_rows - rendered_rows.end) as f64 * row_height;
+                        let column_count = merged_record_batch.num_columns() + 1;
+                        let lineage = plan_for_render
+                            .as_ref()
+                            .map(column_lineage)
+                            .unwrap_or_else(|| vec![None; schema.fields().len()]);
+                        let table_name = parquet_table.table_name();
+                        let matches = matches();
+                        let current_match_index = find_index().map(|i| i % matches.len().max(1));
+                        let current_match = current_match_index.and_then(|i| matches.get(i).copied());
+                        let find_status = match (matches.len(), current_match_index) {
+                            (0, _) => "No matches".to_string(),
+                            (n, Some(i)) => format!("{} of {n}", i + 1),
+                            (n, None) => format!("{n} matches"),
+                        };
                         rsx! {
-                            div { class: "max-h-[32rem] overflow-auto overflow-x-auto relative",
-                                table { class: "table table-zebra table-pin-rows table-xs",
-                                    thead {
-                                        tr {
-                                            for field in schema.fields().iter() {
-                                                th { class: "px-1 py-1 text-left min-w-[200px] leading-tight",
-                                                    div { class: "truncate", title: "{field.name()}", "{field.name()}" }
-                                                    div {
-                                                        class: "text-xs opacity-60 truncate",
-                                                        title: "{format_arrow_type(field.data_type())}",
-                                                        "{format_arrow_type(field.data_type())}"
-                                                    }
+                            div {
+                                class: "outline-none",
+                                tabindex: "0",
+                                onkeydown: move |ev: KeyboardEvent| {
+                                    let modifiers = ev.modifiers();
+                                    let shortcut = modifiers.contains(Modifiers::CONTROL) || modifiers.contains(Modifiers::META);
+                                    if shortcut && ev.key() == Key::Character("f".to_string()) {
+                                        ev.prevent_default();
+                                        if let Some(input) = find_input() {
+                                            spawn(async move {
+                                                let _ = input.set_focus(true).await;
+                                            });
+                                        }
+                                    }
+                                },
+                                div { class: "flex items-center gap-2 text-xs mb-2",
+                                    input {
+                                        r#type: "search",
+                                        class: "input input-bordered input-xs w-48",
+                                        placeholder: "Find in results",
+                                        title: "Highlight shown cells containing this text (Enter / Shift+Enter to jump)",
+                                        value: "{find_term}",
+                                        onmounted: move |ev| find_input.set(Some(ev.data())),
+                                        oninput: move |ev| {
+                                            find_term.set(ev.value());
+                                            find_index.set(None);
+                                        },
+                                        onkeydown: {
+                                            let matches = matches.clone();
+                                            move |ev: KeyboardEvent| {
+                                                if ev.key() == Key::Enter {
+                                                    ev.prevent_default();
+                                                    let forward = !ev.modifiers().contains(Modifiers::SHIFT);
+                                                    step_find_match(find_index, &matches, forward, id, row_height);
                                                 }
                                             }
+                                        },
+                                    }
+                                    if !find_term.read().is_empty() {
+                                        span { class: "opacity-75 whitespace-nowrap",
+                                            "{find_status}"
+                                        }
+                                        button {
+                                            class: "btn btn-xs btn-ghost",
+                                            title: "Previous match (Shift+Enter)",
+                                            disabled: matches.is_empty(),
+                                            onclick: {
+                                                let matches = matches.clone();
+                                                move |_| step_find_match(find_index, &matches, false, id, row_height)
+                                            },
+                                            "↑"
+                                        }
+                                        button {
+                                            class: "btn btn-xs btn-ghost",
+                                            title: "Next match (Enter)",
+                                            disabled: matches.is_empty(),
+                                            onclick: {
+                                                let matches = matches.clone();
+                                                move |_| step_find_match(find_index, &matches, true, id, row_height)
+                                            },
+                                            "↓"
                                         }
                                     }
-                                    tbody {
-                                        for row_idx in 0..show_rows {
-                                            tr { class: "hover",
-                                                for col_idx in 0..merged_record_batch.num_columns() {
-                                                    {
-                                                        let column = merged_record_batch.column(col_idx);
-                                                        let cell_value = array_value_to_string(column.as_ref(), row_idx)
-                                                            .unwrap_or_else(|_| "NULL".to_string());
-                                                        let preview = cell_value.chars().take(200).collect::<String>();
-
-                                                        let image_data_url: Option<String> = if decode_images {
-                                                            let column_value: Option<&[u8]> = if column.is_null(row_idx){
-                                                                None
-                                                            } else {
-                                                                match column.data_type() {
-                                                                    DataType::BinaryView => Some(column.as_binary_view().value(row_idx)),
-                                                                    DataType::Binary => Some(column.as_binary::<i32>().value(row_idx)),
-                                                                    DataType::LargeBinary => Some(column.as_binary::<i64>().value(row_idx)),
-                                                                    _ => None,
-                                                                }
-                                                            };
-
-                                                            column_value.and_then(|bytes| {
-                                                                let mime = detect(bytes);
-                                                                if !mime.kind().is_image() {
-                                                                    return None;
+                                }
+                                if transposed() {
+                                    {transposed_result_table(&merged_record_batch, show_rows, number_format, id, &matches, current_match)}
+                                } else {
+                                    div {
+                                        id: "{rows_container_id(id)}",
+                                        class: "max-h-[32rem] overflow-auto overflow-x-auto relative",
+                                        onscroll: move |ev| {
+                                            let data = ev.data();
+                                            let window = row_window(data.scroll_top(), data.client_height() as f64, row_height, usize::MAX);
+                                            if *rendered_window.peek() != window {
+                                                rendered_window.set(window);
+                                            }
+                                            let near_bottom = data.scroll_top() + data.client_height() as f64
+                                                >= data.scroll_height() as f64 - 4.0 * row_height;
+                                            if near_bottom && has_more_batches {
+                                                load_next_batch();
+                                            }
+                                        },
+                                        table { class: "table table-zebra table-pin-rows table-xs",
+                                            thead {
+                                                tr {
+                                                    th { class: "px-1 py-1 w-6",
+                                                        input {
+                                                            r#type: "checkbox",
+                                                            class: "checkbox checkbox-xs",
+                                                            title: "Select all shown rows",
+                                                            checked: show_rows > 0 && shown_rows.iter().all(|row| selected_rows.read().contains(row)),
+                                                            onchange: {
+                                                                let shown_rows = shown_rows.clone();
+                                                                move |ev: FormEvent| {
+                                                                    if ev.checked() {
+                                                                        selected_rows.write().extend(shown_rows.iter().copied());
+                                                                    } else {
+                                                                        selected_rows.write().retain(|row| !shown_rows.contains(row));
+                                                                    }
                                                                 }
-
-                                                                let b64_string = BASE64_STANDARD.encode(bytes);
-                                                                Some(format!("data:{};base64,{}", mime.mime(), b64_string))
-                                                            })
-                                                        } else {
-                                                            None
-                                                        };
+                                                            },
+                                                        }
+                                                    }
+                                                    for (col_idx , (field , lineage)) in schema.fields().iter().zip(lineage.iter()).enumerate() {
+                                                        th {
+                                                            class: "px-1 py-1 text-left min-w-[200px] leading-tight cursor-pointer select-none",
+                                                            title: "Sort the loaded rows by {field.name()}",
+                                                            onclick: move |_| {
+                                                                let ascending = match sort_by() {
+                                                                    Some((column, ascending)) if column == col_idx => !ascending,
+                                                                    _ => true,
+                                                                };
+                                                                sort_by.set(Some((col_idx, ascending)));
+                                                            },
+                                                            div { class: "truncate", title: "{field.name()}",
+                                                                "{field.name()}{sort_indicator(col_idx)}"
+                                                            }
+                                                            div {
+                                                                class: "text-xs opacity-60 truncate",
+                                                                title: "{format_arrow_type(field.data_type())}",
+                                                                "{format_arrow_type(field.data_type())}"
+                                                            }
+                                                            match lineage {
+                                                                Some(ColumnLineage::Source(source)) if source != field.name() => rsx! {
+                                                                    div {
+                                                                        class: "text-xs opacity-60 truncate font-mono",
+                                                                        title: "Renamed from {table_name}.{source}",
+                                                                        "← {source}"
+                                                                    }
+                                                                },
+                                                                Some(ColumnLineage::Computed { expr, inputs }) => rsx! {
+                                                                    div {
+                                                                        class: "text-xs opacity-60 truncate font-mono",
+                                                                        title: "Computed from {table_name}: {expr}",
+                                                                        if inputs.is_empty() {
+                                                                            "ƒ constant"
+                                                                        } else {
+                                                                            {format!("ƒ {}", inputs.join(", "))}
+                                                                        }
+                                                                    }
+                                                                },
+                                                                _ => rsx! {},
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            tbody {
+                                                if top_spacer > 0.0 {
+                                                    tr { td { colspan: "{column_count}", style: "height: {top_spacer}px; padding: 0" } }
+                                                }
+                                                for row_idx in rendered_rows.clone() {
+                                                    {
+                                                        let source_row = shown_rows[row_idx];
                                                         rsx! {
-                                                            td { class: "px-1 py-1 leading-tight break-words",
-                                                                if let Some(url) = &image_data_url {
-                                                                    img {
-                                                                        class: "max-h-24 max-w-xs object-contain cursor-pointer hover:opacity-80 transition-opacity",
-                                                                        src: "{url}",
-                                                                        onclick: {
-                                                                            let url = Arc::from(url.as_str());
-                                                                            move |_| expanded_image_url.set(Some(Arc::clone(&url)))
+                                                            tr { class: "hover",
+                                                                td { class: "px-1 py-1",
+                                                                    input {
+                                                                        r#type: "checkbox",
+                                                                        class: "checkbox checkbox-xs",
+                                                                        checked: selected_rows.read().contains(&source_row),
+                                                                        onchange: move |ev| {
+                                                                            if ev.checked() {
+                                                                                selected_rows.write().insert(source_row);
+                                                                            } else {
+                                                                                selected_rows.write().remove(&source_row);
+                                                                            }
                                                                         },
                                                                     }
-                                                                } else if cell_value.len() > 200 {
-                                                                    details {
-                                                                        summary { class: "cursor-pointer select-none", "{preview}..." }
-                                                                        pre { class: "whitespace-pre-wrap", "{cell_value}" }
+                                                                }
+                                                                for col_idx in 0..merged_record_batch.num_columns() {
+                                                                    {
+                                                                        let column = merged_record_batch.column(col_idx);
+                                                                        let cell_value = cell_text(column.as_ref(), row_idx, number_format);
+                                                                        let preview = cell_value.chars().take(200).collect::<String>();
+
+                                                                        let image_data_url: Option<String> = if decode_images {
+                                                                            let column_value: Option<&[u8]> = if column.is_null(row_idx){
+                                                                                None
+                                                                            } else {
+                                                                                match column.data_type() {
+                                                                                    DataType::BinaryView => Some(column.as_binary_view().value(row_idx)),
+                                                                                    DataType::Binary => Some(column.as_binary::<i32>().value(row_idx)),
+                                                                                    DataType::LargeBinary => Some(column.as_binary::<i64>().value(row_idx)),
+                                                                                    _ => None,
+                                                                                }
+                                                                            };
+
+                                                                            column_value.and_then(|bytes| {
+                                                                                let mime = detect(bytes);
+                                                                                if !mime.kind().is_image() {
+                                                                                    return None;
+                                                                                }
+
+                                                                                let b64_string = BASE64_STANDARD.encode(bytes);
+                                                                                Some(format!("data:{};base64,{}", mime.mime(), b64_string))
+                                                                            })
+                                                                        } else {
+                                                                            None
+                                                                        };
+                                                                        rsx! {
+                                                                            td {
+                                                                            id: "{cell_element_id(id, row_idx, col_idx)}",
+                                                                            class: "relative group px-1 py-1 leading-tight break-words {find_highlight(&matches, current_match, (row_idx, col_idx))}",
+                                                                                if let Some(url) = &image_data_url {
+                                                                                    img {
+                                                                                        class: "max-h-24 max-w-xs object-contain cursor-pointer hover:opacity-80 transition-opacity",
+                                                                                        src: "{url}",
+                                                                                        onclick: {
+                                                                                            let url = Arc::from(url.as_str());
+                                                                                            move |_| expanded_image_url.set(Some(Arc::clone(&url)))
+                                                                                        },
+                                                                                    }
+                                                                                } else if cell_value.len() > 200 {
+                                                                                    {copy_cell_button(column.clone(), row_idx)}
+                                                                                    details {
+                                                                                        summary { class: "cursor-pointer select-none", "{preview}..." }
+                                                                                        pre { class: "whitespace-pre-wrap", "{cell_value}" }
+                                                                                    }
+                                                                                } else {
+                                                                                    {copy_cell_button(column.clone(), row_idx)}
+                                                                                    "{cell_value}"
+                                                                                }
+                                                                            }
+                                                                        }
                                                                     }
-                                                                } else {
-                                                                    "{cell_value}"
                                                                 }
                                                             }
                                                         }
                                                     }
                                                 }
+                                                if bottom_spacer > 0.0 {
+                                                    tr { td { colspan: "{column_count}", style: "height: {bottom_spacer}px; padding: 0" } }
+                                                }
                                             }
                                         }
                                     }
                                 }
                             }
                             if show_rows < total_rows || has_more_batches {
-                                div { class: "mt-2 flex justify-center",
+                                div { class: "mt-2 flex items-center justify-center gap-3",
+                                    span { class: "text-xs opacity-75",
+                                        "Showing {format_rows(show_rows as u64)} of {format_rows(total_rows as u64)}"
+                                        if has_more_batches {
+                                            "+"
+                                        }
+                                        " rows"
+                                    }
                                     button {
                                         class: "btn btn-sm btn-outline",
                                         disabled: loading_next_batch(),
@@ -351,28 +1174,7 @@ pub fn QueryResultView(
                                                 visible_rows.set(visible_rows() + 20);
                                                 return;
                                             }
-
-                                            if loading_next_batch() {
-                                                return;
-                                            }
-
-                                            let mut loading_next_batch = loading_next_batch;
-                                            let mut execution_error = execution_error;
-                                            let remaining_stream = remaining_stream;
-                                            let record_batches = record_batches;
-                                            loading_next_batch.set(true);
-                                            spawn(async move {
-                                                execution_error.set(None);
-                                                match poll_next_batch(remaining_stream, record_batches).await {
-                                                    Ok(true) => {
-                                                        visible_rows.set(visible_rows() + 20);
-                                                    }
-                                                    Ok(false) => {}
-                                                    Err(e) => execution_error
-                                                        .set(Some(format!("Error loading next batch: {e}"))),
-                                                }
-                                                loading_next_batch.set(false);
-                                            });
+                                            load_next_batch();
                                         },
                                         if loading_next_batch() {
                                             "Loading next batch..."
@@ -386,6 +1188,10 @@ pub fn QueryResultView(
                             }
                         }
                     }
+                } else {
+                    div { class: "text-xs text-base-content opacity-75",
+                        "Query executed successfully, no rows returned."
+                    }
                 }
             }
         }
@@ -401,6 +1207,64 @@ mod tests {
 
     wasm_bindgen_test_configure!(run_in_browser);
 
+    #[wasm_bindgen_test]
+    async fn test_take_selected_rows() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batches = vec![
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(vec![1, 2]))])
+                .unwrap(),
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(vec![3, 4]))])
+                .unwrap(),
+        ];
+
+        let selected = BTreeSet::from([0, 3, 10]);
+        let taken = take_selected_rows(&batches, &selected).unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(
+            taken[0]
+                .column(0)
+                .as_primitive::<arrow::datatypes::Int32Type>()
+                .values(),
+            &[1, 4]
+        );
+        assert!(take_selected_rows(&[], &selected).unwrap().is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_cell_copy_text() {
+        let numbers = Int32Array::from(vec![Some(1200), None]);
+        assert_eq!(cell_copy_text(&numbers, 0), "1200");
+        assert_eq!(cell_copy_text(&numbers, 1), "NULL");
+
+        let long = "x".repeat(500);
+        let strings = arrow_array::StringArray::from(vec![long.as_str()]);
+        assert_eq!(cell_copy_text(&strings, 0), long);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_sorted_row_order() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(3), None, Some(1)])),
+                Arc::new(arrow_array::StringArray::from(vec!["b", "c", "a"])),
+            ],
+        )
+        .unwrap();
+
+        let order = |column, ascending| {
+            sorted_row_order(&batch, column, ascending).map(|order| order.values().to_vec())
+        };
+        assert_eq!(order(0, true), Some(vec![2, 0, 1]));
+        assert_eq!(order(0, false), Some(vec![0, 2, 1]));
+        assert_eq!(order(1, true), Some(vec![2, 0, 1]));
+        assert_eq!(order(2, true), None);
+    }
+
     #[wasm_bindgen_test]
     async fn test_batches_can_be_merged() {
         let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
@@ -415,4 +1279,55 @@ mod tests {
         assert!(merged.is_ok());
         assert_eq!(merged.unwrap().num_rows(), 4);
     }
+
+    #[wasm_bindgen_test]
+    fn test_cell_text() {
+        let column = Int32Array::from(vec![1234]);
+        let format = NumberFormat {
+            thousands_separator: true,
+            ..Default::default()
+        };
+        assert_eq!(cell_text(&column, 0, format), "1,234");
+        assert_eq!(cell_text(&column, 0, NumberFormat::default()), "1234");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_find_matching_cells() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![12, 3, 1200])),
+                Arc::new(arrow_array::StringArray::from(vec!["Foo", "bar", "food12"])),
+            ],
+        )
+        .unwrap();
+        let format = NumberFormat::default();
+
+        assert_eq!(
+            find_matching_cells(&batch, 3, "12", format),
+            vec![(0, 0), (2, 0), (2, 1)]
+        );
+        assert_eq!(
+            find_matching_cells(&batch, 3, "FOO", format),
+            vec![(0, 1), (2, 1)]
+        );
+        // Only the shown rows are searched.
+        assert_eq!(find_matching_cells(&batch, 1, "12", format), vec![(0, 0)]);
+        assert!(find_matching_cells(&batch, 3, "", format).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_row_window() {
+        // 512px viewport of 28px rows at the top: 19 visible rows plus overscan below.
+        assert_eq!(row_window(0.0, 512.0, 28.0, 100_000), 0..29);
+        // Scrolled to row 1000: overscan on both sides.
+        assert_eq!(row_window(28_000.0, 512.0, 28.0, 100_000), 990..1029);
+        // Near the end the window stops at the last row.
+        assert_eq!(row_window(28_000.0, 512.0, 28.0, 1005), 990..1005);
+        assert_eq!(row_window(0.0, 512.0, 28.0, 0), 0..0);
+    }
 }
@@ -1,15 +1,34 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::{Result, anyhow};
-use arrow::array::AsArray;
-use arrow::datatypes::Int64Type;
-use byte_unit::{Byte, UnitType};
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch, make_comparator};
+use arrow::compute::SortOptions;
+use arrow::datatypes::{Float64Type, Int64Type, UInt64Type};
+use arrow_cast::display::array_value_to_string;
+use arrow_schema::{DataType, Field, Schema};
 use dioxus::prelude::*;
+use futures::StreamExt;
+use parquet::arrow::ParquetRecordBatchStreamBuilder;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ArrowReaderMetadata;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::basic::{ConvertedType, LogicalType, TimeUnit};
 use parquet::file::metadata::ParquetMetaData;
+use parquet::schema::types::ColumnDescriptor;
+use parquet::variant::VariantType;
+use serde_json::{Value, json};
 
-use crate::components::ui::{Panel, SectionHeader};
-use crate::utils::{execute_query_inner, format_arrow_type, get_column_chunk_page_info};
+use crate::components::ui::{LoadError, Panel, SectionHeader, Skeleton};
+use crate::utils::{
+    column_chunk_page_info, copy_to_clipboard, download_data, execute_query_inner,
+    format_arrow_type, format_bytes, format_rows, prefetch_column_chunks, schema_to_ipc,
+    schema_to_json, yield_to_browser,
+};
+use crate::views::settings::{
+    approx_distinct_enabled, background_worker_enabled, page_scan_concurrency,
+};
+use crate::worker::{WorkerError, run_in_worker};
 use crate::{ParquetResolved, SESSION_CTX};
 
 #[derive(Clone)]
@@ -17,7 +36,10 @@ struct SchemaRow {
     arrow_index: usize,
     arrow_name: String,
     arrow_type: String,
+    data_type: DataType,
     arrow_nullable: String,
+    /// Whether the field carries the Parquet Variant extension type.
+    variant: bool,
     parquet_columns: Vec<ParquetColumnDisplay>,
 }
 
@@ -52,12 +74,36 @@ fn calculate_arrow_memory_size(metadata: &ParquetMetaData, column_index: usize)
     Some(data_size + validity_bitmap_size + metadata_overhead)
 }
 
+/// Keeps rows whose Arrow name, or one of whose Parquet column names or paths, contains `query`
+/// (case-insensitive). When only some leaf columns of a field match, the row keeps just those,
+/// so its rowspan still covers every rendered child.
+fn filter_schema_rows(rows: Vec<SchemaRow>, query: &str) -> Vec<SchemaRow> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return rows;
+    }
+    rows.into_iter()
+        .filter_map(|mut row| {
+            if row.arrow_name.to_lowercase().contains(&query) {
+                return Some(row);
+            }
+            row.parquet_columns.retain(|column| {
+                column.name.to_lowercase().contains(&query)
+                    || column.path.join(".").to_lowercase().contains(&query)
+            });
+            (!row.parquet_columns.is_empty()).then_some(row)
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 struct ParquetColumnDisplay {
     id: usize,
     name: String,
     path: Vec<String>,
     physical_type: String,
+    /// Parquet logical (or legacy converted) type, e.g. `TIMESTAMP(MICROS, true)`.
+    logical_type: Option<String>,
     logical_size: Option<u64>,
     encoded_size: u64,
     compressed_size: u64,
@@ -68,6 +114,46 @@ struct ParquetColumnDisplay {
     compression_summary: String,
 }
 
+/// Time unit name without the payload `Debug` prints, e.g. `MICROS`.
+fn time_unit_name(unit: &TimeUnit) -> String {
+    let name = format!("{unit:?}");
+    name.split('(').next().unwrap_or_default().to_string()
+}
+
+/// The column's logical type as written in Parquet schemas, falling back to the legacy
+/// converted type for files that only set that.
+fn format_logical_type(descriptor: &ColumnDescriptor) -> Option<String> {
+    let formatted = match descriptor.logical_type() {
+        Some(LogicalType::Decimal { scale, precision }) => format!("DECIMAL({precision},{scale})"),
+        Some(LogicalType::Timestamp {
+            is_adjusted_to_u_t_c,
+            unit,
+        }) => format!(
+            "TIMESTAMP({}, {is_adjusted_to_u_t_c})",
+            time_unit_name(&unit)
+        ),
+        Some(LogicalType::Time {
+            is_adjusted_to_u_t_c,
+            unit,
+        }) => format!("TIME({}, {is_adjusted_to_u_t_c})", time_unit_name(&unit)),
+        Some(LogicalType::Integer {
+            bit_width,
+            is_signed,
+        }) => format!("INTEGER({bit_width}, {is_signed})"),
+        Some(logical_type) => format!("{logical_type:?}").to_uppercase(),
+        None => match descriptor.converted_type() {
+            ConvertedType::NONE => return None,
+            ConvertedType::DECIMAL => format!(
+                "DECIMAL({},{})",
+                descriptor.type_precision(),
+                descriptor.type_scale()
+            ),
+            converted => converted.to_string(),
+        },
+    };
+    Some(formatted)
+}
+
 #[derive(Clone, Default)]
 struct ColumnAggregate {
     compressed_size: u64,
@@ -77,12 +163,18 @@ struct ColumnAggregate {
     compressions: HashMap<String, u32>,
 }
 
+fn logical_type_cell(column: &ParquetColumnDisplay) -> Element {
+    match &column.logical_type {
+        Some(logical_type) => rsx! { "{logical_type}" },
+        None => rsx! {
+            span { class: "opacity-50", "-" }
+        },
+    }
+}
+
 fn format_data_size(size: Option<u64>) -> String {
     match size {
-        Some(value) => format!(
-            "{:.2}",
-            Byte::from_u64(value).get_appropriate_unit(UnitType::Binary)
-        ),
+        Some(value) => format_bytes(value),
         None => "-".to_string(),
     }
 }
@@ -96,47 +188,438 @@ fn format_ratio(value: Option<f32>) -> String {
     }
 }
 
-async fn calculate_distinct(column_name: &str, registered_table_name: &str) -> Result<u32> {
-    let distinct_query =
-        format!("SELECT COUNT(DISTINCT \"{column_name}\") from \"{registered_table_name}\"");
+/// Distinct count of a column, either exact or estimated with `approx_distinct` (HyperLogLog).
+#[derive(Clone, Copy)]
+struct DistinctCount {
+    value: u64,
+    approximate: bool,
+}
+
+async fn calculate_distinct(
+    column_name: &str,
+    registered_table_name: &str,
+    approximate: bool,
+) -> Result<DistinctCount> {
+    let distinct_query = if approximate {
+        format!("SELECT approx_distinct(\"{column_name}\") from \"{registered_table_name}\"")
+    } else {
+        format!("SELECT COUNT(DISTINCT \"{column_name}\") from \"{registered_table_name}\"")
+    };
+    // Paint the loading state first; the scan itself runs without yielding.
+    yield_to_browser().await;
     let (results, _) = execute_query_inner(&distinct_query, &SESSION_CTX).await?;
     let first_batch = results
         .first()
         .ok_or_else(|| anyhow!("No record batch returned for distinct count"))?;
-    if first_batch.num_rows() == 0 {
-        return Ok(0);
+    let value = if first_batch.num_rows() == 0 {
+        0
+    } else {
+        let column = first_batch.column(0);
+        match column.data_type() {
+            DataType::UInt64 => column.as_primitive::<UInt64Type>().value(0),
+            _ => column.as_primitive::<Int64Type>().value(0) as u64,
+        }
+    };
+    Ok(DistinctCount { value, approximate })
+}
+
+/// Most frequent values shown for a column that is not bucketed.
+const HISTOGRAM_TOP_VALUES: usize = 20;
+/// Equal-width buckets of a numeric column's histogram.
+const HISTOGRAM_BUCKETS: usize = 10;
+
+/// One bar of a column histogram: a value, a bucket of values, or the column's NULLs.
+#[derive(Clone, Debug, PartialEq)]
+struct HistogramBar {
+    label: String,
+    count: u64,
+    null: bool,
+}
+
+/// Nested values cannot be grouped, so those columns get no histogram.
+fn supports_histogram(data_type: &DataType) -> bool {
+    !data_type.is_nested()
+}
+
+fn top_values_sql(column_name: &str, registered_table_name: &str) -> String {
+    let column = quote_identifier(column_name);
+    format!(
+        "SELECT {column} AS value, COUNT(*) AS count FROM {} GROUP BY {column} ORDER BY count DESC LIMIT {HISTOGRAM_TOP_VALUES}",
+        quote_identifier(registered_table_name)
+    )
+}
+
+fn range_sql(column_name: &str, registered_table_name: &str) -> String {
+    let column = quote_identifier(column_name);
+    format!(
+        "SELECT CAST(MIN({column}) AS DOUBLE), CAST(MAX({column}) AS DOUBLE), COUNT(*) - COUNT({column}) FROM {}",
+        quote_identifier(registered_table_name)
+    )
+}
+
+/// Width of each of [`HISTOGRAM_BUCKETS`] buckets spanning `min..=max`; a column holding a
+/// single value gets one bucket.
+fn bucket_width(min: f64, max: f64) -> f64 {
+    if max > min {
+        (max - min) / HISTOGRAM_BUCKETS as f64
+    } else {
+        1.0
+    }
+}
+
+/// Counts non-null values per bucket; the maximum lands in the last bucket rather than its own.
+fn buckets_sql(column_name: &str, registered_table_name: &str, min: f64, width: f64) -> String {
+    let column = quote_identifier(column_name);
+    format!(
+        "SELECT LEAST(CAST(FLOOR((CAST({column} AS DOUBLE) - {min:?}) / {width:?}) AS BIGINT), {}) AS bucket, COUNT(*) AS count \
+         FROM {} WHERE {column} IS NOT NULL GROUP BY bucket ORDER BY bucket",
+        HISTOGRAM_BUCKETS - 1,
+        quote_identifier(registered_table_name)
+    )
+}
+
+fn format_bucket_bound(value: f64) -> String {
+    let text = format!("{value:.4}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// One bar per bucket, empty ones included so gaps in the distribution show, then NULLs.
+fn bucket_bars(counts: &[(i64, u64)], min: f64, max: f64, null_count: u64) -> Vec<HistogramBar> {
+    let width = bucket_width(min, max);
+    let buckets = if max > min { HISTOGRAM_BUCKETS } else { 1 };
+    let mut bars = (0..buckets)
+        .map(|bucket| {
+            let low = min + width * bucket as f64;
+            let label = if buckets == 1 {
+                format_bucket_bound(min)
+            } else if bucket + 1 == buckets {
+                format!(
+                    "[{}, {}]",
+                    format_bucket_bound(low),
+                    format_bucket_bound(max)
+                )
+            } else {
+                format!(
+                    "[{}, {})",
+                    format_bucket_bound(low),
+                    format_bucket_bound(low + width)
+                )
+            };
+            let count = counts
+                .iter()
+                .filter(|(index, _)| *index == bucket as i64)
+                .map(|(_, count)| count)
+                .sum();
+            HistogramBar {
+                label,
+                count,
+                null: false,
+            }
+        })
+        .collect::<Vec<_>>();
+    bars.extend(null_bar(null_count));
+    bars
+}
+
+fn null_bar(null_count: u64) -> Option<HistogramBar> {
+    (null_count > 0).then(|| HistogramBar {
+        label: "NULL".to_string(),
+        count: null_count,
+        null: true,
+    })
+}
+
+fn count_value(column: &dyn Array, row: usize) -> u64 {
+    match column.data_type() {
+        DataType::UInt64 => column.as_primitive::<UInt64Type>().value(row),
+        _ => column.as_primitive::<Int64Type>().value(row) as u64,
+    }
+}
+
+/// Bars of the value distribution of a column: equal-width buckets for numbers, the most
+/// frequent values otherwise.
+async fn calculate_histogram(
+    column_name: &str,
+    registered_table_name: &str,
+    data_type: &DataType,
+) -> Result<Vec<HistogramBar>> {
+    yield_to_browser().await;
+    if data_type.is_numeric() {
+        let (results, _) =
+            execute_query_inner(&range_sql(column_name, registered_table_name), &SESSION_CTX)
+                .await?;
+        let batch = results
+            .first()
+            .ok_or_else(|| anyhow!("No record batch returned for the column range"))?;
+        let min = batch.column(0).as_primitive::<Float64Type>();
+        let max = batch.column(1).as_primitive::<Float64Type>();
+        let null_count = count_value(batch.column(2), 0);
+        if min.is_null(0)
+            || max.is_null(0)
+            || !min.value(0).is_finite()
+            || !max.value(0).is_finite()
+        {
+            return Ok(null_bar(null_count).into_iter().collect());
+        }
+        let (min, max) = (min.value(0), max.value(0));
+        let sql = buckets_sql(
+            column_name,
+            registered_table_name,
+            min,
+            bucket_width(min, max),
+        );
+        let (results, _) = execute_query_inner(&sql, &SESSION_CTX).await?;
+        let counts = results
+            .iter()
+            .flat_map(|batch| {
+                let buckets = batch.column(0).as_primitive::<Int64Type>();
+                (0..batch.num_rows())
+                    .filter(|&row| buckets.is_valid(row))
+                    .map(|row| (buckets.value(row), count_value(batch.column(1), row)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        return Ok(bucket_bars(&counts, min, max, null_count));
+    }
+
+    let (results, _) = execute_query_inner(
+        &top_values_sql(column_name, registered_table_name),
+        &SESSION_CTX,
+    )
+    .await?;
+    let mut bars = Vec::new();
+    for batch in &results {
+        let values = batch.column(0);
+        for row in 0..batch.num_rows() {
+            let null = values.is_null(row);
+            bars.push(HistogramBar {
+                label: if null {
+                    "NULL".to_string()
+                } else {
+                    array_value_to_string(values, row)?
+                },
+                count: count_value(batch.column(1), row),
+                null,
+            });
+        }
+    }
+    Ok(bars)
+}
+
+/// Quotes `ident` as a SQL identifier, doubling any embedded quotes.
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// All top-level column names, comma-separated.
+fn column_name_list(schema: &Schema) -> String {
+    schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A `SELECT` of every column by name, to edit instead of writing `SELECT *`.
+fn select_list_sql(schema: &Schema, table_name: &str) -> String {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| quote_identifier(field.name()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("SELECT {columns} FROM {}", quote_identifier(table_name))
+}
+
+fn field_to_json(field: &Field) -> Value {
+    let data_type = match field.data_type() {
+        DataType::Struct(_) => "Struct".to_string(),
+        DataType::List(_) => "List".to_string(),
+        DataType::LargeList(_) => "LargeList".to_string(),
+        DataType::ListView(_) => "ListView".to_string(),
+        DataType::LargeListView(_) => "LargeListView".to_string(),
+        DataType::FixedSizeList(_, size) => format!("FixedSizeList({size})"),
+        DataType::Map(_, _) => "Map".to_string(),
+        data_type => format_arrow_type(data_type),
+    };
+    let mut value = json!({
+        "name": field.name(),
+        "type": data_type,
+        "nullable": field.is_nullable(),
+        "metadata": field.metadata(),
+    });
+    match field.data_type() {
+        DataType::Struct(children) => {
+            value["fields"] = children.iter().map(|child| field_to_json(child)).collect();
+        }
+        DataType::List(item)
+        | DataType::LargeList(item)
+        | DataType::ListView(item)
+        | DataType::LargeListView(item)
+        | DataType::FixedSizeList(item, _) => value["item"] = field_to_json(item),
+        DataType::Map(entries, _) => value["entries"] = field_to_json(entries),
+        _ => {}
+    }
+    value
+}
+
+/// Field names, types, nullability and metadata of `schema`, recursing into nested types.
+fn schema_json_text(schema: &Schema) -> String {
+    let value = json!({
+        "fields": schema.fields().iter().map(|field| field_to_json(field)).collect::<Vec<_>>(),
+        "metadata": schema.metadata(),
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+/// Rows read from each row group when validating statistics.
+const STATS_SAMPLE_ROWS: usize = 4096;
+
+/// Outcome of checking sampled values against the stored row group statistics.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct StatisticsValidation {
+    /// Row groups whose stored min/max exclude at least one sampled value, keyed by Arrow field name.
+    suspect: HashMap<String, Vec<usize>>,
+    /// Columns whose sampled values can't be compared with their stored min/max.
+    not_checkable: BTreeSet<String>,
+}
+
+/// A column being validated: its Arrow field name and per row group min/max.
+type CheckedColumn = (usize, String, ArrayRef, ArrayRef);
+
+/// Whether a non-null sampled value falls outside the row group's stored `[min, max]`.
+/// `min` and `max` hold one entry per row group, as produced by [`StatisticsConverter`].
+fn violates_statistics(
+    values: &dyn Array,
+    min: &dyn Array,
+    max: &dyn Array,
+    row_group: usize,
+) -> Result<bool> {
+    let options = SortOptions::default();
+    let cmp_min = make_comparator(values, min, options)?;
+    let cmp_max = make_comparator(values, max, options)?;
+    let min_known = min.is_valid(row_group);
+    let max_known = max.is_valid(row_group);
+    Ok((0..values.len()).filter(|&i| values.is_valid(i)).any(|i| {
+        (min_known && cmp_min(i, row_group).is_lt()) || (max_known && cmp_max(i, row_group).is_gt())
+    }))
+}
+
+/// Checks the sampled `batch` of `row_group` against the stored statistics of the `checked`
+/// columns. A column whose values can't be compared is reported as not checkable and
+/// skipped from then on, without stopping the others.
+fn check_sampled_batch(
+    batch: &RecordBatch,
+    checked: &[CheckedColumn],
+    row_group: usize,
+    validation: &mut StatisticsValidation,
+) {
+    for (_, name, mins, maxes) in checked {
+        if validation.not_checkable.contains(name) {
+            continue;
+        }
+        let Some(values) = batch.column_by_name(name) else {
+            continue;
+        };
+        match violates_statistics(values.as_ref(), mins.as_ref(), maxes.as_ref(), row_group) {
+            Ok(true) => {
+                let row_groups = validation.suspect.entry(name.clone()).or_default();
+                if row_groups.last() != Some(&row_group) {
+                    row_groups.push(row_group);
+                }
+            }
+            Ok(false) => {}
+            Err(_) => {
+                validation.not_checkable.insert(name.clone());
+            }
+        }
     }
-    let distinct_value = first_batch.column(0).as_primitive::<Int64Type>().value(0);
-    Ok(distinct_value as u32)
+}
+
+/// Reads the first rows of every row group and checks them against the stored row group
+/// statistics. Nested columns and types without statistics support are skipped.
+async fn validate_statistics(parquet_reader: Arc<ParquetResolved>) -> Result<StatisticsValidation> {
+    let metadata = parquet_reader.metadata().metadata.clone();
+    let reader_metadata = ArrowReaderMetadata::try_new(metadata.clone(), Default::default())?;
+    let arrow_schema = reader_metadata.schema().clone();
+    let parquet_schema = metadata.file_metadata().schema_descr();
+
+    let mut checked = Vec::new();
+    for (root_index, field) in arrow_schema.fields().iter().enumerate() {
+        if field.data_type().is_nested() {
+            continue;
+        }
+        let Ok(converter) =
+            StatisticsConverter::try_new(field.name(), &arrow_schema, parquet_schema)
+        else {
+            continue;
+        };
+        let (Ok(mins), Ok(maxes)) = (
+            converter.row_group_mins(metadata.row_groups()),
+            converter.row_group_maxes(metadata.row_groups()),
+        ) else {
+            continue;
+        };
+        if mins.null_count() == mins.len() && maxes.null_count() == maxes.len() {
+            continue;
+        }
+        checked.push((root_index, field.name().clone(), mins, maxes));
+    }
+
+    let mut validation = StatisticsValidation::default();
+    if checked.is_empty() {
+        return Ok(validation);
+    }
+    let projection = ProjectionMask::roots(
+        parquet_schema,
+        checked.iter().map(|(root_index, ..)| *root_index),
+    );
+
+    for row_group in 0..metadata.num_row_groups() {
+        let mut stream = ParquetRecordBatchStreamBuilder::new_with_metadata(
+            parquet_reader.reader().clone(),
+            reader_metadata.clone(),
+        )
+        .with_projection(projection.clone())
+        .with_row_groups(vec![row_group])
+        .with_limit(STATS_SAMPLE_ROWS)
+        .build()?;
+
+        while let Some(batch) = stream.next().await {
+            check_sampled_batch(&batch?, &checked, row_group, &mut validation);
+        }
+        yield_to_browser().await;
+    }
+    Ok(validation)
 }
 
 async fn calculate_page_encodings(
     parquet_reader: Arc<ParquetResolved>,
     column_id: usize,
+    concurrency: usize,
 ) -> Result<String> {
-    let mut column_reader = parquet_reader.reader().clone();
     let metadata = parquet_reader.metadata().metadata.clone();
 
     let mut encoding_counts: HashMap<parquet::basic::Encoding, u32> = HashMap::new();
     let mut total_pages = 0u32;
 
-    for (row_group_id, _rg) in metadata.row_groups().iter().enumerate() {
-        let pages = match get_column_chunk_page_info(
-            &mut column_reader,
-            &metadata,
-            row_group_id,
-            column_id,
-        )
-        .await
-        {
-            Ok(pages) => pages,
-            Err(_) => continue,
-        };
+    let mut chunks = std::pin::pin!(prefetch_column_chunks(
+        parquet_reader.reader(),
+        &metadata,
+        column_id,
+        concurrency,
+    ));
+    while let Some(chunk) = chunks.next().await {
+        let (row_group_id, bytes) = chunk?;
+        let pages = column_chunk_page_info(&metadata, row_group_id, column_id, bytes, false)
+            .map_err(|e| anyhow!("row group {row_group_id}: {e}"))?;
 
         for page in pages {
             total_pages += 1;
             *encoding_counts.entry(page.encoding).or_insert(0) += 1;
         }
+        yield_to_browser().await;
     }
 
     if total_pages == 0 {
@@ -158,41 +641,198 @@ async fn calculate_page_encodings(
         .join(", "))
 }
 
+/// Runs a schema scan described by `task` against `table`. Settings the scan depends on travel
+/// in the task, since the background worker cannot read them from local storage.
+pub(crate) async fn run_schema_task(table: Arc<ParquetResolved>, task: &Value) -> Result<Value> {
+    match task["kind"].as_str() {
+        Some("distinct") => {
+            let column = task["column"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Distinct count task without a column"))?;
+            let approximate = task["approximate"].as_bool().unwrap_or(false);
+            let count =
+                calculate_distinct(column, table.registered_table_name(), approximate).await?;
+            Ok(json!({ "value": count.value, "approximate": count.approximate }))
+        }
+        Some("page_encodings") => {
+            let column_id = task["column_id"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("Page encodings task without a column"))?;
+            let concurrency = task["concurrency"].as_u64().unwrap_or(1);
+            let encodings =
+                calculate_page_encodings(table, column_id as usize, concurrency as usize).await?;
+            Ok(json!(encodings))
+        }
+        _ => Err(anyhow!("Unknown schema task {task}")),
+    }
+}
+
+/// Runs `task` in a background worker so the page stays responsive, or on the page when the
+/// worker is turned off in settings or cannot be started.
+async fn offload_schema_task(table: Arc<ParquetResolved>, task: Value) -> Result<Value> {
+    if background_worker_enabled() {
+        match run_in_worker(&table, &task).await {
+            Ok(value) => return Ok(value),
+            Err(WorkerError::Task(e)) => return Err(anyhow!(e)),
+            Err(WorkerError::Unavailable(e)) => {
+                tracing::warn!("Background worker unavailable, scanning on the page: {e}");
+            }
+        }
+    }
+    run_schema_task(table, &task).await
+}
+
 #[component]
-fn DistinctCell(field_name: String, registered_table_name: String) -> Element {
+fn DistinctCell(field_name: String, parquet_reader: Arc<ParquetResolved>) -> Element {
+    let approximate_by_default = approx_distinct_enabled();
+    let mut approximate = use_signal(|| approximate_by_default);
     let mut action = use_action(move || {
-        let field_name = field_name.clone();
-        let registered_table_name = registered_table_name.clone();
-        async move { calculate_distinct(&field_name, &registered_table_name).await }
+        let parquet_reader = parquet_reader.clone();
+        let task = json!({
+            "kind": "distinct",
+            "column": field_name.clone(),
+            "approximate": approximate(),
+        });
+        async move {
+            let count = offload_schema_task(parquet_reader, task).await?;
+            Ok::<_, anyhow::Error>(DistinctCount {
+                value: count["value"].as_u64().unwrap_or_default(),
+                approximate: count["approximate"].as_bool().unwrap_or_default(),
+            })
+        }
     });
 
     if action.pending() {
         return rsx! {
-            span { class: "opacity-50", "..." }
+            Skeleton {}
         };
     }
 
     match action.value() {
-        Some(Ok(cnt)) => rsx! {
-            span { class: "font-mono text-base-content", "{cnt.read()}" }
-        },
-        Some(Err(_e)) => rsx! {
-            button {
-                class: "text-red-500 hover:underline focus:outline-none",
-                onclick: move |_| {
+        Some(Ok(count)) => {
+            let count = *count.read();
+            if count.approximate {
+                rsx! {
+                    span {
+                        class: "font-mono text-base-content",
+                        title: "Approximate (HyperLogLog)",
+                        "~{format_rows(count.value)}"
+                    }
+                    button {
+                        class: "link link-primary ml-1",
+                        title: "Run an exact COUNT(DISTINCT)",
+                        onclick: move |_| {
+                            approximate.set(false);
+                            action.call();
+                        },
+                        "exact"
+                    }
+                }
+            } else {
+                rsx! {
+                    span { class: "font-mono text-base-content", "{format_rows(count.value)}" }
+                }
+            }
+        }
+        Some(Err(e)) => rsx! {
+            LoadError {
+                message: e.to_string(),
+                on_retry: move |_| {
                     action.call();
                 },
-                "retry"
             }
         },
         None => rsx! {
             button {
                 class: "link link-primary",
                 onclick: move |_| {
+                    approximate.set(approximate_by_default);
                     action.call();
                 },
                 "show"
             }
+            button {
+                class: "link link-primary ml-1 opacity-60",
+                title: if approximate_by_default { "Run an exact COUNT(DISTINCT)" } else { "Estimate with approx_distinct (HyperLogLog)" },
+                onclick: move |_| {
+                    approximate.set(!approximate_by_default);
+                    action.call();
+                },
+                if approximate_by_default {
+                    "exact"
+                } else {
+                    "approx"
+                }
+            }
+        },
+    }
+}
+
+#[component]
+fn HistogramCell(
+    field_name: String,
+    registered_table_name: String,
+    data_type: DataType,
+) -> Element {
+    let mut action = use_action(move || {
+        let field_name = field_name.clone();
+        let registered_table_name = registered_table_name.clone();
+        let data_type = data_type.clone();
+        async move { calculate_histogram(&field_name, &registered_table_name, &data_type).await }
+    });
+
+    if action.pending() {
+        return rsx! {
+            Skeleton {}
+        };
+    }
+
+    match action.value() {
+        Some(Ok(bars)) => {
+            let bars = bars.read().clone();
+            let max_count = bars.iter().map(|bar| bar.count).max().unwrap_or(0).max(1);
+            rsx! {
+                div { class: "mt-1 space-y-0.5 min-w-48",
+                    for (index , bar) in bars.into_iter().enumerate() {
+                        div {
+                            key: "{index}",
+                            class: "flex items-center gap-1 text-[10px]",
+                            title: "{bar.label}: {format_rows(bar.count)}",
+                            span {
+                                class: if bar.null { "w-20 truncate font-mono italic opacity-60" } else { "w-20 truncate font-mono" },
+                                "{bar.label}"
+                            }
+                            div { class: "flex-1 h-2 bg-base-200 rounded",
+                                div {
+                                    class: if bar.null { "h-2 rounded bg-base-content/30" } else { "h-2 rounded bg-primary/70" },
+                                    style: "width: {bar.count as f64 / max_count as f64 * 100.0}%",
+                                }
+                            }
+                            span { class: "font-mono opacity-75", "{format_rows(bar.count)}" }
+                        }
+                    }
+                }
+            }
+        }
+        Some(Err(e)) => rsx! {
+            LoadError {
+                message: e.to_string(),
+                on_retry: move |_| {
+                    action.call();
+                },
+            }
+        },
+        None => rsx! {
+            div {
+                button {
+                    class: "link link-primary text-xs",
+                    title: "Chart the distribution of values in this column",
+                    onclick: move |_| {
+                        action.call();
+                    },
+                    "histogram"
+                }
+            }
         },
     }
 }
@@ -201,12 +841,20 @@ fn DistinctCell(field_name: String, registered_table_name: String) -> Element {
 fn PageEncodingsCell(parquet_reader: Arc<ParquetResolved>, column_id: usize) -> Element {
     let mut action = use_action(move || {
         let parquet_reader = parquet_reader.clone();
-        async move { calculate_page_encodings(parquet_reader, column_id).await }
+        let task = json!({
+            "kind": "page_encodings",
+            "column_id": column_id,
+            "concurrency": page_scan_concurrency(),
+        });
+        async move {
+            let encodings = offload_schema_task(parquet_reader, task).await?;
+            Ok::<_, anyhow::Error>(encodings.as_str().unwrap_or_default().to_string())
+        }
     });
 
     if action.pending() {
         return rsx! {
-            span { class: "opacity-50", "..." }
+            Skeleton {}
         };
     }
 
@@ -214,13 +862,12 @@ fn PageEncodingsCell(parquet_reader: Arc<ParquetResolved>, column_id: usize) ->
         Some(Ok(enc)) => rsx! {
             span { "{enc.read()}" }
         },
-        Some(Err(_e)) => rsx! {
-            button {
-                class: "text-red-500 hover:underline focus:outline-none",
-                onclick: move |_| {
+        Some(Err(e)) => rsx! {
+            LoadError {
+                message: e.to_string(),
+                on_retry: move |_| {
                     action.call();
                 },
-                "retry"
             }
         },
         None => rsx! {
@@ -235,12 +882,113 @@ fn PageEncodingsCell(parquet_reader: Arc<ParquetResolved>, column_id: usize) ->
     }
 }
 
+fn suspect_statistics_badge(
+    validation: Option<&StatisticsValidation>,
+    arrow_name: &str,
+) -> Element {
+    let Some(validation) = validation else {
+        return rsx! {};
+    };
+    if validation.not_checkable.contains(arrow_name) {
+        return rsx! {
+            span {
+                class: "badge badge-ghost badge-xs whitespace-nowrap",
+                title: "Sampled values can't be compared with the stored min/max, so these statistics were not checked",
+                "stats not checked"
+            }
+        };
+    }
+    let Some(row_groups) = validation.suspect.get(arrow_name) else {
+        return rsx! {};
+    };
+    let row_groups = row_groups
+        .iter()
+        .map(|rg| rg.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    rsx! {
+        span {
+            class: "badge badge-warning badge-xs whitespace-nowrap",
+            title: "Sampled values fall outside the stored min/max in row group(s) {row_groups}; don't rely on these statistics for pruning",
+            "suspect stats"
+        }
+    }
+}
+
+/// Names of the shredded fields backing a Variant, taken from the path segment after each
+/// `typed_value`. A variant shredded as a single primitive is listed by its own name.
+fn variant_shredded_fields(arrow_name: &str, columns: &[ParquetColumnDisplay]) -> Vec<String> {
+    let mut fields = Vec::new();
+    for column in columns {
+        let Some(typed) = column.path.iter().position(|part| part == "typed_value") else {
+            continue;
+        };
+        let field = column
+            .path
+            .get(typed + 1)
+            .map(String::as_str)
+            .unwrap_or(arrow_name)
+            .to_string();
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+/// Marks a Variant field and lists the physical columns (`metadata`, `value` and any shredded
+/// `typed_value` columns) that store it.
+fn variant_badge(row: &SchemaRow) -> Element {
+    if !row.variant {
+        return rsx! {};
+    }
+    let shredded = variant_shredded_fields(&row.arrow_name, &row.parquet_columns);
+    rsx! {
+        span {
+            class: "badge badge-info badge-xs whitespace-nowrap",
+            title: "Semi-structured Variant value stored across several Parquet columns",
+            "Variant"
+        }
+        details { class: "text-[11px]",
+            summary { class: "cursor-pointer opacity-60 select-none",
+                "{row.parquet_columns.len()} physical column(s)"
+            }
+            ul { class: "pl-3 font-mono opacity-75",
+                for column in row.parquet_columns.iter() {
+                    li { key: "{column.id}", "{column.path[1..].join(\".\")}" }
+                }
+            }
+            if shredded.is_empty() {
+                div { class: "opacity-60", "Not shredded" }
+            } else {
+                div { class: "opacity-75", "Shredded: {shredded.join(\", \")}" }
+            }
+        }
+    }
+}
+
 #[component]
 pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
     let parquet_info = parquet_reader.metadata().clone();
     let schema = parquet_info.schema.clone();
     let metadata = parquet_info.metadata.clone();
     let registered_table_name = parquet_reader.registered_table_name().to_string();
+    let mut physical_order = use_signal(|| false);
+    let mut column_filter = use_signal(String::new);
+    let mut stats_validation = use_action({
+        let parquet_reader = parquet_reader.clone();
+        move || {
+            let parquet_reader = parquet_reader.clone();
+            async move { validate_statistics(parquet_reader).await }
+        }
+    });
+    let statistics_validation = match stats_validation.value() {
+        Some(Ok(validation)) => Some(validation.read().clone()),
+        _ => None,
+    };
+    let not_checkable_count = statistics_validation
+        .as_ref()
+        .map_or(0, |validation| validation.not_checkable.len());
 
     let schema_descriptor = metadata.file_metadata().schema_descr();
     let parquet_column_count = schema_descriptor.columns().len();
@@ -314,6 +1062,7 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                 name: descriptor.name().to_string(),
                 path,
                 physical_type: format!("{:?}", descriptor.physical_type()),
+                logical_type: format_logical_type(descriptor),
                 logical_size,
                 encoded_size,
                 compressed_size,
@@ -336,32 +1085,53 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
         }
     }
 
-    let schema_rows: Vec<SchemaRow> = schema
-        .fields()
-        .iter()
-        .enumerate()
-        .map(|(arrow_index, field)| {
-            let field = field.as_ref();
-            let parquet_columns_for_field: Vec<ParquetColumnDisplay> = columns_by_root
-                .get(field.name())
-                .into_iter()
-                .flatten()
-                .filter_map(|&parquet_idx| parquet_columns.get(parquet_idx).cloned())
-                .collect();
-
-            SchemaRow {
-                arrow_index,
-                arrow_name: field.name().to_string(),
-                arrow_type: format_arrow_type(field.data_type()),
-                arrow_nullable: if field.is_nullable() {
-                    "Y".to_string()
-                } else {
-                    "N".to_string()
-                },
-                parquet_columns: parquet_columns_for_field,
-            }
-        })
-        .collect();
+    let schema_row =
+        |arrow_index: usize, field: &Field, parquet_columns: Vec<ParquetColumnDisplay>| SchemaRow {
+            arrow_index,
+            arrow_name: field.name().to_string(),
+            arrow_type: format_arrow_type(field.data_type()),
+            data_type: field.data_type().clone(),
+            arrow_nullable: if field.is_nullable() {
+                "Y".to_string()
+            } else {
+                "N".to_string()
+            },
+            variant: field.try_extension_type::<VariantType>().is_ok(),
+            parquet_columns,
+        };
+
+    let schema_rows: Vec<SchemaRow> = if physical_order() {
+        // One row per leaf column, in the order the column chunks are laid out on disk.
+        parquet_columns
+            .iter()
+            .filter_map(|column| {
+                let root = column.path.first()?;
+                let (arrow_index, field) = schema
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, field)| field.name() == root)?;
+                Some(schema_row(arrow_index, field, vec![column.clone()]))
+            })
+            .collect()
+    } else {
+        schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(arrow_index, field)| {
+                let parquet_columns_for_field: Vec<ParquetColumnDisplay> = columns_by_root
+                    .get(field.name())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|&parquet_idx| parquet_columns.get(parquet_idx).cloned())
+                    .collect();
+                schema_row(arrow_index, field, parquet_columns_for_field)
+            })
+            .collect()
+    };
+    let total_schema_rows = schema_rows.len();
+    let schema_rows = filter_schema_rows(schema_rows, &column_filter());
 
     rsx! {
         Panel { class: Some("rounded-lg p-3 flex-1 overflow-auto space-y-4".to_string()),
@@ -369,7 +1139,148 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                 title: "Schema".to_string(),
                 subtitle: None,
                 class: Some("mb-1".to_string()),
-                trailing: None,
+                trailing: Some(rsx! {
+                    div { class: "flex items-center gap-3",
+                        match (&statistics_validation, stats_validation.value()) {
+                            (Some(validation), _) if validation.suspect.is_empty() => rsx! {
+                                span { class: "text-xs opacity-60", "Statistics consistent with sampled data" }
+                            },
+                            (Some(validation), _) => rsx! {
+                                span { class: "text-xs text-warning", "{validation.suspect.len()} column(s) with suspect statistics" }
+                            },
+                            (None, Some(Err(e))) => rsx! {
+                                span { class: "text-xs text-red-500", "Validation failed: {e}" }
+                            },
+                            _ => rsx! {},
+                        }
+                        if not_checkable_count > 0 {
+                            span { class: "text-xs opacity-60", "{not_checkable_count} column(s) not checkable" }
+                        }
+                        button {
+                            class: "btn btn-xs btn-ghost",
+                            title: "Read the first {STATS_SAMPLE_ROWS} rows of each row group and check them against the stored min/max",
+                            disabled: stats_validation.pending(),
+                            onclick: move |_| {
+                                stats_validation.call();
+                            },
+                            if stats_validation.pending() {
+                                "Validating..."
+                            } else {
+                                "Validate statistics"
+                            }
+                        }
+                        div { class: "dropdown dropdown-end",
+                            div {
+                                tabindex: "0",
+                                role: "button",
+                                class: "btn btn-xs btn-ghost",
+                                "Copy columns"
+                            }
+                            ul {
+                                tabindex: "0",
+                                class: "dropdown-content menu menu-xs bg-base-100 rounded-box z-20 w-48 p-1 shadow",
+                                li {
+                                    button {
+                                        title: "All column names, comma-separated",
+                                        onclick: {
+                                            let schema = schema.clone();
+                                            move |_| copy_to_clipboard(&column_name_list(&schema))
+                                        },
+                                        "Column names"
+                                    }
+                                }
+                                li {
+                                    button {
+                                        title: "A SELECT statement listing every column",
+                                        onclick: {
+                                            let schema = schema.clone();
+                                            let registered_table_name = registered_table_name.clone();
+                                            move |_| copy_to_clipboard(&select_list_sql(&schema, &registered_table_name))
+                                        },
+                                        "As SELECT list"
+                                    }
+                                }
+                                li {
+                                    button {
+                                        title: "Names, types, nullability and metadata of every field, nested types included",
+                                        onclick: {
+                                            let schema = schema.clone();
+                                            move |_| copy_to_clipboard(&schema_json_text(&schema))
+                                        },
+                                        "Copy schema JSON"
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "dropdown dropdown-end",
+                            div {
+                                tabindex: "0",
+                                role: "button",
+                                class: "btn btn-xs btn-ghost",
+                                "Download schema"
+                            }
+                            ul {
+                                tabindex: "0",
+                                class: "dropdown-content menu menu-xs bg-base-100 rounded-box z-20 w-48 p-1 shadow",
+                                li {
+                                    button {
+                                        title: "Arrow IPC stream with only the schema message",
+                                        onclick: {
+                                            let schema = schema.clone();
+                                            let table_name = parquet_reader.table_name().to_string();
+                                            move |_| {
+                                                if let Ok(data) = schema_to_ipc(&schema) {
+                                                    download_data(&format!("{table_name}.schema.arrows"), data);
+                                                }
+                                            }
+                                        },
+                                        "Arrow IPC"
+                                    }
+                                }
+                                li {
+                                    button {
+                                        title: "Arrow schema as JSON",
+                                        onclick: {
+                                            let schema = schema.clone();
+                                            let table_name = parquet_reader.table_name().to_string();
+                                            move |_| {
+                                                if let Ok(data) = schema_to_json(&schema) {
+                                                    download_data(&format!("{table_name}.schema.json"), data);
+                                                }
+                                            }
+                                        },
+                                        "JSON"
+                                    }
+                                }
+                            }
+                        }
+                        label {
+                            class: "flex items-center gap-1.5 text-xs cursor-pointer",
+                            title: "List leaf columns in on-disk order instead of grouping them by Arrow field",
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-xs",
+                                checked: physical_order(),
+                                onchange: move |ev| physical_order.set(ev.checked()),
+                            }
+                            "Physical column order"
+                        }
+                    }
+                }),
+            }
+            div { class: "flex items-center gap-2",
+                input {
+                    r#type: "search",
+                    class: "input input-bordered input-xs w-64",
+                    placeholder: "Filter by column name or path",
+                    value: "{column_filter}",
+                    oninput: move |ev| column_filter.set(ev.value()),
+                }
+                if !column_filter().trim().is_empty() {
+                    span { class: "text-xs opacity-60",
+                        "{schema_rows.len()} of {total_schema_rows} fields"
+                    }
+                }
             }
             div { class: "rounded-lg border border-base-300 bg-base-100 overflow-x-auto",
                 table { class: "min-w-full text-xs",
@@ -383,6 +1294,7 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                             }
                             th { class: "py-2 px-3 font-medium", "Parquet Column" }
                             th { class: "py-2 px-3 font-medium", "Parquet Type" }
+                            th { class: "py-2 px-3 font-medium", "Logical Type" }
                             th { class: "py-2 px-3 font-medium", "Logical (L)*" }
                             th { class: "py-2 px-3 font-medium", "Encoded (E)*" }
                             th { class: "py-2 px-3 font-medium", "Compressed (C)*" }
@@ -416,6 +1328,8 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 div { class: "flex flex-col gap-0.5",
                                                     span { class: "font-mono text-[11px] opacity-60", "#{row.arrow_index}" }
                                                     span { class: "font-semibold font-semibold", "{row.arrow_name}" }
+                                                    {suspect_statistics_badge(statistics_validation.as_ref(), &row.arrow_name)}
+                                                    {variant_badge(row)}
                                                 }
                                             }
                                             td { class: "py-1.5 px-3", rowspan: "{group_size}",
@@ -429,7 +1343,14 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 rowspan: "{group_size}",
                                                 DistinctCell {
                                                     field_name: row.arrow_name.clone(),
-                                                    registered_table_name: registered_table_name.clone(),
+                                                    parquet_reader: parquet_reader.clone(),
+                                                }
+                                                if supports_histogram(&row.data_type) {
+                                                    HistogramCell {
+                                                        field_name: row.arrow_name.clone(),
+                                                        registered_table_name: registered_table_name.clone(),
+                                                        data_type: row.data_type.clone(),
+                                                    }
                                                 }
                                             }
 
@@ -443,6 +1364,7 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 span { class: "opacity-50", "-" }
                                             }
                                             td { class: "py-1.5 px-3", "-" }
+                                            td { class: "py-1.5 px-3", "-" }
                                             td { class: "py-1.5 px-3 font-mono", "-" }
                                             td { class: "py-1.5 px-3 font-mono", "-" }
                                             td { class: "py-1.5 px-3 font-mono", "-" }
@@ -466,6 +1388,8 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 div { class: "flex flex-col gap-0.5",
                                                     span { class: "font-mono text-[11px] opacity-60", "#{row.arrow_index}" }
                                                     span { class: "font-semibold font-semibold", "{row.arrow_name}" }
+                                                    {suspect_statistics_badge(statistics_validation.as_ref(), &row.arrow_name)}
+                                                    {variant_badge(row)}
                                                 }
                                             }
                                             td { class: "py-1.5 px-3", rowspan: "{group_size}",
@@ -479,7 +1403,14 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 rowspan: "{group_size}",
                                                 DistinctCell {
                                                     field_name: row.arrow_name.clone(),
-                                                    registered_table_name: registered_table_name.clone(),
+                                                    parquet_reader: parquet_reader.clone(),
+                                                }
+                                                if supports_histogram(&row.data_type) {
+                                                    HistogramCell {
+                                                        field_name: row.arrow_name.clone(),
+                                                        registered_table_name: registered_table_name.clone(),
+                                                        data_type: row.data_type.clone(),
+                                                    }
                                                 }
                                             }
 
@@ -493,6 +1424,9 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                 }
                                             }
                                             td { class: "py-1.5 px-3", "{first_pq_col.physical_type}" }
+                                            td { class: "py-1.5 px-3 font-mono break-all",
+                                                {logical_type_cell(first_pq_col)}
+                                            }
                                             td { class: "py-1.5 px-3 font-mono", "{format_data_size(first_pq_col.logical_size)}" }
                                             td { class: "py-1.5 px-3 font-mono", "{format_data_size(Some(first_pq_col.encoded_size))}" }
                                             td { class: "py-1.5 px-3 font-mono", "{format_data_size(Some(first_pq_col.compressed_size))}" }
@@ -521,6 +1455,9 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
                                                     }
                                                 }
                                                 td { class: "py-1.5 px-3", "{pq_col.physical_type}" }
+                                                td { class: "py-1.5 px-3 font-mono break-all",
+                                                    {logical_type_cell(pq_col)}
+                                                }
                                                 td { class: "py-1.5 px-3 font-mono", "{format_data_size(pq_col.logical_size)}" }
                                                 td { class: "py-1.5 px-3 font-mono", "{format_data_size(Some(pq_col.encoded_size))}" }
                                                 td { class: "py-1.5 px-3 font-mono", "{format_data_size(Some(pq_col.compressed_size))}" }
@@ -580,3 +1517,249 @@ pub fn SchemaSection(parquet_reader: Arc<ParquetResolved>) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow_schema::Fields;
+
+    #[test]
+    fn test_violates_statistics() {
+        let values = StringArray::from(vec![Some("b"), None, Some("d")]);
+        let mins = StringArray::from(vec![Some("a"), Some("c"), None]);
+        let maxes = StringArray::from(vec![Some("z"), Some("z"), Some("c")]);
+        assert!(!violates_statistics(&values, &mins, &maxes, 0).unwrap());
+        assert!(violates_statistics(&values, &mins, &maxes, 1).unwrap());
+        assert!(violates_statistics(&values, &mins, &maxes, 2).unwrap());
+
+        let values = Int32Array::from(vec![5, 7]);
+        let unknown = Int32Array::from(vec![None::<i32>]);
+        assert!(!violates_statistics(&values, &unknown, &unknown, 0).unwrap());
+    }
+
+    #[test]
+    fn test_check_sampled_batch_skips_incomparable_columns() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "text",
+                Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef,
+            ),
+            ("num", Arc::new(Int32Array::from(vec![1, 9])) as ArrayRef),
+        ])
+        .unwrap();
+        let int_stats = |v: i32| Arc::new(Int32Array::from(vec![v])) as ArrayRef;
+        // The text column's statistics have a type its values can't be compared with.
+        let checked = vec![
+            (0, "text".to_string(), int_stats(0), int_stats(10)),
+            (1, "num".to_string(), int_stats(0), int_stats(5)),
+        ];
+        let mut validation = StatisticsValidation::default();
+        check_sampled_batch(&batch, &checked, 0, &mut validation);
+        assert_eq!(
+            validation.not_checkable,
+            BTreeSet::from(["text".to_string()])
+        );
+        assert_eq!(
+            validation.suspect,
+            HashMap::from([("num".to_string(), vec![0])])
+        );
+    }
+
+    #[test]
+    fn test_format_logical_type() {
+        let schema = parquet::schema::parser::parse_message_type(
+            "message m {
+                required int64 ts (TIMESTAMP(MICROS, true));
+                required int32 d (DATE);
+                required fixed_len_byte_array(8) price (DECIMAL(10, 2));
+                optional binary name (UTF8);
+                required int32 small (INTEGER(8, false));
+                required int64 raw;
+            }",
+        )
+        .unwrap();
+        let descriptor = parquet::schema::types::SchemaDescriptor::new(Arc::new(schema));
+        let logical_types = descriptor
+            .columns()
+            .iter()
+            .map(|column| format_logical_type(column))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            logical_types,
+            vec![
+                Some("TIMESTAMP(MICROS, true)".to_string()),
+                Some("DATE".to_string()),
+                Some("DECIMAL(10,2)".to_string()),
+                Some("STRING".to_string()),
+                Some("INTEGER(8, false)".to_string()),
+                None,
+            ]
+        );
+    }
+
+    fn column(id: usize, path: &[&str]) -> ParquetColumnDisplay {
+        ParquetColumnDisplay {
+            id,
+            name: path.last().unwrap().to_string(),
+            path: path.iter().map(|part| part.to_string()).collect(),
+            physical_type: "INT64".to_string(),
+            logical_type: None,
+            logical_size: None,
+            encoded_size: 0,
+            compressed_size: 0,
+            compression_ratio: None,
+            logical_compression_ratio: None,
+            null_count: 0,
+            encodings: String::new(),
+            compression_summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_schema_rows() {
+        let row = |arrow_index: usize, name: &str, columns: Vec<ParquetColumnDisplay>| SchemaRow {
+            arrow_index,
+            arrow_name: name.to_string(),
+            arrow_type: "Int64".to_string(),
+            data_type: DataType::Int64,
+            arrow_nullable: "N".to_string(),
+            variant: false,
+            parquet_columns: columns,
+        };
+        let rows = vec![
+            row(0, "UserId", vec![column(0, &["UserId"])]),
+            row(
+                1,
+                "address",
+                vec![
+                    column(1, &["address", "street"]),
+                    column(2, &["address", "zip"]),
+                ],
+            ),
+        ];
+
+        let ids = |rows: Vec<SchemaRow>| {
+            rows.iter()
+                .map(|row| {
+                    let columns = row.parquet_columns.iter().map(|c| c.id).collect::<Vec<_>>();
+                    (row.arrow_index, columns)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(filter_schema_rows(rows.clone(), "  ")),
+            vec![(0, vec![0]), (1, vec![1, 2])]
+        );
+        assert_eq!(
+            ids(filter_schema_rows(rows.clone(), "userid")),
+            vec![(0, vec![0])]
+        );
+        assert_eq!(
+            ids(filter_schema_rows(rows.clone(), "ADDR")),
+            vec![(1, vec![1, 2])]
+        );
+        assert_eq!(
+            ids(filter_schema_rows(rows.clone(), "ZIP")),
+            vec![(1, vec![2])]
+        );
+        assert_eq!(
+            ids(filter_schema_rows(rows.clone(), "s.st")),
+            vec![(1, vec![1])]
+        );
+        assert!(filter_schema_rows(rows, "missing").is_empty());
+    }
+
+    #[test]
+    fn test_variant_shredded_fields() {
+        let unshredded = [column(0, &["v", "metadata"]), column(1, &["v", "value"])];
+        assert!(variant_shredded_fields("v", &unshredded).is_empty());
+
+        let primitive = [
+            column(0, &["v", "metadata"]),
+            column(1, &["v", "value"]),
+            column(2, &["v", "typed_value"]),
+        ];
+        assert_eq!(variant_shredded_fields("v", &primitive), vec!["v"]);
+
+        let object = [
+            column(0, &["v", "metadata"]),
+            column(1, &["v", "value"]),
+            column(2, &["v", "typed_value", "a", "value"]),
+            column(3, &["v", "typed_value", "a", "typed_value"]),
+            column(4, &["v", "typed_value", "b", "value"]),
+        ];
+        assert_eq!(variant_shredded_fields("v", &object), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_schema_json_text() {
+        let address = Fields::from(vec![Field::new("zip", DataType::Utf8, true)]);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false)
+                .with_metadata(HashMap::from([("origin".to_string(), "db".to_string())])),
+            Field::new("address", DataType::Struct(address), true),
+            Field::new_list("tags", Field::new_list_field(DataType::Utf8, true), true),
+        ]);
+        let value: Value = serde_json::from_str(&schema_json_text(&schema)).unwrap();
+        assert_eq!(
+            value["fields"][0],
+            json!({"name": "id", "type": "Int64", "nullable": false, "metadata": {"origin": "db"}})
+        );
+        assert_eq!(value["fields"][1]["type"], "Struct");
+        assert_eq!(value["fields"][1]["fields"][0]["name"], "zip");
+        assert_eq!(value["fields"][1]["fields"][0]["type"], "String");
+        assert_eq!(value["fields"][2]["type"], "List");
+        assert_eq!(value["fields"][2]["item"]["type"], "String");
+        assert_eq!(value["metadata"], json!({}));
+    }
+
+    #[test]
+    fn test_bucket_bars() {
+        let bars = bucket_bars(&[(0, 3), (9, 2), (4, 1)], 0.0, 100.0, 5);
+        assert_eq!(bars.len(), HISTOGRAM_BUCKETS + 1);
+        assert_eq!(bars[0].label, "[0, 10)");
+        assert_eq!(bars[0].count, 3);
+        assert_eq!(bars[1].count, 0);
+        assert_eq!(bars[4].count, 1);
+        assert_eq!(bars[9].label, "[90, 100]");
+        assert_eq!(bars[9].count, 2);
+        assert!(bars[10].null);
+        assert_eq!(bars[10].count, 5);
+
+        let bars = bucket_bars(&[(0, 7)], 2.5, 2.5, 0);
+        assert_eq!(
+            bars,
+            vec![HistogramBar {
+                label: "2.5".to_string(),
+                count: 7,
+                null: false,
+            }]
+        );
+
+        assert_eq!(
+            buckets_sql("price", "t", -1.5, 0.25),
+            "SELECT LEAST(CAST(FLOOR((CAST(\"price\" AS DOUBLE) - -1.5) / 0.25) AS BIGINT), 9) AS bucket, COUNT(*) AS count \
+             FROM \"t\" WHERE \"price\" IS NOT NULL GROUP BY bucket ORDER BY bucket"
+        );
+        assert!(supports_histogram(&DataType::Utf8));
+        assert!(!supports_histogram(&DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Int32,
+            true
+        )))));
+    }
+
+    #[test]
+    fn test_select_list_sql() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("say \"hi\"", DataType::Utf8, true),
+        ]);
+        assert_eq!(column_name_list(&schema), "id, say \"hi\"");
+        assert_eq!(
+            select_list_sql(&schema, "data_ab12"),
+            "SELECT \"id\", \"say \"\"hi\"\"\" FROM \"data_ab12\""
+        );
+    }
+}
@@ -1,13 +1,243 @@
+use std::time::Duration;
+
+use datafusion::config::ConfigOptions;
+use datafusion::prelude::SessionConfig;
 use dioxus::prelude::*;
 
 use crate::{
+    SESSION_CTX,
     components::ui::{BUTTON_PRIMARY, INPUT_BASE, SectionHeader},
-    utils::{get_stored_value, save_to_storage},
+    storage::persistent_cache::{self, MAX_PERSISTENT_CACHE_BYTES},
+    utils::{CsvExportOptions, NumberFormat, NumberLocale, get_stored_value, save_to_storage},
 };
 
 pub(crate) const S3_ENDPOINT_KEY: &str = "s3_endpoint";
 pub(crate) const S3_ACCESS_KEY_ID_KEY: &str = "s3_access_key_id";
 pub(crate) const S3_SECRET_KEY_KEY: &str = "s3_secret_key";
+pub(crate) const S3_SESSION_TOKEN_KEY: &str = "s3_session_token";
+pub(crate) const S3_VIRTUAL_HOST_STYLE_KEY: &str = "s3_virtual_host_style";
+pub(crate) const GCS_SERVICE_ACCOUNT_KEY: &str = "gcs_service_account";
+pub(crate) const PRELOAD_PAGE_INDEX_KEY: &str = "preload_page_index";
+pub(crate) const APPROX_DISTINCT_KEY: &str = "approx_distinct";
+pub(crate) const BACKGROUND_WORKER_KEY: &str = "background_worker";
+pub(crate) const PAGE_SCAN_CONCURRENCY_KEY: &str = "page_scan_concurrency";
+pub(crate) const REQUEST_TIMEOUT_KEY: &str = "request_timeout_secs";
+pub(crate) const PERSISTENT_RANGE_CACHE_KEY: &str = "persistent_range_cache";
+pub(crate) const THOUSANDS_SEPARATOR_KEY: &str = "number_thousands_separator";
+pub(crate) const DECIMAL_PLACES_KEY: &str = "number_decimal_places";
+pub(crate) const NUMBER_LOCALE_KEY: &str = "number_locale";
+pub(crate) const CSV_DELIMITER_KEY: &str = "csv_delimiter";
+pub(crate) const CSV_HEADER_KEY: &str = "csv_header";
+pub(crate) const CSV_NULL_VALUE_KEY: &str = "csv_null_value";
+pub(crate) const CSV_QUOTE_ALL_KEY: &str = "csv_quote_all";
+pub(crate) const EXPORT_MAX_VALUE_LEN_KEY: &str = "export_max_value_len";
+pub(crate) const DF_PUSHDOWN_FILTERS_KEY: &str = "datafusion_pushdown_filters";
+pub(crate) const DF_TARGET_PARTITIONS_KEY: &str = "datafusion_target_partitions";
+pub(crate) const DF_BATCH_SIZE_KEY: &str = "datafusion_batch_size";
+
+/// Whether S3 requests address the bucket as a subdomain (`bucket.endpoint/key`) instead of
+/// a path (`endpoint/bucket/key`), defaults to path-style.
+pub(crate) fn s3_virtual_host_style_enabled() -> bool {
+    get_stored_value(S3_VIRTUAL_HOST_STYLE_KEY).is_some_and(|v| v == "true")
+}
+
+/// Whether column/offset indexes are fetched together with the footer, defaults to on.
+pub(crate) fn preload_page_index_enabled() -> bool {
+    get_stored_value(PRELOAD_PAGE_INDEX_KEY).is_none_or(|v| v != "false")
+}
+
+/// Whether byte ranges of remote files are kept in IndexedDB across reloads, defaults to on.
+pub(crate) fn persistent_range_cache_enabled() -> bool {
+    get_stored_value(PERSISTENT_RANGE_CACHE_KEY).is_none_or(|v| v != "false")
+}
+
+/// Whether the schema's distinct counts default to `approx_distinct`, defaults to off.
+pub(crate) fn approx_distinct_enabled() -> bool {
+    get_stored_value(APPROX_DISTINCT_KEY).is_some_and(|v| v == "true")
+}
+
+/// Whether distinct counts and page scans run in a Web Worker, defaults to on.
+pub(crate) fn background_worker_enabled() -> bool {
+    get_stored_value(BACKGROUND_WORKER_KEY).is_none_or(|v| v != "false")
+}
+
+const DEFAULT_PAGE_SCAN_CONCURRENCY: usize = 4;
+
+/// Number of column chunks fetched at once when scanning pages across row groups, defaults to 4.
+pub(crate) fn page_scan_concurrency() -> usize {
+    stored_positive_usize(PAGE_SCAN_CONCURRENCY_KEY).unwrap_or(DEFAULT_PAGE_SCAN_CONCURRENCY)
+}
+
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// How long a remote GET/HEAD may stall before it fails, defaults to 60 seconds; 0 disables it.
+pub(crate) fn request_timeout() -> Option<Duration> {
+    let secs = get_stored_value(REQUEST_TIMEOUT_KEY)
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Numeric formatting for query results, defaults to raw values.
+pub(crate) fn number_format() -> NumberFormat {
+    NumberFormat {
+        thousands_separator: get_stored_value(THOUSANDS_SEPARATOR_KEY).is_some_and(|v| v == "true"),
+        decimal_places: get_stored_value(DECIMAL_PLACES_KEY)
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(|v| v.min(12)),
+        locale: number_locale(),
+    }
+}
+
+/// Digit grouping and decimal marks for numbers across the UI, defaults to English.
+pub(crate) fn number_locale() -> NumberLocale {
+    get_stored_value(NUMBER_LOCALE_KEY)
+        .and_then(|v| NumberLocale::from_key(&v))
+        .unwrap_or_default()
+}
+
+/// Delimiters offered for CSV export, as (stored value, label, byte).
+pub(crate) const CSV_DELIMITERS: &[(&str, &str, u8)] = &[
+    ("comma", "Comma (,)", b','),
+    ("tab", "Tab (TSV)", b'\t'),
+    ("semicolon", "Semicolon (;)", b';'),
+    ("pipe", "Pipe (|)", b'|'),
+];
+
+/// CSV export options saved in settings, defaults to comma-separated with a header.
+pub(crate) fn csv_export_options() -> CsvExportOptions {
+    let defaults = CsvExportOptions::default();
+    CsvExportOptions {
+        delimiter: get_stored_value(CSV_DELIMITER_KEY)
+            .and_then(|v| CSV_DELIMITERS.iter().find(|(value, _, _)| *value == v))
+            .map(|(_, _, delimiter)| *delimiter)
+            .unwrap_or(defaults.delimiter),
+        header: get_stored_value(CSV_HEADER_KEY).is_none_or(|v| v != "false"),
+        null_value: get_stored_value(CSV_NULL_VALUE_KEY).unwrap_or(defaults.null_value),
+        quote_all: get_stored_value(CSV_QUOTE_ALL_KEY).is_some_and(|v| v == "true"),
+        max_value_len: export_max_value_len(),
+    }
+}
+
+/// Length string and binary cells are truncated to in CSV/JSON exports, defaults to off.
+pub(crate) fn export_max_value_len() -> Option<usize> {
+    stored_positive_usize(EXPORT_MAX_VALUE_LEN_KEY)
+}
+
+fn stored_positive_usize(key: &str) -> Option<usize> {
+    get_stored_value(key)
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+}
+
+/// Applies the DataFusion options saved in settings on top of the built-in defaults.
+pub(crate) fn apply_stored_session_config(config: &mut SessionConfig) {
+    let options = config.options_mut();
+    if let Some(pushdown) = get_stored_value(DF_PUSHDOWN_FILTERS_KEY) {
+        options.execution.parquet.pushdown_filters = pushdown != "false";
+    }
+    if let Some(target_partitions) = stored_positive_usize(DF_TARGET_PARTITIONS_KEY) {
+        options.execution.target_partitions = target_partitions;
+    }
+    if let Some(batch_size) = stored_positive_usize(DF_BATCH_SIZE_KEY) {
+        options.execution.batch_size = batch_size;
+    }
+}
+
+fn update_session_options(update: impl FnOnce(&mut ConfigOptions)) {
+    let state = SESSION_CTX.state_ref();
+    let mut state = state.write();
+    update(state.config_mut().options_mut());
+}
+
+fn config_row(name: &'static str, value: String) -> Element {
+    rsx! {
+        div { class: "flex justify-between gap-3 font-mono text-xs",
+            span { class: "opacity-75", "{name}" }
+            span { "{value}" }
+        }
+    }
+}
+
+#[component]
+fn DataFusionSettings() -> Element {
+    let config = SESSION_CTX.copied_config();
+    let options = config.options();
+    let mut pushdown_filters = use_signal(|| options.execution.parquet.pushdown_filters);
+    let mut target_partitions = use_signal(|| options.execution.target_partitions.to_string());
+    let mut batch_size = use_signal(|| options.execution.batch_size.to_string());
+
+    rsx! {
+        div { class: "card bg-base-200 p-6",
+            h3 { class: "text-lg font-medium mb-5", "Query Engine" }
+            div { class: "space-y-3",
+                div { class: "flex items-start justify-between gap-3",
+                    div { class: "space-y-0.5",
+                        label { class: "label font-medium", "Filter pushdown" }
+                        p { class: "text-xs opacity-75",
+                            "Evaluate filters while decoding Parquet (late materialization). Applies to files loaded afterwards."
+                        }
+                    }
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm",
+                        checked: pushdown_filters(),
+                        onchange: move |ev| {
+                            let enabled = ev.checked();
+                            save_to_storage(DF_PUSHDOWN_FILTERS_KEY, &enabled.to_string());
+                            update_session_options(|o| o.execution.parquet.pushdown_filters = enabled);
+                            pushdown_filters.set(enabled);
+                        },
+                    }
+                }
+                div { class: "grid grid-cols-2 gap-3",
+                    div {
+                        label { class: "label font-medium", "Target partitions" }
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{target_partitions()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                if let Some(n) = value.parse::<usize>().ok().filter(|n| *n > 0) {
+                                    save_to_storage(DF_TARGET_PARTITIONS_KEY, &value);
+                                    update_session_options(|o| o.execution.target_partitions = n);
+                                }
+                                target_partitions.set(value);
+                            },
+                        }
+                    }
+                    div {
+                        label { class: "label font-medium", "Batch size" }
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            class: "w-full {INPUT_BASE}",
+                            value: "{batch_size()}",
+                            oninput: move |ev| {
+                                let value = ev.value();
+                                if let Some(n) = value.parse::<usize>().ok().filter(|n| *n > 0) {
+                                    save_to_storage(DF_BATCH_SIZE_KEY, &value);
+                                    update_session_options(|o| o.execution.batch_size = n);
+                                }
+                                batch_size.set(value);
+                            },
+                        }
+                    }
+                }
+                div { class: "space-y-1 pt-2 border-t border-base-300",
+                    p { class: "text-xs opacity-75 mb-1", "Read-only" }
+                    {config_row("sql_parser.dialect", format!("{:?}", options.sql_parser.dialect))}
+                    {config_row("parquet.enable_page_index", options.execution.parquet.enable_page_index.to_string())}
+                    {config_row("parquet.pruning", options.execution.parquet.pruning.to_string())}
+                    {config_row("parquet.reorder_filters", options.execution.parquet.reorder_filters.to_string())}
+                    {config_row("parquet.bloom_filter_on_read", options.execution.parquet.bloom_filter_on_read.to_string())}
+                }
+            }
+        }
+    }
+}
 
 #[component]
 pub fn Settings(show: bool, on_close: EventHandler<()>) -> Element {
@@ -17,6 +247,35 @@ pub fn Settings(show: bool, on_close: EventHandler<()>) -> Element {
     let mut s3_access_key_id =
         use_signal(|| get_stored_value(S3_ACCESS_KEY_ID_KEY).unwrap_or_default());
     let mut s3_secret_key = use_signal(|| get_stored_value(S3_SECRET_KEY_KEY).unwrap_or_default());
+    let mut s3_session_token =
+        use_signal(|| get_stored_value(S3_SESSION_TOKEN_KEY).unwrap_or_default());
+    let mut s3_virtual_host_style = use_signal(s3_virtual_host_style_enabled);
+    let mut gcs_service_account =
+        use_signal(|| get_stored_value(GCS_SERVICE_ACCOUNT_KEY).unwrap_or_default());
+    let mut preload_page_index = use_signal(preload_page_index_enabled);
+    let mut persistent_range_cache = use_signal(persistent_range_cache_enabled);
+    let mut cache_cleared = use_signal(|| None::<Result<(), String>>);
+    let mut approx_distinct = use_signal(approx_distinct_enabled);
+    let mut background_worker = use_signal(background_worker_enabled);
+    let mut scan_concurrency = use_signal(|| page_scan_concurrency().to_string());
+    let mut request_timeout_secs = use_signal(|| {
+        request_timeout()
+            .map(|timeout| timeout.as_secs())
+            .unwrap_or_default()
+            .to_string()
+    });
+    let mut thousands_separator = use_signal(|| number_format().thousands_separator);
+    let mut decimal_places =
+        use_signal(|| get_stored_value(DECIMAL_PLACES_KEY).unwrap_or_default());
+    let mut locale = use_signal(number_locale);
+    let mut csv_delimiter =
+        use_signal(|| get_stored_value(CSV_DELIMITER_KEY).unwrap_or("comma".to_string()));
+    let mut csv_null_value =
+        use_signal(|| get_stored_value(CSV_NULL_VALUE_KEY).unwrap_or_default());
+    let mut csv_header = use_signal(|| csv_export_options().header);
+    let mut csv_quote_all = use_signal(|| csv_export_options().quote_all);
+    let mut export_max_value_len =
+        use_signal(|| get_stored_value(EXPORT_MAX_VALUE_LEN_KEY).unwrap_or_default());
 
     if !show {
         return rsx! {};
@@ -101,8 +360,334 @@ pub fn Settings(show: bool, on_close: EventHandler<()>) -> Element {
                                     },
                                 }
                             }
+                            div {
+                                label { class: "label font-medium", "Session Token" }
+                                input {
+                                    r#type: "password",
+                                    placeholder: "Only for temporary credentials",
+                                    class: "w-full {INPUT_BASE}",
+                                    value: "{s3_session_token()}",
+                                    oninput: move |ev| {
+                                        let value = ev.value();
+                                        save_to_storage(S3_SESSION_TOKEN_KEY, &value);
+                                        s3_session_token.set(value);
+                                    },
+                                }
+                            }
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "label font-medium", "Virtual-hosted-style addressing" }
+                                    p { class: "text-xs opacity-75",
+                                        "Address buckets as bucket.endpoint instead of endpoint/bucket. Leave off for MinIO and most self-hosted gateways, which need path-style requests."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: s3_virtual_host_style(),
+                                    onchange: move |ev| {
+                                        let enabled = ev.checked();
+                                        save_to_storage(S3_VIRTUAL_HOST_STYLE_KEY, &enabled.to_string());
+                                        s3_virtual_host_style.set(enabled);
+                                    },
+                                }
+                            }
+                        }
+                    }
+                    div { class: "card bg-base-200 p-6",
+                        h3 { class: "text-lg font-medium mb-5", "GCS Configuration" }
+                        div {
+                            label { class: "label font-medium", "Service Account JSON" }
+                            textarea {
+                                class: "textarea textarea-bordered w-full font-mono text-xs",
+                                rows: "4",
+                                placeholder: "Leave empty to read public buckets anonymously",
+                                value: "{gcs_service_account()}",
+                                oninput: move |ev| {
+                                    let value = ev.value();
+                                    save_to_storage(GCS_SERVICE_ACCOUNT_KEY, &value);
+                                    gcs_service_account.set(value);
+                                },
+                            }
+                            p { class: "text-xs opacity-75 mt-1",
+                                "The key file of a service account with read access to the bucket. It is stored in this browser's local storage."
+                            }
+                        }
+                    }
+                    div { class: "card bg-base-200 p-6",
+                        h3 { class: "text-lg font-medium mb-5", "File Loading" }
+                        div { class: "flex items-start justify-between gap-3",
+                            div { class: "space-y-0.5",
+                                label { class: "label font-medium", "Preload page index" }
+                                p { class: "text-xs opacity-75",
+                                    "Fetch column and offset indexes when a file is opened. Turn off to speed up loading wide or large remote files; page panels then fetch indexes on demand."
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: preload_page_index(),
+                                onchange: move |ev| {
+                                    let enabled = ev.checked();
+                                    save_to_storage(PRELOAD_PAGE_INDEX_KEY, &enabled.to_string());
+                                    preload_page_index.set(enabled);
+                                },
+                            }
+                        }
+                        div { class: "flex items-start justify-between gap-3 mt-4",
+                            div { class: "space-y-0.5",
+                                label { class: "label font-medium", "Approximate distinct counts" }
+                                p { class: "text-xs opacity-75",
+                                    "Count distinct values in the schema table with approx_distinct (HyperLogLog) by default. Much cheaper on large remote files; the exact count stays one click away."
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: approx_distinct(),
+                                onchange: move |ev| {
+                                    let enabled = ev.checked();
+                                    save_to_storage(APPROX_DISTINCT_KEY, &enabled.to_string());
+                                    approx_distinct.set(enabled);
+                                },
+                            }
+                        }
+                        div { class: "flex items-start justify-between gap-3 mt-4",
+                            div { class: "space-y-0.5",
+                                label { class: "label font-medium", "Scan in a background worker" }
+                                p { class: "text-xs opacity-75",
+                                    "Run distinct counts and page encoding scans in a Web Worker so the page stays responsive on large files. Falls back to the page when the browser cannot start one."
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: background_worker(),
+                                onchange: move |ev| {
+                                    let enabled = ev.checked();
+                                    save_to_storage(BACKGROUND_WORKER_KEY, &enabled.to_string());
+                                    background_worker.set(enabled);
+                                },
+                            }
+                        }
+                        div { class: "mt-4",
+                            label { class: "label font-medium", "Page scan concurrency" }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                class: "w-full {INPUT_BASE}",
+                                value: "{scan_concurrency()}",
+                                oninput: move |ev| {
+                                    let value = ev.value();
+                                    save_to_storage(PAGE_SCAN_CONCURRENCY_KEY, &value);
+                                    scan_concurrency.set(value);
+                                },
+                            }
+                            p { class: "text-xs opacity-75 mt-1",
+                                "Column chunks fetched at once when scanning the pages of every row group, e.g. for page encodings. Higher values speed up remote files with many row groups."
+                            }
+                        }
+                        div { class: "flex items-start justify-between gap-3 mt-4",
+                            div { class: "space-y-0.5",
+                                label { class: "label font-medium", "Cache remote reads across reloads" }
+                                p { class: "text-xs opacity-75",
+                                    "Keep fetched byte ranges of remote files in this browser's IndexedDB, up to {MAX_PERSISTENT_CACHE_BYTES / 1024 / 1024} MB, so reopening a file skips the network. Applies to files opened afterwards."
+                                }
+                                div { class: "flex items-center gap-2 pt-1",
+                                    button {
+                                        class: "btn btn-xs btn-ghost",
+                                        onclick: move |_| {
+                                            spawn(async move {
+                                                let result = persistent_cache::clear()
+                                                    .await
+                                                    .map_err(|e| format!("{e:?}"));
+                                                cache_cleared.set(Some(result));
+                                            });
+                                        },
+                                        "Clear cache"
+                                    }
+                                    match cache_cleared() {
+                                        Some(Ok(())) => rsx! {
+                                            span { class: "text-xs opacity-60", "Cache cleared" }
+                                        },
+                                        Some(Err(e)) => rsx! {
+                                            span { class: "text-xs text-error", "Failed to clear the cache: {e}" }
+                                        },
+                                        None => rsx! {},
+                                    }
+                                }
+                            }
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-sm",
+                                checked: persistent_range_cache(),
+                                onchange: move |ev| {
+                                    let enabled = ev.checked();
+                                    save_to_storage(PERSISTENT_RANGE_CACHE_KEY, &enabled.to_string());
+                                    persistent_range_cache.set(enabled);
+                                },
+                            }
+                        }
+                        div { class: "mt-4",
+                            label { class: "label font-medium", "Request timeout (seconds)" }
+                            input {
+                                r#type: "number",
+                                min: "0",
+                                class: "w-full {INPUT_BASE}",
+                                value: "{request_timeout_secs()}",
+                                oninput: move |ev| {
+                                    let value = ev.value();
+                                    save_to_storage(REQUEST_TIMEOUT_KEY, &value);
+                                    request_timeout_secs.set(value);
+                                },
+                            }
+                            p { class: "text-xs opacity-75 mt-1",
+                                "Remote reads that get no response in time fail with a timeout error instead of loading forever. 0 waits indefinitely. Applies to files opened afterwards."
+                            }
+                        }
+                    }
+                    div { class: "card bg-base-200 p-6",
+                        h3 { class: "text-lg font-medium mb-5", "Number Formatting" }
+                        div { class: "space-y-3",
+                            div { class: "flex items-start justify-between gap-3",
+                                div { class: "space-y-0.5",
+                                    label { class: "label font-medium", "Thousands separators" }
+                                    p { class: "text-xs opacity-75",
+                                        "Group the digits of numeric columns, e.g. 1,234,567 instead of 1234567."
+                                    }
+                                }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: thousands_separator(),
+                                    onchange: move |ev| {
+                                        let enabled = ev.checked();
+                                        save_to_storage(THOUSANDS_SEPARATOR_KEY, &enabled.to_string());
+                                        thousands_separator.set(enabled);
+                                    },
+                                }
+                            }
+                            div {
+                                label { class: "label font-medium", "Decimal places" }
+                                input {
+                                    r#type: "number",
+                                    min: "0",
+                                    max: "12",
+                                    placeholder: "As stored",
+                                    class: "w-full {INPUT_BASE}",
+                                    value: "{decimal_places()}",
+                                    oninput: move |ev| {
+                                        let value = ev.value();
+                                        save_to_storage(DECIMAL_PLACES_KEY, &value);
+                                        decimal_places.set(value);
+                                    },
+                                }
+                                p { class: "text-xs opacity-75 mt-1",
+                                    "Fixed precision for floating point columns. Leave empty to show values as stored."
+                                }
+                            }
+                            div {
+                                label { class: "label font-medium", "Number locale" }
+                                select {
+                                    class: "select select-bordered select-sm w-full",
+                                    value: "{locale().key()}",
+                                    onchange: move |ev| {
+                                        if let Some(value) = NumberLocale::from_key(&ev.value()) {
+                                            save_to_storage(NUMBER_LOCALE_KEY, value.key());
+                                            locale.set(value);
+                                        }
+                                    },
+                                    for choice in NumberLocale::ALL {
+                                        option { value: "{choice.key()}", "{choice.label()}" }
+                                    }
+                                }
+                                p { class: "text-xs opacity-75 mt-1",
+                                    "Thousands grouping and decimal marks for row counts, byte sizes and numeric columns."
+                                }
+                            }
+                        }
+                    }
+                    div { class: "card bg-base-200 p-6",
+                        h3 { class: "text-lg font-medium mb-5", "Export" }
+                        div { class: "space-y-3",
+                            div { class: "grid grid-cols-2 gap-3",
+                                div {
+                                    label { class: "label font-medium", "Delimiter" }
+                                    select {
+                                        class: "select select-bordered select-sm w-full",
+                                        value: "{csv_delimiter()}",
+                                        onchange: move |ev| {
+                                            let value = ev.value();
+                                            save_to_storage(CSV_DELIMITER_KEY, &value);
+                                            csv_delimiter.set(value);
+                                        },
+                                        for (value , label , _) in CSV_DELIMITERS.iter() {
+                                            option { value: "{value}", "{label}" }
+                                        }
+                                    }
+                                }
+                                div {
+                                    label { class: "label font-medium", "Null value" }
+                                    input {
+                                        r#type: "text",
+                                        placeholder: "(empty)",
+                                        class: "w-full {INPUT_BASE}",
+                                        value: "{csv_null_value()}",
+                                        oninput: move |ev| {
+                                            let value = ev.value();
+                                            save_to_storage(CSV_NULL_VALUE_KEY, &value);
+                                            csv_null_value.set(value);
+                                        },
+                                    }
+                                }
+                            }
+                            div { class: "flex items-center justify-between gap-3",
+                                label { class: "label font-medium", "Include header row" }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: csv_header(),
+                                    onchange: move |ev| {
+                                        let enabled = ev.checked();
+                                        save_to_storage(CSV_HEADER_KEY, &enabled.to_string());
+                                        csv_header.set(enabled);
+                                    },
+                                }
+                            }
+                            div { class: "flex items-center justify-between gap-3",
+                                label { class: "label font-medium", "Quote all fields" }
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-sm",
+                                    checked: csv_quote_all(),
+                                    onchange: move |ev| {
+                                        let enabled = ev.checked();
+                                        save_to_storage(CSV_QUOTE_ALL_KEY, &enabled.to_string());
+                                        csv_quote_all.set(enabled);
+                                    },
+                                }
+                            }
+                            div {
+                                label { class: "label font-medium", "Truncate long values" }
+                                input {
+                                    r#type: "number",
+                                    min: "1",
+                                    placeholder: "Off",
+                                    class: "w-full {INPUT_BASE}",
+                                    value: "{export_max_value_len()}",
+                                    oninput: move |ev| {
+                                        let value = ev.value();
+                                        save_to_storage(EXPORT_MAX_VALUE_LEN_KEY, &value);
+                                        export_max_value_len.set(value);
+                                    },
+                                }
+                                p { class: "text-xs opacity-75 mt-1",
+                                    "Cut string and binary cells to this many characters (bytes for binary) in CSV and JSON exports. Leave empty to export full values."
+                                }
+                            }
                         }
                     }
+                    DataFusionSettings {}
                 }
 
                 div { class: "modal-action mt-3 pt-2 border-t border-base-300 flex justify-between items-center w-full",
@@ -0,0 +1,397 @@
+//! Runs heavy schema scans (distinct counts, page encodings) in a Web Worker so the page stays
+//! responsive.
+//!
+//! The worker is a small module script that imports this app's wasm-bindgen glue and
+//! instantiates the same compiled module, so it runs the same Rust code as the page. It cannot
+//! reach the page's object stores, handles or caches, so it opens the file through a
+//! [`PageRangeStore`] that posts every range read back to the page:
+//!
+//! - page → worker: `[module, task]` once, then `[id, bytes | error]` per read
+//! - worker → page: `["read", id, start, end]`, then `["result", json]` or `["error", message]`
+
+use std::{
+    cell::{Cell, OnceCell, RefCell},
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion::execution::object_store::ObjectStoreUrl;
+use futures::{channel::oneshot, stream::BoxStream};
+use object_store::{
+    Error as ObjectStoreError, GetOptions, GetResult, GetResultPayload, ListResult,
+    MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOptions, PutOptions, PutPayload,
+    PutResult, path::Path,
+};
+use serde_json::{Value, json};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{
+    Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, ErrorEvent, MessageEvent, Url, Worker,
+    WorkerOptions, WorkerType,
+    js_sys::{Array, Uint8Array},
+};
+
+use crate::storage::{SendWrapper, resolve_get_range};
+use crate::views::parquet_reader::ParquetUnresolved;
+use crate::views::schema::run_schema_task;
+use crate::{ParquetResolved, SESSION_CTX};
+
+/// Object store URL the worker registers the page's file under.
+const PAGE_STORE_URL: &str = "worker://page";
+
+/// Loads the app's wasm from the first message, then hands the task to [`run_worker_task`].
+const WORKER_SCRIPT: &str = r#"import init, { run_worker_task } from GLUE_URL;
+self.onmessage = async (event) => {
+  self.onmessage = null;
+  const [module, task] = event.data;
+  await init({ module_or_path: module });
+  await run_worker_task(task);
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// URL of the wasm-bindgen glue module, which the worker imports to load this binary.
+    #[wasm_bindgen(thread_local_v2, js_namespace = ["import", "meta"], js_name = url)]
+    static GLUE_URL: String;
+}
+
+thread_local! {
+    static WORKER_SCRIPT_URL: OnceCell<String> = const { OnceCell::new() };
+    static PENDING_READS: RefCell<HashMap<u32, oneshot::Sender<Result<Bytes, String>>>> =
+        RefCell::default();
+    static NEXT_READ_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Why a task did not produce a value in the worker.
+#[derive(Debug)]
+pub(crate) enum WorkerError {
+    /// The worker could not be started or died, so the task can still run on the page.
+    Unavailable(String),
+    /// The task itself failed.
+    Task(String),
+}
+
+/// Terminates the worker when the task finishes or its caller goes away.
+struct WorkerGuard(Worker);
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        self.0.terminate();
+    }
+}
+
+/// A blob URL of the worker script, created once per page.
+fn worker_script_url() -> Result<String, JsValue> {
+    WORKER_SCRIPT_URL.with(|cached| {
+        if let Some(url) = cached.get() {
+            return Ok(url.clone());
+        }
+        let glue_url = GLUE_URL.with(|url| Value::from(url.as_str()).to_string());
+        let script = WORKER_SCRIPT.replace("GLUE_URL", &glue_url);
+        let options = BlobPropertyBag::new();
+        options.set_type("text/javascript");
+        let blob = Blob::new_with_str_sequence_and_options(&Array::of1(&script.into()), &options)?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+        let _ = cached.set(url.clone());
+        Ok(url)
+    })
+}
+
+fn spawn_worker() -> Result<Worker, JsValue> {
+    let options = WorkerOptions::new();
+    options.set_type(WorkerType::Module);
+    Worker::new_with_options(&worker_script_url()?, &options)
+}
+
+/// Runs `task` on `table` in a fresh worker, serving the worker's reads from the table's store.
+pub(crate) async fn run_in_worker(
+    table: &ParquetResolved,
+    task: &Value,
+) -> Result<Value, WorkerError> {
+    let store = table
+        .object_store(&SESSION_CTX)
+        .map_err(|e| WorkerError::Unavailable(e.to_string()))?;
+    let message = json!({
+        "file_name": format!("{}.parquet", table.table_name()),
+        "path": table.path().as_ref(),
+        "size": table.metadata().file_size,
+        "task": task,
+    });
+    let worker =
+        WorkerGuard(spawn_worker().map_err(|e| WorkerError::Unavailable(format!("{e:?}")))?);
+
+    let (sender, receiver) = oneshot::channel::<Result<Value, WorkerError>>();
+    let sender = Rc::new(RefCell::new(Some(sender)));
+    let finish = move |result| {
+        if let Some(sender) = sender.borrow_mut().take() {
+            let _ = sender.send(result);
+        }
+    };
+
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+        let finish = finish.clone();
+        let worker = worker.0.clone();
+        let path = table.path().clone();
+        move |event: MessageEvent| {
+            let data = Array::from(&event.data());
+            match data.get(0).as_string().as_deref() {
+                Some("read") => {
+                    let id = data.get(1);
+                    let start = data.get(2).as_f64().unwrap_or_default() as u64;
+                    let end = data.get(3).as_f64().unwrap_or_default() as u64;
+                    let store = store.clone();
+                    let worker = worker.clone();
+                    let path = path.clone();
+                    spawn_local(async move {
+                        let _ = match store.get_range(&path, start..end).await {
+                            Ok(bytes) => {
+                                let bytes = Uint8Array::from(bytes.as_ref());
+                                worker.post_message_with_transfer(
+                                    &Array::of2(&id, &bytes),
+                                    &Array::of1(&bytes.buffer()),
+                                )
+                            }
+                            Err(e) => worker
+                                .post_message(&Array::of2(&id, &JsValue::from_str(&e.to_string()))),
+                        };
+                    });
+                }
+                Some("result") => finish(
+                    serde_json::from_str(&data.get(1).as_string().unwrap_or_default())
+                        .map_err(|e| WorkerError::Task(e.to_string())),
+                ),
+                Some("error") => finish(Err(WorkerError::Task(
+                    data.get(1).as_string().unwrap_or_default(),
+                ))),
+                _ => {}
+            }
+        }
+    });
+    // Module scripts that fail to load or instantiate report a plain `Event`.
+    let on_error = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+        let message = event
+            .dyn_ref::<ErrorEvent>()
+            .map(|event| event.message())
+            .unwrap_or_else(|| "the worker failed to start".to_string());
+        finish(Err(WorkerError::Unavailable(message)));
+    });
+    worker
+        .0
+        .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    worker
+        .0
+        .set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    worker
+        .0
+        .post_message(&Array::of2(
+            &wasm_bindgen::module(),
+            &message.to_string().into(),
+        ))
+        .map_err(|e| WorkerError::Unavailable(format!("{e:?}")))?;
+    receiver
+        .await
+        .unwrap_or_else(|_| Err(WorkerError::Unavailable("the worker went away".to_string())))
+}
+
+fn worker_scope() -> DedicatedWorkerGlobalScope {
+    web_sys::js_sys::global().unchecked_into()
+}
+
+/// Entry point of the worker, called by the worker script with the task posted by
+/// [`run_in_worker`]. Posts the task's result back to the page.
+#[wasm_bindgen]
+pub async fn run_worker_task(message: String) {
+    let scope = worker_scope();
+    let on_reply = Closure::<dyn FnMut(MessageEvent)>::new(|event: MessageEvent| {
+        deliver_page_reply(&event);
+    });
+    scope.set_onmessage(Some(on_reply.as_ref().unchecked_ref()));
+    on_reply.forget();
+
+    let reply = match run_posted_task(&message).await {
+        Ok(value) => Array::of2(&"result".into(), &value.to_string().into()),
+        Err(e) => Array::of2(&"error".into(), &format!("{e:#}").into()),
+    };
+    let _ = scope.post_message(&reply);
+}
+
+async fn run_posted_task(message: &str) -> Result<Value> {
+    let message: Value = serde_json::from_str(message)?;
+    let file_name = message["file_name"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Worker task without a file name"))?;
+    let path = Path::parse(
+        message["path"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Worker task without a path"))?,
+    )?;
+    let size = message["size"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("Worker task without a file size"))?;
+
+    let store = Arc::new(PageRangeStore {
+        location: path.clone(),
+        size,
+    });
+    let table = ParquetUnresolved::try_new(
+        file_name.to_string(),
+        path,
+        ObjectStoreUrl::parse(PAGE_STORE_URL)?,
+        store,
+    )?
+    .try_into_resolved(&SESSION_CTX)
+    .await?;
+    run_schema_task(Arc::new(table), &message["task"]).await
+}
+
+/// Resolves the pending read that a page reply `[id, bytes | error]` answers.
+fn deliver_page_reply(event: &MessageEvent) {
+    let reply = Array::from(&event.data());
+    let Some(id) = reply.get(0).as_f64() else {
+        return;
+    };
+    let Some(sender) = PENDING_READS.with_borrow_mut(|pending| pending.remove(&(id as u32))) else {
+        return;
+    };
+    let result = match reply.get(1).dyn_into::<Uint8Array>() {
+        Ok(bytes) => Ok(Bytes::from(bytes.to_vec())),
+        Err(error) => Err(error.as_string().unwrap_or_else(|| format!("{error:?}"))),
+    };
+    let _ = sender.send(result);
+}
+
+/// Asks the page for `range` of its file and waits for [`deliver_page_reply`].
+async fn read_from_page(range: Range<u64>) -> Result<Bytes, String> {
+    let id = NEXT_READ_ID.with(|next| {
+        let id = next.get();
+        next.set(id.wrapping_add(1));
+        id
+    });
+    let (sender, receiver) = oneshot::channel();
+    PENDING_READS.with_borrow_mut(|pending| pending.insert(id, sender));
+    worker_scope()
+        .post_message(&Array::of4(
+            &"read".into(),
+            &id.into(),
+            &(range.start as f64).into(),
+            &(range.end as f64).into(),
+        ))
+        .map_err(|e| format!("Failed to request {range:?} from the page: {e:?}"))?;
+    receiver
+        .await
+        .map_err(|_| "The page dropped the read".to_string())?
+}
+
+/// The page's file as seen from the worker; every range is read by the page.
+#[derive(Debug)]
+struct PageRangeStore {
+    location: Path,
+    size: u64,
+}
+
+impl Display for PageRangeStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PageRangeStore({})", self.location)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for PageRangeStore {
+    async fn put_opts(
+        &self,
+        _location: &Path,
+        _payload: PutPayload,
+        _opts: PutOptions,
+    ) -> Result<PutResult, ObjectStoreError> {
+        unreachable!()
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        _location: &Path,
+        _opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>, ObjectStoreError> {
+        unreachable!()
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> Result<GetResult, ObjectStoreError> {
+        let meta = self.head(location).await?;
+        if options.head {
+            return Ok(GetResult {
+                payload: GetResultPayload::Stream(Box::pin(futures::stream::empty())),
+                range: 0..0,
+                meta,
+                attributes: Default::default(),
+            });
+        }
+
+        let range = resolve_get_range(options.range, meta.size);
+        let range_for_stream = range.clone();
+        let stream = futures::stream::once(async move {
+            let result_bytes = SendWrapper {
+                inner: read_from_page(range_for_stream),
+            }
+            .await;
+
+            result_bytes.map_err(|e| ObjectStoreError::Generic {
+                store: "PageRangeStore",
+                source: anyhow!(e).into(),
+            })
+        });
+
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(Box::pin(stream)),
+            range,
+            meta,
+            attributes: Default::default(),
+        })
+    }
+
+    async fn head(&self, _location: &Path) -> Result<ObjectMeta, ObjectStoreError> {
+        Ok(ObjectMeta {
+            location: self.location.clone(),
+            last_modified: Default::default(),
+            size: self.size,
+            e_tag: None,
+            version: None,
+        })
+    }
+
+    async fn delete(&self, _location: &Path) -> Result<(), ObjectStoreError> {
+        unreachable!()
+    }
+
+    fn list(
+        &self,
+        _prefix: Option<&Path>,
+    ) -> BoxStream<'static, Result<ObjectMeta, ObjectStoreError>> {
+        unreachable!()
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        _prefix: Option<&Path>,
+    ) -> Result<ListResult, ObjectStoreError> {
+        unreachable!()
+    }
+
+    async fn copy(&self, _from: &Path, _to: &Path) -> Result<(), ObjectStoreError> {
+        unreachable!()
+    }
+
+    async fn copy_if_not_exists(&self, _from: &Path, _to: &Path) -> Result<(), ObjectStoreError> {
+        unreachable!()
+    }
+}